@@ -1,32 +1,291 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::io::Write;
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use arboard::Clipboard;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use zeroize::Zeroizing;
 
 use crate::error::{Result, VaulturaError};
 
-pub struct ClipboardManager {
-    clear_seconds: u64,
+/// Graphical session the system clipboard is reached through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplaySession {
+    Wayland,
+    X11,
+    /// macOS / Windows, where the clipboard does not depend on a display server.
+    Native,
+    Headless,
+}
+
+impl DisplaySession {
+    pub fn detect() -> Self {
+        if cfg!(any(target_os = "macos", windows)) {
+            return Self::Native;
+        }
+        let wayland = std::env::var("WAYLAND_DISPLAY").ok();
+        let display = std::env::var("DISPLAY").ok();
+        Self::from_env(wayland.as_deref(), display.as_deref())
+    }
+
+    /// Classify a Unix session from `$WAYLAND_DISPLAY` and `$DISPLAY`.
+    fn from_env(wayland_display: Option<&str>, display: Option<&str>) -> Self {
+        let set = |v: Option<&str>| v.is_some_and(|s| !s.is_empty());
+        if set(wayland_display) {
+            Self::Wayland
+        } else if set(display) {
+            Self::X11
+        } else {
+            Self::Headless
+        }
+    }
+
+    /// Actionable explanation for a clipboard that failed to initialize.
+    pub fn unavailable_message(self) -> &'static str {
+        match self {
+            Self::Wayland => "No clipboard backend available — is wl-clipboard installed?",
+            Self::X11 => "No clipboard backend available — is the X server on $DISPLAY reachable?",
+            Self::Native => "No clipboard backend available",
+            Self::Headless => {
+                "No clipboard backend available — neither $WAYLAND_DISPLAY nor $DISPLAY is set"
+            }
+        }
+    }
+}
+
+/// Result of probing the clipboard at startup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardStatus {
+    Ready(DisplaySession),
+    /// The system clipboard is unavailable; copies go through the terminal (OSC 52).
+    Osc52Fallback(DisplaySession),
+    Unavailable(DisplaySession),
+}
+
+impl ClipboardStatus {
+    fn derive(session: DisplaySession, backend_ok: bool, osc52_fallback: bool) -> Self {
+        match (backend_ok, osc52_fallback) {
+            (true, _) => Self::Ready(session),
+            (false, true) => Self::Osc52Fallback(session),
+            (false, false) => Self::Unavailable(session),
+        }
+    }
+
+    /// Warning to surface to the user, if the clipboard is not fully working.
+    pub fn warning(&self) -> Option<String> {
+        match self {
+            Self::Ready(_) => None,
+            Self::Osc52Fallback(session) => Some(format!(
+                "{}; using terminal clipboard (OSC 52)",
+                session.unavailable_message()
+            )),
+            Self::Unavailable(session) => Some(session.unavailable_message().to_string()),
+        }
+    }
+}
+
+/// Where copied text goes; the system clipboard outside of tests.
+pub trait ClipboardBackend: Send + Sync + 'static {
+    /// Replace the clipboard contents, or describe why that was not possible.
+    fn set_text(&self, text: &str) -> std::result::Result<(), String>;
+
+    /// Current clipboard contents, or why they could not be read.
+    fn get_text(&self) -> std::result::Result<String, String>;
+
+    /// Whether the clipboard can be reached at all.
+    fn is_available(&self) -> bool;
+}
+
+/// The desktop clipboard, through arboard.
+pub struct SystemClipboard;
+
+impl ClipboardBackend for SystemClipboard {
+    fn set_text(&self, text: &str) -> std::result::Result<(), String> {
+        Clipboard::new()
+            .and_then(|mut clipboard| clipboard.set_text(text))
+            .map_err(|e| e.to_string())
+    }
+
+    fn get_text(&self) -> std::result::Result<String, String> {
+        Clipboard::new()
+            .and_then(|mut clipboard| clipboard.get_text())
+            .map_err(|e| e.to_string())
+    }
+
+    fn is_available(&self) -> bool {
+        Clipboard::new().is_ok()
+    }
+}
+
+impl ClipboardBackend for Box<dyn ClipboardBackend> {
+    fn set_text(&self, text: &str) -> std::result::Result<(), String> {
+        (**self).set_text(text)
+    }
+
+    fn get_text(&self) -> std::result::Result<String, String> {
+        (**self).get_text()
+    }
+
+    fn is_available(&self) -> bool {
+        (**self).is_available()
+    }
+}
+
+/// In-memory clipboard whose contents a test can inspect after handing it over.
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub(crate) struct MemoryClipboard {
+    contents: Arc<Mutex<String>>,
+    broken: bool,
+}
+
+#[cfg(test)]
+impl MemoryClipboard {
+    pub(crate) fn contents(&self) -> String {
+        self.contents.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+impl ClipboardBackend for MemoryClipboard {
+    fn set_text(&self, text: &str) -> std::result::Result<(), String> {
+        if self.broken {
+            return Err("no clipboard".to_string());
+        }
+        *self.contents.lock().unwrap() = text.to_string();
+        Ok(())
+    }
+
+    fn get_text(&self) -> std::result::Result<String, String> {
+        if self.broken {
+            return Err("no clipboard".to_string());
+        }
+        Ok(self.contents())
+    }
+
+    fn is_available(&self) -> bool {
+        !self.broken
+    }
+}
+
+pub struct ClipboardManager<B: ClipboardBackend = SystemClipboard> {
+    backend: Arc<B>,
+    clear_after: Duration,
     /// Tracks the generation count so stale clear-threads don't wipe newer clipboard content.
     generation: Arc<Mutex<u64>>,
+    /// Set while a copied secret is waiting for its auto-clear.
+    pending_clear: Arc<Mutex<Option<PendingClear>>>,
+    /// Fall back to the OSC 52 terminal escape when the system clipboard is unavailable.
+    osc52_fallback: bool,
+    /// What a cleared clipboard is set to; empty by default.
+    clear_value: String,
+    /// Leave the clipboard alone when it no longer holds the secret we copied.
+    clear_only_if_ours: bool,
+    /// Keyed per manager, so fingerprints of copied secrets are not comparable elsewhere.
+    hasher: RandomState,
+    /// When a clear last actually reset the clipboard, from a timer or `clear_pending`.
+    last_cleared: Arc<Mutex<Option<Instant>>>,
+}
+
+/// A copied secret waiting to be cleared.
+#[derive(Debug, Clone, Copy)]
+struct PendingClear {
+    via_osc52: bool,
+    /// Hash of the copied text, to recognise it on the clipboard without keeping it.
+    fingerprint: u64,
 }
 
 impl ClipboardManager {
     pub fn new(clear_seconds: u64) -> Self {
+        Self::with_backend(SystemClipboard, Duration::from_secs(clear_seconds))
+    }
+}
+
+impl<B: ClipboardBackend> ClipboardManager<B> {
+    pub fn with_backend(backend: B, clear_after: Duration) -> Self {
         Self {
-            clear_seconds,
+            backend: Arc::new(backend),
+            clear_after,
             generation: Arc::new(Mutex::new(0)),
+            pending_clear: Arc::new(Mutex::new(None)),
+            osc52_fallback: false,
+            clear_value: String::new(),
+            clear_only_if_ours: true,
+            hasher: RandomState::new(),
+            last_cleared: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn set_osc52_fallback(&mut self, enabled: bool) {
+        self.osc52_fallback = enabled;
+    }
+
+    pub fn set_clear_value(&mut self, value: String) {
+        self.clear_value = value;
+    }
+
+    /// With `true` (the default), a clear is skipped when something else has been copied
+    /// since; `false` clears regardless.
+    pub fn set_clear_only_if_ours(&mut self, enabled: bool) {
+        self.clear_only_if_ours = enabled;
+    }
+
+    /// When vaultura last cleared a copied secret; skipped clears do not count.
+    pub fn last_cleared(&self) -> Option<Instant> {
+        *self.last_cleared.lock().unwrap()
+    }
+
+    /// Check whether the system clipboard can be opened in this session.
+    pub fn probe(&self) -> ClipboardStatus {
+        ClipboardStatus::derive(
+            DisplaySession::detect(),
+            self.backend.is_available(),
+            self.osc52_fallback,
+        )
+    }
+
+    /// Copy text to the clipboard without scheduling a clear.
+    pub fn copy(&self, text: &str) -> Result<()> {
+        self.set_text(text)?;
+        // The secret we were going to clear has just been overwritten.
+        *self.generation.lock().unwrap() += 1;
+        *self.pending_clear.lock().unwrap() = None;
+        Ok(())
+    }
+
+    /// Clear a copied secret now instead of waiting for its timer.
+    ///
+    /// Does nothing (and returns false) when no copied secret is pending, so text the
+    /// user copied elsewhere or non-secret copies are left alone. The same goes for a
+    /// pending secret that something outside vaultura has since replaced.
+    pub fn clear_pending(&self) -> Result<bool> {
+        let Some(pending) = self.pending_clear.lock().unwrap().take() else {
+            return Ok(false);
+        };
+        *self.generation.lock().unwrap() += 1;
+        if pending.via_osc52 {
+            set_osc52_text(&self.clear_value)?;
+        } else {
+            if self.clear_only_if_ours
+                && !still_ours(self.backend.as_ref(), &self.hasher, pending.fingerprint)
+            {
+                return Ok(false);
+            }
+            self.backend
+                .set_text(&self.clear_value)
+                .map_err(VaulturaError::Clipboard)?;
         }
+        *self.last_cleared.lock().unwrap() = Some(Instant::now());
+        Ok(true)
     }
 
-    /// Copy text to clipboard and schedule an auto-clear after `clear_seconds`.
+    /// Copy text to clipboard and schedule an auto-clear after the clear interval.
     pub fn copy_and_clear(&self, text: &str) -> Result<()> {
-        let mut clipboard =
-            Clipboard::new().map_err(|e| VaulturaError::Clipboard(e.to_string()))?;
-        clipboard
-            .set_text(text)
-            .map_err(|e| VaulturaError::Clipboard(e.to_string()))?;
+        let via_osc52 = self.set_text(text)?;
 
         let gen = {
             let mut g = self.generation.lock().unwrap();
@@ -34,19 +293,250 @@ impl ClipboardManager {
             *g
         };
 
-        let clear_seconds = self.clear_seconds;
+        let fingerprint = self.hasher.hash_one(text);
+        *self.pending_clear.lock().unwrap() = Some(PendingClear {
+            via_osc52,
+            fingerprint,
+        });
+
+        let clear_after = self.clear_after;
+        let clear_value = self.clear_value.clone();
+        let clear_only_if_ours = self.clear_only_if_ours;
+        let hasher = self.hasher.clone();
+        let backend = Arc::clone(&self.backend);
         let generation = Arc::clone(&self.generation);
+        let pending_clear = Arc::clone(&self.pending_clear);
+        let last_cleared = Arc::clone(&self.last_cleared);
 
         thread::spawn(move || {
-            thread::sleep(Duration::from_secs(clear_seconds));
+            thread::sleep(clear_after);
             let current_gen = *generation.lock().unwrap();
             if current_gen == gen {
-                if let Ok(mut cb) = Clipboard::new() {
-                    let _ = cb.set_text("");
+                *pending_clear.lock().unwrap() = None;
+                let cleared = if via_osc52 {
+                    set_osc52_text(&clear_value).is_ok()
+                } else if !clear_only_if_ours || still_ours(backend.as_ref(), &hasher, fingerprint)
+                {
+                    backend.set_text(&clear_value).is_ok()
+                } else {
+                    false
+                };
+                if cleared {
+                    *last_cleared.lock().unwrap() = Some(Instant::now());
                 }
             }
         });
 
         Ok(())
     }
+
+    /// Set the clipboard, returning whether the OSC 52 fallback was used.
+    fn set_text(&self, text: &str) -> Result<bool> {
+        match self.backend.set_text(text) {
+            Ok(()) => Ok(false),
+            Err(_) if self.osc52_fallback => set_osc52_text(text).map(|()| true),
+            Err(e) => Err(VaulturaError::Clipboard(format!(
+                "{} ({e})",
+                DisplaySession::detect().unavailable_message()
+            ))),
+        }
+    }
+}
+
+/// Whether the clipboard still holds the text with `fingerprint`. An unreadable
+/// clipboard counts as ours, so a secret is never left behind for lack of a check.
+fn still_ours<B: ClipboardBackend>(backend: &B, hasher: &RandomState, fingerprint: u64) -> bool {
+    match backend.get_text() {
+        // Whatever is on the clipboard may be someone else's secret; wipe it once hashed.
+        Ok(current) => hasher.hash_one(Zeroizing::new(current).as_str()) == fingerprint,
+        Err(_) => true,
+    }
+}
+
+/// Ask the terminal to set its clipboard via the OSC 52 escape sequence.
+fn set_osc52_text(text: &str) -> Result<()> {
+    let sequence = format!("\x1b]52;c;{}\x07", BASE64.encode(text));
+    let mut stdout = std::io::stdout().lock();
+    stdout
+        .write_all(sequence.as_bytes())
+        .and_then(|()| stdout.flush())
+        .map_err(|e| VaulturaError::Clipboard(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn manager(clear_after_ms: u64) -> (MemoryClipboard, ClipboardManager<MemoryClipboard>) {
+        let memory = MemoryClipboard::default();
+        let manager =
+            ClipboardManager::with_backend(memory.clone(), Duration::from_millis(clear_after_ms));
+        (memory, manager)
+    }
+
+    /// Wait up to a second for `done`, so slow machines don't fail the timing tests.
+    fn wait_for(done: impl Fn() -> bool) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(1);
+        while Instant::now() < deadline {
+            if done() {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        done()
+    }
+
+    #[test]
+    fn test_copy_and_clear_sets_then_clears() {
+        let (memory, manager) = manager(20);
+        manager.copy_and_clear("hunter2").unwrap();
+        assert_eq!(memory.contents(), "hunter2");
+
+        assert!(wait_for(|| memory.contents().is_empty()));
+        // Once the timer has fired there is nothing left to clear.
+        assert!(wait_for(|| !manager.clear_pending().unwrap()));
+    }
+
+    #[test]
+    fn test_newer_copy_is_not_cleared_by_stale_timer() {
+        let (memory, manager) = manager(30);
+        manager.copy_and_clear("old secret").unwrap();
+        manager.copy("https://example.com").unwrap();
+
+        thread::sleep(Duration::from_millis(120));
+        assert_eq!(memory.contents(), "https://example.com");
+        assert!(!manager.clear_pending().unwrap());
+    }
+
+    #[test]
+    fn test_clear_pending_clears_only_a_pending_secret() {
+        let (memory, manager) = manager(60_000);
+        manager.copy("not secret").unwrap();
+        assert!(!manager.clear_pending().unwrap());
+        assert_eq!(memory.contents(), "not secret");
+
+        manager.copy_and_clear("secret").unwrap();
+        assert!(manager.clear_pending().unwrap());
+        assert_eq!(memory.contents(), "");
+    }
+
+    #[test]
+    fn test_clear_skipped_when_someone_else_copied() {
+        let (memory, timed) = manager(20);
+        timed.copy_and_clear("hunter2").unwrap();
+        // Another application copies over the secret before the timer fires.
+        memory.set_text("notes from elsewhere").unwrap();
+        thread::sleep(Duration::from_millis(120));
+        assert_eq!(memory.contents(), "notes from elsewhere");
+
+        // Nor does clearing on lock touch it.
+        let (memory, locking) = manager(60_000);
+        locking.copy_and_clear("hunter2").unwrap();
+        memory.set_text("notes from elsewhere").unwrap();
+        assert!(!locking.clear_pending().unwrap());
+        assert_eq!(memory.contents(), "notes from elsewhere");
+    }
+
+    #[test]
+    fn test_completed_clear_records_when() {
+        let (memory, timed) = manager(20);
+        assert!(timed.last_cleared().is_none());
+        let before = Instant::now();
+        timed.copy_and_clear("hunter2").unwrap();
+        assert!(wait_for(|| timed.last_cleared().is_some()));
+        assert!(timed.last_cleared().unwrap() >= before);
+        assert!(memory.contents().is_empty());
+
+        // A clear skipped because the clipboard changed is not recorded.
+        let (memory, locking) = manager(60_000);
+        locking.copy_and_clear("hunter2").unwrap();
+        memory.set_text("notes from elsewhere").unwrap();
+        assert!(!locking.clear_pending().unwrap());
+        assert!(locking.last_cleared().is_none());
+
+        locking.copy_and_clear("hunter2").unwrap();
+        assert!(locking.clear_pending().unwrap());
+        assert!(locking.last_cleared().is_some());
+    }
+
+    #[test]
+    fn test_clear_regardless_when_check_disabled() {
+        let (memory, mut manager) = manager(20);
+        manager.set_clear_only_if_ours(false);
+        manager.copy_and_clear("hunter2").unwrap();
+        memory.set_text("notes from elsewhere").unwrap();
+        assert!(wait_for(|| memory.contents().is_empty()));
+    }
+
+    #[test]
+    fn test_clear_sets_configured_value() {
+        let (memory, mut manager) = manager(20);
+        manager.set_clear_value("[cleared]".to_string());
+
+        manager.copy_and_clear("hunter2").unwrap();
+        assert!(wait_for(|| memory.contents() == "[cleared]"));
+
+        manager.copy_and_clear("hunter2").unwrap();
+        manager.set_clear_value(" ".to_string());
+        assert!(manager.clear_pending().unwrap());
+        assert_eq!(memory.contents(), " ");
+    }
+
+    #[test]
+    fn test_unavailable_backend_reports_error() {
+        let memory = MemoryClipboard {
+            broken: true,
+            ..MemoryClipboard::default()
+        };
+        let manager = ClipboardManager::with_backend(memory, Duration::from_secs(1));
+        assert!(!matches!(manager.probe(), ClipboardStatus::Ready(_)));
+        assert!(matches!(
+            manager.copy("text"),
+            Err(VaulturaError::Clipboard(reason)) if reason.contains("no clipboard")
+        ));
+    }
+
+    #[test]
+    fn test_session_from_env() {
+        assert_eq!(
+            DisplaySession::from_env(Some("wayland-0"), Some(":0")),
+            DisplaySession::Wayland
+        );
+        assert_eq!(
+            DisplaySession::from_env(None, Some(":0")),
+            DisplaySession::X11
+        );
+        assert_eq!(
+            DisplaySession::from_env(Some(""), Some(":1")),
+            DisplaySession::X11
+        );
+        assert_eq!(
+            DisplaySession::from_env(None, None),
+            DisplaySession::Headless
+        );
+    }
+
+    #[test]
+    fn test_status_messages() {
+        let wayland = DisplaySession::from_env(Some("wayland-0"), None);
+
+        let status = ClipboardStatus::derive(wayland, true, false);
+        assert_eq!(status, ClipboardStatus::Ready(DisplaySession::Wayland));
+        assert_eq!(status.warning(), None);
+
+        let status = ClipboardStatus::derive(wayland, false, false);
+        assert_eq!(
+            status.warning().unwrap(),
+            "No clipboard backend available — is wl-clipboard installed?"
+        );
+
+        let status = ClipboardStatus::derive(wayland, false, true);
+        assert!(matches!(status, ClipboardStatus::Osc52Fallback(_)));
+        assert!(status.warning().unwrap().contains("OSC 52"));
+
+        let headless = DisplaySession::from_env(None, None);
+        let status = ClipboardStatus::derive(headless, false, false);
+        assert!(status.warning().unwrap().contains("$DISPLAY"));
+    }
 }
@@ -1,19 +1,183 @@
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Once;
 
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
+use crate::core::password_generator;
+use crate::core::template::DEFAULT_ITEM_TEMPLATE;
 use crate::error::{Result, VaulturaError};
+use crate::storage::atomic;
+
+/// Where a saved filter applies when it is picked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterScope {
+    /// Switch to "All Items" before filtering.
+    #[default]
+    All,
+    /// Filter within whichever group is currently selected.
+    CurrentGroup,
+}
+
+/// What Enter does on a selected item in the items list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemEnterAction {
+    /// Show the details panel across the whole window.
+    #[default]
+    Details,
+    /// Open the edit form.
+    Edit,
+    /// Copy the password.
+    CopyPassword,
+}
+
+/// Which status-bar messages are shown; everything is still recorded in the event log.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusVerbosity {
+    #[default]
+    Verbose,
+    /// Hide info, success and warning messages.
+    ErrorsOnly,
+    /// Hide every message.
+    Silent,
+}
+
+/// A named search that can be re-applied from the quick-pick modal (Ctrl+F).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedFilter {
+    pub name: String,
+    #[serde(default)]
+    pub query: String,
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub scope: FilterScope,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     pub vault_path: PathBuf,
     pub auto_lock_secs: u64,
+    /// Ask before auto-lock saves unsaved changes, saving anyway if nobody answers.
+    pub confirm_autolock_save: bool,
     pub clipboard_clear_secs: u64,
     pub kdf_memory_cost_kib: u32,
     pub kdf_time_cost: u32,
     pub kdf_parallelism: u32,
+    /// Mask every sensitive field in the details panel while it is not focused.
+    pub mask_details_when_unfocused: bool,
+    /// Show how many characters a masked password has next to the mask.
+    pub show_password_length: bool,
+    /// Reveal the password only for this many milliseconds per press (0 = toggle).
+    pub reveal_flash_ms: u64,
+    /// Seconds after the username is copied before `c` swaps in the password (0 = next press).
+    pub credential_sequence_delay_secs: u64,
+    /// Ask for the master password again before revealing a stored password.
+    pub require_password_to_reveal: bool,
+    /// How long a successful re-authentication stays valid (0 = ask on every reveal).
+    pub reveal_reauth_window_secs: u64,
+    /// Ask for the master password before every reveal or copy of a secret.
+    pub seal_mode: bool,
+    /// Copy through the terminal (OSC 52) when no system clipboard is available.
+    pub clipboard_osc52_fallback: bool,
+    /// Ask for confirmation before copying a username or password.
+    #[serde(alias = "confirm_before_copy")]
+    pub confirm_clipboard_copy: bool,
+    /// With `confirm_clipboard_copy`, ask only for secrets, not usernames or URLs.
+    pub confirm_copy_secrets_only: bool,
+    /// Ask before Esc closes an item or group form with unsaved edits.
+    pub confirm_discard_form: bool,
+    /// Named searches offered by the Ctrl+F quick-pick.
+    pub saved_filters: Vec<SavedFilter>,
+    /// Drop the active search when another group is selected.
+    pub clear_search_on_group_change: bool,
+    /// Length change for Shift+Left/Right and PageUp/PageDown in the password generator.
+    pub generator_length_step: usize,
+    /// Characters the generator leaves out when "Exclude ambiguous" is on.
+    pub generator_ambiguous_chars: String,
+    /// Let Ctrl+R on the lock screen show the master password being typed.
+    pub allow_show_master: bool,
+    /// Words in the master passphrase suggested when a vault is created.
+    pub passphrase_words: usize,
+    /// Lock the vault after this many failed reveal re-authentications (0 = never).
+    pub max_reveal_reauth_failures: u32,
+    /// Vault opened when the master password does not match but unlocks this file instead.
+    pub decoy_vault_path: Option<PathBuf>,
+    /// Template for copying a whole item with `y`; see `core::template::format_item`.
+    pub item_copy_template: String,
+    /// Save after every create, update or delete (false = only Ctrl+S, lock and quit save).
+    pub autosave: bool,
+    /// Re-select the group and item that were selected when the vault was last locked.
+    pub restore_last_selection: bool,
+    /// Remembered for `restore_last_selection`; written on lock and quit.
+    pub last_selected_group: Option<Uuid>,
+    pub last_selected_item: Option<Uuid>,
+    /// Largest file that can be embedded in an item.
+    pub max_embedded_file_bytes: u64,
+    /// Warn once embedded files in the vault add up to more than this.
+    pub embedded_warn_total_bytes: u64,
+    /// Email that Ctrl+U in the Username field turns into a `base+site@domain` alias.
+    pub username_alias_base: Option<String>,
+    /// Length of random handles from Ctrl+U when no alias base is set.
+    pub username_handle_length: usize,
+    /// Strip trailing whitespace from text before it is copied.
+    pub trim_on_copy: bool,
+    /// Strip leading and trailing whitespace from usernames and passwords when an item is saved.
+    pub trim_on_save: bool,
+    /// List items with a TOTP secret before the others.
+    pub sort_totp_first: bool,
+    /// Moving past the end of the groups or items list wraps to the other end.
+    pub list_wraparound: bool,
+    /// Order of the item form's fields by name; empty for the default order.
+    pub item_form_field_order: Vec<String>,
+    /// What Enter does on an item in the items list.
+    pub item_enter_action: ItemEnterAction,
+    /// List groups by name rather than in the order they were created.
+    pub sort_groups_alphabetically: bool,
+    /// Re-read and decrypt the vault after each save, keeping a backup if it does not verify.
+    pub verify_after_write: bool,
+    /// Keep the password field (only) of each item encrypted in memory, decrypting it only when used.
+    pub seal_passwords_in_memory: bool,
+    /// Pad the encrypted vault to a multiple of this many bytes so its size hides the item count (0 = off).
+    pub pad_to_block: usize,
+    /// Below this terminal size a "too small" message replaces the UI.
+    pub min_terminal_width: u16,
+    pub min_terminal_height: u16,
+    /// Single key that locks the vault from the main screen, in addition to Ctrl+L.
+    pub lock_key: Option<char>,
+    /// Warn after unlocking a vault whose key derivation is weaker than recommended.
+    pub warn_weak_kdf: bool,
+    /// Allow items' launch commands to be run from the details panel.
+    pub enable_launch_commands: bool,
+    /// Text the clipboard is set to when a copied secret is cleared.
+    pub clipboard_clear_value: String,
+    /// Skip the clear when the clipboard no longer holds the copied secret.
+    pub clipboard_clear_only_if_ours: bool,
+    /// Keep a copy of the vault from before each import so the import can be undone.
+    pub backup_before_import: bool,
+    /// Strip invisible characters and smart quotes from usernames and passwords imported from CSV or KeePass.
+    pub sanitize_imports: bool,
+    /// Most items the vault may hold; creation past it fails and imports stop there.
+    pub max_items: Option<usize>,
+    /// Most groups the vault may hold; creation past it fails and imports stop there.
+    pub max_groups: Option<usize>,
+    /// Save a tag typed in the item form with the casing it already has in the vault.
+    pub normalize_tag_case: bool,
+    /// Group new items start in, instead of the selected one.
+    pub default_group_name: Option<String>,
+    /// Keep the selected group for new items even when `default_group_name` is set.
+    pub prefer_selected_group: bool,
+    /// Command whose output unlocks the vault at startup instead of the password prompt.
+    pub master_password_command: Option<String>,
+    /// Which messages reach the status bar.
+    pub status_verbosity: StatusVerbosity,
 }
 
 impl Default for AppConfig {
@@ -21,10 +185,65 @@ impl Default for AppConfig {
         Self {
             vault_path: default_vault_path(),
             auto_lock_secs: 300,
+            confirm_autolock_save: false,
             clipboard_clear_secs: 30,
             kdf_memory_cost_kib: 65536,
             kdf_time_cost: 3,
             kdf_parallelism: 4,
+            mask_details_when_unfocused: false,
+            show_password_length: false,
+            reveal_flash_ms: 0,
+            credential_sequence_delay_secs: 0,
+            require_password_to_reveal: false,
+            reveal_reauth_window_secs: 300,
+            seal_mode: false,
+            clipboard_osc52_fallback: false,
+            confirm_clipboard_copy: false,
+            confirm_copy_secrets_only: false,
+            confirm_discard_form: true,
+            saved_filters: Vec::new(),
+            clear_search_on_group_change: true,
+            generator_length_step: 8,
+            generator_ambiguous_chars: password_generator::DEFAULT_AMBIGUOUS.to_string(),
+            passphrase_words: 7,
+            allow_show_master: false,
+            max_reveal_reauth_failures: 3,
+            decoy_vault_path: None,
+            item_copy_template: DEFAULT_ITEM_TEMPLATE.to_string(),
+            autosave: true,
+            restore_last_selection: false,
+            last_selected_group: None,
+            last_selected_item: None,
+            max_embedded_file_bytes: 1024 * 1024,
+            embedded_warn_total_bytes: 10 * 1024 * 1024,
+            username_alias_base: None,
+            username_handle_length: 12,
+            trim_on_copy: false,
+            trim_on_save: false,
+            sort_totp_first: false,
+            list_wraparound: false,
+            item_enter_action: ItemEnterAction::Details,
+            item_form_field_order: Vec::new(),
+            sort_groups_alphabetically: false,
+            verify_after_write: true,
+            seal_passwords_in_memory: false,
+            pad_to_block: 0,
+            min_terminal_width: 60,
+            min_terminal_height: 20,
+            lock_key: None,
+            warn_weak_kdf: true,
+            enable_launch_commands: false,
+            clipboard_clear_value: String::new(),
+            clipboard_clear_only_if_ours: true,
+            backup_before_import: true,
+            sanitize_imports: true,
+            max_items: None,
+            max_groups: None,
+            normalize_tag_case: true,
+            default_group_name: None,
+            prefer_selected_group: false,
+            master_password_command: None,
+            status_verbosity: StatusVerbosity::Verbose,
         }
     }
 }
@@ -38,27 +257,94 @@ impl AppConfig {
         }
     }
 
+    /// Apply `VAULTURA_*` environment overrides, warning about (and ignoring) invalid values.
+    pub fn apply_env_overrides(&mut self) {
+        for warning in self.apply_overrides_from(|name| std::env::var(name).ok()) {
+            eprintln!("Warning: {warning}");
+        }
+    }
+
+    /// Apply overrides read through `lookup`; returns a warning for each rejected value.
+    fn apply_overrides_from(&mut self, lookup: impl Fn(&str) -> Option<String>) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let mut env = EnvReader {
+            lookup,
+            warnings: &mut warnings,
+        };
+
+        if let Some(path) = env.get::<PathBuf>("VAULTURA_VAULT_PATH", |p| !p.as_os_str().is_empty())
+        {
+            self.vault_path = path;
+        }
+        if let Some(v) = env.get("VAULTURA_AUTO_LOCK_SECS", |_| true) {
+            self.auto_lock_secs = v;
+        }
+        if let Some(v) = env.get("VAULTURA_CLIPBOARD_CLEAR_SECS", |_| true) {
+            self.clipboard_clear_secs = v;
+        }
+        if let Some(v) = env.get("VAULTURA_KDF_MEMORY_COST_KIB", |v| *v >= 8) {
+            self.kdf_memory_cost_kib = v;
+        }
+        if let Some(v) = env.get("VAULTURA_KDF_TIME_COST", |v| *v >= 1) {
+            self.kdf_time_cost = v;
+        }
+        if let Some(v) = env.get("VAULTURA_KDF_PARALLELISM", |v| *v >= 1) {
+            self.kdf_parallelism = v;
+        }
+        if let Some(v) = env.get_bool("VAULTURA_MASK_DETAILS_WHEN_UNFOCUSED") {
+            self.mask_details_when_unfocused = v;
+        }
+        if let Some(v) = env.get_bool("VAULTURA_REQUIRE_PASSWORD_TO_REVEAL") {
+            self.require_password_to_reveal = v;
+        }
+        if let Some(v) = env.get_bool("VAULTURA_CONFIRM_CLIPBOARD_COPY") {
+            self.confirm_clipboard_copy = v;
+        }
+
+        warnings
+    }
+
+    /// Load the config file, or the defaults if there is none. Never writes.
     pub fn load() -> Result<Self> {
-        let path = config_file_path();
+        Self::load_or_default(&config_file_path())
+    }
+
+    pub fn load_or_default(path: &Path) -> Result<Self> {
         if path.exists() {
-            let content = fs::read_to_string(&path)?;
-            let config: AppConfig = toml::from_str(&content)?;
-            Ok(config)
+            Self::load_from(path)
         } else {
-            let config = AppConfig::default();
-            config.save()?;
-            Ok(config)
+            Ok(AppConfig::default())
+        }
+    }
+
+    /// Write this config to `path` if no file exists there yet.
+    ///
+    /// Fails without touching the filesystem when the directory cannot be written,
+    /// so callers can keep running on the in-memory config and say why it was not saved.
+    pub fn ensure_persisted(&self, path: &Path) -> Result<()> {
+        if path.exists() {
+            return Ok(());
+        }
+        let dir = path.parent().unwrap_or(Path::new("."));
+        if !dir_writable(dir) {
+            return Err(VaulturaError::Config(format!(
+                "config not saved — {} is not writable",
+                dir.display()
+            )));
         }
+        self.save_to(path)
     }
 
     pub fn save(&self) -> Result<()> {
-        let path = config_file_path();
+        self.save_to(&config_file_path())
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
         let content = toml::to_string_pretty(self)?;
-        fs::write(&path, content)?;
-        Ok(())
+        atomic::write(path, content.as_bytes())
     }
 
     pub fn load_from(path: &Path) -> Result<Self> {
@@ -75,19 +361,89 @@ impl AppConfig {
     }
 }
 
-fn config_file_path() -> PathBuf {
-    if let Some(dirs) = ProjectDirs::from("", "", "vaultura") {
-        dirs.config_dir().join("config.toml")
-    } else {
-        PathBuf::from("vaultura.toml")
+struct EnvReader<'a, F> {
+    lookup: F,
+    warnings: &'a mut Vec<String>,
+}
+
+impl<F: Fn(&str) -> Option<String>> EnvReader<'_, F> {
+    fn get<T: FromStr>(&mut self, name: &str, valid: impl Fn(&T) -> bool) -> Option<T> {
+        let raw = (self.lookup)(name)?;
+        match raw.trim().parse::<T>() {
+            Ok(value) if valid(&value) => Some(value),
+            _ => {
+                self.warnings
+                    .push(format!("ignoring invalid {name}={raw:?}"));
+                None
+            }
+        }
+    }
+
+    fn get_bool(&mut self, name: &str) -> Option<bool> {
+        let raw = (self.lookup)(name)?;
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => Some(true),
+            "0" | "false" | "no" | "off" => Some(false),
+            _ => {
+                self.warnings
+                    .push(format!("ignoring invalid {name}={raw:?}"));
+                None
+            }
+        }
     }
 }
 
+/// Whether `dir` (or, if it does not exist yet, its nearest existing ancestor) can be written.
+fn dir_writable(dir: &Path) -> bool {
+    dir.ancestors()
+        .filter(|a| !a.as_os_str().is_empty())
+        .find(|a| a.exists())
+        .and_then(|a| fs::metadata(a).ok())
+        .is_some_and(|meta| meta.is_dir() && !meta.permissions().readonly())
+}
+
+pub fn config_file_path() -> PathBuf {
+    app_dirs().config_dir.join("config.toml")
+}
+
 fn default_vault_path() -> PathBuf {
-    if let Some(dirs) = ProjectDirs::from("", "", "vaultura") {
-        dirs.data_dir().join("vault.vltr")
-    } else {
-        PathBuf::from("vault.vltr")
+    app_dirs().data_dir.join("vault.vltr")
+}
+
+/// Directories holding the config file and the default vault.
+#[derive(Debug, PartialEq, Eq)]
+struct AppDirs {
+    config_dir: PathBuf,
+    data_dir: PathBuf,
+}
+
+fn app_dirs() -> AppDirs {
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from);
+    resolve_dirs(ProjectDirs::from("", "", "vaultura"), home)
+}
+
+/// Use the platform directories when available, otherwise `$HOME/.vaultura/` for both.
+fn resolve_dirs(project: Option<ProjectDirs>, home: Option<PathBuf>) -> AppDirs {
+    if let Some(dirs) = project {
+        return AppDirs {
+            config_dir: dirs.config_dir().to_path_buf(),
+            data_dir: dirs.data_dir().to_path_buf(),
+        };
+    }
+
+    let base = home.unwrap_or_default().join(".vaultura");
+    static WARNED: Once = Once::new();
+    WARNED.call_once(|| {
+        eprintln!(
+            "Warning: platform directories unavailable; using {} for config and vault",
+            base.display()
+        );
+    });
+    AppDirs {
+        config_dir: base.clone(),
+        data_dir: base,
     }
 }
 
@@ -108,6 +464,7 @@ mod tests {
             kdf_memory_cost_kib: 32768,
             kdf_time_cost: 2,
             kdf_parallelism: 2,
+            ..AppConfig::default()
         };
 
         let content = toml::to_string_pretty(&config).unwrap();
@@ -119,12 +476,183 @@ mod tests {
         assert_eq!(loaded.clipboard_clear_secs, config.clipboard_clear_secs);
     }
 
+    #[test]
+    fn test_save_is_atomic() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "vault_path = \"/old\"\n").unwrap();
+        // Leftovers of an interrupted save are replaced, never appended to the config.
+        fs::write(atomic::temp_path(&path), "vault_pa").unwrap();
+
+        let config = AppConfig {
+            vault_path: PathBuf::from("/tmp/new.vltr"),
+            ..AppConfig::default()
+        };
+        config.save_to(&path).unwrap();
+
+        assert!(!atomic::temp_path(&path).exists());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            toml::to_string_pretty(&config).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_missing_config_loads_defaults_without_writing() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("vaultura").join("config.toml");
+
+        let config = AppConfig::load_or_default(&path).unwrap();
+        assert_eq!(config.auto_lock_secs, AppConfig::default().auto_lock_secs);
+        assert!(!path.exists());
+
+        config.ensure_persisted(&path).unwrap();
+        assert!(path.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unwritable_config_dir_keeps_defaults_in_memory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let locked = dir.path().join("readonly");
+        fs::create_dir(&locked).unwrap();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o555)).unwrap();
+        let path = locked.join("vaultura").join("config.toml");
+
+        let config = AppConfig::load_or_default(&path).unwrap();
+        assert_eq!(config.vault_path, AppConfig::default().vault_path);
+
+        let err = config.ensure_persisted(&path).unwrap_err();
+        assert!(err.to_string().contains("not writable"), "{err}");
+        assert!(!locked.join("vaultura").exists());
+
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
     #[test]
     fn test_default_config() {
         let config = AppConfig::default();
         assert_eq!(config.auto_lock_secs, 300);
         assert_eq!(config.clipboard_clear_secs, 30);
         assert_eq!(config.kdf_memory_cost_kib, 65536);
+        assert!(!config.mask_details_when_unfocused);
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let config: AppConfig = toml::from_str("auto_lock_secs = 60").unwrap();
+        assert_eq!(config.auto_lock_secs, 60);
+        assert_eq!(config.clipboard_clear_secs, 30);
+        assert!(!config.mask_details_when_unfocused);
+    }
+
+    #[test]
+    fn test_confirm_before_copy_is_an_alias() {
+        let config: AppConfig = toml::from_str("confirm_before_copy = true").unwrap();
+        assert!(config.confirm_clipboard_copy);
+        assert!(!config.confirm_copy_secrets_only);
+    }
+
+    #[test]
+    fn test_saved_filters_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+
+        let config = AppConfig {
+            saved_filters: vec![
+                SavedFilter {
+                    name: "Work weak".to_string(),
+                    query: "weak".to_string(),
+                    tag: Some("work".to_string()),
+                    scope: FilterScope::All,
+                },
+                SavedFilter {
+                    name: "Here".to_string(),
+                    query: "admin".to_string(),
+                    tag: None,
+                    scope: FilterScope::CurrentGroup,
+                },
+            ],
+            ..AppConfig::default()
+        };
+        config.save_to(&path).unwrap();
+
+        let loaded = AppConfig::load_from(&path).unwrap();
+        assert_eq!(loaded.saved_filters, config.saved_filters);
+
+        // Only the name is required when written by hand.
+        let loaded: AppConfig = toml::from_str("[[saved_filters]]\nname = \"All\"").unwrap();
+        assert_eq!(loaded.saved_filters[0].query, "");
+        assert_eq!(loaded.saved_filters[0].scope, FilterScope::All);
+    }
+
+    #[test]
+    fn test_fallback_dirs_are_under_home() {
+        let dirs = resolve_dirs(None, Some(PathBuf::from("/home/alice")));
+        assert_eq!(dirs.config_dir, PathBuf::from("/home/alice/.vaultura"));
+        assert_eq!(dirs.data_dir, PathBuf::from("/home/alice/.vaultura"));
+
+        // Even without a home directory, files go into a dedicated folder.
+        let dirs = resolve_dirs(None, None);
+        assert_eq!(dirs.config_dir, PathBuf::from(".vaultura"));
+    }
+
+    #[test]
+    fn test_env_overrides_apply() {
+        let vars = [
+            ("VAULTURA_VAULT_PATH", "/srv/ci.vltr"),
+            ("VAULTURA_AUTO_LOCK_SECS", "45"),
+            ("VAULTURA_KDF_TIME_COST", " 2 "),
+            ("VAULTURA_CONFIRM_CLIPBOARD_COPY", "yes"),
+        ];
+        let mut config = AppConfig::default();
+        let warnings = config.apply_overrides_from(|name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        });
+
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert_eq!(config.vault_path, PathBuf::from("/srv/ci.vltr"));
+        assert_eq!(config.auto_lock_secs, 45);
+        assert_eq!(config.kdf_time_cost, 2);
+        assert!(config.confirm_clipboard_copy);
+        assert_eq!(config.clipboard_clear_secs, 30);
+    }
+
+    #[test]
+    fn test_invalid_env_overrides_are_ignored() {
+        let vars = [
+            ("VAULTURA_AUTO_LOCK_SECS", "soon"),
+            ("VAULTURA_KDF_PARALLELISM", "0"),
+            ("VAULTURA_MASK_DETAILS_WHEN_UNFOCUSED", "maybe"),
+            ("VAULTURA_CLIPBOARD_CLEAR_SECS", "10"),
+        ];
+        let mut config = AppConfig::default();
+        let warnings = config.apply_overrides_from(|name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        });
+
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].contains("VAULTURA_AUTO_LOCK_SECS"));
+        assert_eq!(config.auto_lock_secs, 300);
+        assert_eq!(config.kdf_parallelism, 4);
+        assert!(!config.mask_details_when_unfocused);
+        assert_eq!(config.clipboard_clear_secs, 10);
+    }
+
+    #[test]
+    fn test_apply_env_overrides_reads_process_env() {
+        std::env::set_var("VAULTURA_KDF_MEMORY_COST_KIB", "2048");
+        let mut config = AppConfig::default();
+        config.apply_env_overrides();
+        std::env::remove_var("VAULTURA_KDF_MEMORY_COST_KIB");
+
+        assert_eq!(config.kdf_memory_cost_kib, 2048);
     }
 
     #[test]
@@ -0,0 +1,136 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::core::models::VaultPayload;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Modified,
+    Removed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeTarget {
+    Group,
+    Item,
+}
+
+/// One difference between the open vault and its last saved state.
+///
+/// Only names and titles are reported, never field values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeSummary {
+    pub kind: ChangeKind,
+    pub target: ChangeTarget,
+    pub name: String,
+}
+
+impl ChangeSummary {
+    /// One-line description such as `+ item 'GitHub'`.
+    pub fn describe(&self) -> String {
+        let sign = match self.kind {
+            ChangeKind::Added => '+',
+            ChangeKind::Modified => '~',
+            ChangeKind::Removed => '-',
+        };
+        let target = match self.target {
+            ChangeTarget::Group => "group",
+            ChangeTarget::Item => "item",
+        };
+        format!("{sign} {target} '{}'", self.name)
+    }
+}
+
+/// Fingerprints of the groups and items as last saved.
+///
+/// Keeps ids, names and keyed hashes rather than a copy of the payload, so no
+/// secrets are duplicated in memory. The hash key is random per snapshot.
+pub struct Snapshot {
+    hasher: RandomState,
+    groups: Vec<(Uuid, String, u64)>,
+    items: Vec<(Uuid, String, u64)>,
+}
+
+impl Snapshot {
+    pub fn take(payload: &VaultPayload) -> Self {
+        let hasher = RandomState::new();
+        let groups = payload
+            .groups
+            .iter()
+            .map(|g| (g.id, g.name.clone(), fingerprint(&hasher, g)))
+            .collect();
+        let items = payload
+            .items
+            .iter()
+            .map(|i| (i.id, i.title.clone(), fingerprint(&hasher, i)))
+            .collect();
+        Self {
+            hasher,
+            groups,
+            items,
+        }
+    }
+
+    /// Groups first, then items; additions and edits in current order, then removals.
+    pub fn diff(&self, payload: &VaultPayload) -> Vec<ChangeSummary> {
+        let mut changes = diff_entries(
+            ChangeTarget::Group,
+            &self.groups,
+            payload
+                .groups
+                .iter()
+                .map(|g| (g.id, g.name.as_str(), fingerprint(&self.hasher, g))),
+        );
+        changes.extend(diff_entries(
+            ChangeTarget::Item,
+            &self.items,
+            payload
+                .items
+                .iter()
+                .map(|i| (i.id, i.title.as_str(), fingerprint(&self.hasher, i))),
+        ));
+        changes
+    }
+}
+
+fn fingerprint(hasher: &RandomState, value: &impl Serialize) -> u64 {
+    hasher.hash_one(bincode::serialize(value).unwrap_or_default())
+}
+
+fn diff_entries<'a>(
+    target: ChangeTarget,
+    saved: &[(Uuid, String, u64)],
+    current: impl Iterator<Item = (Uuid, &'a str, u64)>,
+) -> Vec<ChangeSummary> {
+    let saved_by_id: HashMap<Uuid, u64> = saved.iter().map(|(id, _, fp)| (*id, *fp)).collect();
+    let mut seen = Vec::new();
+    let mut changes = Vec::new();
+    for (id, name, fp) in current {
+        seen.push(id);
+        let kind = match saved_by_id.get(&id) {
+            None => ChangeKind::Added,
+            Some(saved_fp) if *saved_fp != fp => ChangeKind::Modified,
+            Some(_) => continue,
+        };
+        changes.push(ChangeSummary {
+            kind,
+            target,
+            name: name.to_string(),
+        });
+    }
+    for (id, name, _) in saved {
+        if !seen.contains(id) {
+            changes.push(ChangeSummary {
+                kind: ChangeKind::Removed,
+                target,
+                name: name.clone(),
+            });
+        }
+    }
+    changes
+}
@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use uuid::Uuid;
+
+use crate::core::models::VaultPayload;
+use crate::error::{Result, VaulturaError};
+use crate::storage::vault_file;
+
+/// Counts reported by a successful `verify_vault`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyReport {
+    pub groups: usize,
+    pub items: usize,
+}
+
+/// Read, decrypt, and deserialize a vault, then check its structure.
+///
+/// Returns the first problem found, so the result can be reported as-is.
+pub fn verify_vault(path: &Path, password: &str) -> Result<VerifyReport> {
+    let (payload, _) = vault_file::read_vault(path, password)?;
+    check_payload(&payload)?;
+    Ok(VerifyReport {
+        groups: payload.groups.len(),
+        items: payload.items.len(),
+    })
+}
+
+/// Check for duplicate ids, dangling group references, and parent cycles.
+pub fn check_payload(payload: &VaultPayload) -> Result<()> {
+    let mut group_ids = HashSet::new();
+    for group in &payload.groups {
+        if !group_ids.insert(group.id) {
+            return Err(integrity(format!("duplicate group id {}", group.id)));
+        }
+    }
+
+    for group in &payload.groups {
+        if let Some(parent) = group.parent_id {
+            if !group_ids.contains(&parent) {
+                return Err(integrity(format!(
+                    "group \"{}\" has a missing parent {parent}",
+                    group.name
+                )));
+            }
+        }
+    }
+
+    for group in &payload.groups {
+        if has_parent_cycle(payload, group.id) {
+            return Err(integrity(format!(
+                "group \"{}\" is part of a parent cycle",
+                group.name
+            )));
+        }
+    }
+
+    let mut item_ids = HashSet::new();
+    for item in &payload.items {
+        if !item_ids.insert(item.id) {
+            return Err(integrity(format!("duplicate item id {}", item.id)));
+        }
+        if let Some(gid) = item.group_id {
+            if !group_ids.contains(&gid) {
+                return Err(integrity(format!(
+                    "item \"{}\" references a missing group {gid}",
+                    item.title
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn has_parent_cycle(payload: &VaultPayload, start: Uuid) -> bool {
+    let mut seen = HashSet::new();
+    let mut current = Some(start);
+    while let Some(id) = current {
+        if !seen.insert(id) {
+            return true;
+        }
+        current = payload
+            .groups
+            .iter()
+            .find(|g| g.id == id)
+            .and_then(|g| g.parent_id);
+    }
+    false
+}
+
+fn integrity(reason: String) -> VaulturaError {
+    VaulturaError::Integrity(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::{Group, Item};
+
+    #[test]
+    fn test_clean_payload_passes() {
+        let mut payload = VaultPayload::default();
+        let parent = Group::new("Work".to_string(), None);
+        let child = Group::new("Servers".to_string(), Some(parent.id));
+        payload
+            .items
+            .push(Item::new("Prod".to_string(), Some(child.id)));
+        payload.items.push(Item::new("Loose".to_string(), None));
+        payload.groups.extend([parent, child]);
+
+        assert!(check_payload(&payload).is_ok());
+    }
+
+    #[test]
+    fn test_orphans_are_reported() {
+        let mut payload = VaultPayload::default();
+        payload
+            .items
+            .push(Item::new("Lost".to_string(), Some(Uuid::new_v4())));
+        let err = check_payload(&payload).unwrap_err().to_string();
+        assert!(err.contains("item \"Lost\" references a missing group"));
+
+        let mut payload = VaultPayload::default();
+        payload
+            .groups
+            .push(Group::new("Stray".to_string(), Some(Uuid::new_v4())));
+        let err = check_payload(&payload).unwrap_err().to_string();
+        assert!(err.contains("group \"Stray\" has a missing parent"));
+    }
+
+    #[test]
+    fn test_parent_cycle_is_reported() {
+        let mut payload = VaultPayload::default();
+        let mut a = Group::new("A".to_string(), None);
+        let b = Group::new("B".to_string(), Some(a.id));
+        a.parent_id = Some(b.id);
+        payload.groups.extend([a, b]);
+
+        let err = check_payload(&payload).unwrap_err().to_string();
+        assert!(err.contains("parent cycle"));
+    }
+}
@@ -0,0 +1,127 @@
+use std::process::{Command, Stdio};
+
+use zeroize::Zeroizing;
+
+use crate::core::models::Item;
+use crate::core::template;
+use crate::error::{Result, VaulturaError};
+
+/// Environment variable that carries the item's password to its launch command.
+pub const PASSWORD_ENV: &str = "VAULTURA_PASSWORD";
+
+/// An item's launch command with its placeholders filled in, ready to spawn.
+pub struct LaunchCommand {
+    program: String,
+    args: Vec<String>,
+    password: Zeroizing<String>,
+}
+
+impl LaunchCommand {
+    /// Split `template` on whitespace and fill `{title}`, `{username}`, `{url}`, `{notes}`
+    /// and `{tags}` in each word, so a value containing spaces stays one argument.
+    ///
+    /// No shell is involved. `{password}` is refused: arguments are visible to every
+    /// process on the machine, so the password is only handed over in [`PASSWORD_ENV`].
+    /// For the same reason `{notes}` is refused when the notes are marked secret.
+    pub fn expand(template: &str, item: &Item) -> Result<Self> {
+        if template::includes_password(template) {
+            return Err(VaulturaError::Config(format!(
+                "{{password}} can't be passed as an argument; read ${PASSWORD_ENV} instead"
+            )));
+        }
+        if item.notes_secret && template.contains("{notes}") {
+            return Err(VaulturaError::Config(
+                "{notes} can't be passed as an argument while the notes are secret".to_string(),
+            ));
+        }
+        let mut words = template
+            .split_whitespace()
+            .map(|word| template::format_item(item, word));
+        let program = words
+            .next()
+            .ok_or_else(|| VaulturaError::Config("Launch command is empty".to_string()))?;
+        Ok(Self {
+            program,
+            args: words.collect(),
+            password: Zeroizing::new(item.password.clone()),
+        })
+    }
+
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// The command line as shown in the confirmation prompt.
+    pub fn display(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn command(&self) -> Command {
+        let mut command = Command::new(&self.program);
+        command
+            .args(&self.args)
+            .env(PASSWORD_ENV, self.password.as_str())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        command
+    }
+
+    /// Start the command detached from the terminal, without waiting for it to exit.
+    ///
+    /// A background thread waits on the child so it is reaped instead of lingering as
+    /// a zombie until Vaultura exits.
+    pub fn spawn(&self) -> Result<()> {
+        let mut child = self.command().spawn()?;
+        std::thread::spawn(move || {
+            let _ = child.wait();
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expansion_keeps_password_out_of_arguments() {
+        let mut item = Item::new("Build box".to_string(), None);
+        item.username = "deploy".to_string();
+        item.password = "hunter2".to_string();
+        item.url = "build.example.com".to_string();
+
+        let launch = LaunchCommand::expand("ssh  {username}@{url} -T", &item).unwrap();
+        assert_eq!(launch.program(), "ssh");
+        assert_eq!(launch.args(), ["deploy@build.example.com", "-T"]);
+        assert_eq!(launch.display(), "ssh deploy@build.example.com -T");
+
+        let command = launch.command();
+        assert!(!command.get_args().any(|arg| arg == "hunter2"));
+        let env: Vec<_> = command.get_envs().collect();
+        assert_eq!(env, [(PASSWORD_ENV.as_ref(), Some("hunter2".as_ref()))]);
+
+        assert!(LaunchCommand::expand("sshpass -p {password} ssh {url}", &item).is_err());
+        assert!(LaunchCommand::expand("   ", &item).is_err());
+    }
+
+    #[test]
+    fn test_secret_notes_are_refused_as_arguments() {
+        let mut item = Item::new("Build box".to_string(), None);
+        item.notes = "recovery code 1234".to_string();
+
+        let launch = LaunchCommand::expand("notify-send {notes}", &item).unwrap();
+        assert_eq!(launch.args(), ["recovery code 1234"]);
+
+        item.notes_secret = true;
+        assert!(LaunchCommand::expand("notify-send {notes}", &item).is_err());
+        assert!(LaunchCommand::expand("notify-send {title}", &item).is_ok());
+    }
+}
@@ -1,3 +1,16 @@
+pub mod changes;
+pub mod integrity;
+pub mod launch;
 pub mod models;
+pub mod opener;
+pub mod password_command;
 pub mod password_generator;
+pub mod phonetic;
+pub mod portable;
+pub mod qr;
+pub mod sanitize;
+pub mod template;
+pub mod totp;
+pub mod url;
+pub mod username_generator;
 pub mod vault_service;
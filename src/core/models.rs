@@ -1,7 +1,11 @@
+use std::path::PathBuf;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::crypto::session::SealedSecret;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct KdfParams {
     pub memory_cost_kib: u32,
@@ -19,7 +23,19 @@ impl Default for KdfParams {
     }
 }
 
+/// OWASP's lowest recommended Argon2id setting is 19 MiB of memory with two passes;
+/// settings that trade memory for extra passes are accepted down to 7 MiB.
+const RECOMMENDED_MEMORY_TIME_KIB: u64 = 19 * 1024 * 2;
+const RECOMMENDED_MIN_MEMORY_KIB: u32 = 7 * 1024;
+
 impl KdfParams {
+    /// Whether these parameters are cheaper to brute-force than the recommended minimum.
+    pub fn is_weak(&self) -> bool {
+        self.memory_cost_kib < RECOMMENDED_MIN_MEMORY_KIB
+            || u64::from(self.memory_cost_kib) * u64::from(self.time_cost)
+                < RECOMMENDED_MEMORY_TIME_KIB
+    }
+
     /// Fast parameters for testing only.
     #[cfg(test)]
     pub fn test_params() -> Self {
@@ -41,6 +57,10 @@ pub struct VaultMeta {
     pub version: u32,
     pub created_at: DateTime<Utc>,
     pub modified_at: DateTime<Utc>,
+    /// Idle timeout for this vault, replacing `AppConfig::auto_lock_secs` once unlocked.
+    pub auto_lock_secs_override: Option<u64>,
+    /// Keep an item's previous password in its history when it changes.
+    pub record_password_history: bool,
 }
 
 impl Default for VaultMeta {
@@ -50,16 +70,24 @@ impl Default for VaultMeta {
             version: 1,
             created_at: now,
             modified_at: now,
+            auto_lock_secs_override: None,
+            record_password_history: true,
         }
     }
 }
 
+/// The `#[serde(default)]` fields only default when read from JSON archives (see
+/// `core::portable`); bincode vaults written before a field existed are decoded
+/// through `storage::legacy`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Group {
     pub id: Uuid,
     pub name: String,
     pub parent_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
+    /// Short note shown in the status bar when the group is selected.
+    #[serde(default)]
+    pub description: String,
 }
 
 impl Group {
@@ -69,6 +97,7 @@ impl Group {
             name,
             parent_id,
             created_at: Utc::now(),
+            description: String::new(),
         }
     }
 }
@@ -77,8 +106,64 @@ impl Group {
 pub struct PasswordHistoryEntry {
     pub password: String,
     pub changed_at: DateTime<Utc>,
+    /// `password` encrypted under the session key while secrets are sealed in memory,
+    /// like `Item::sealed_password`. Never written to disk.
+    #[serde(skip)]
+    pub sealed: Option<SealedSecret>,
 }
 
+/// Reference to a file kept outside the vault (a key file, recovery codes, ...).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Attachment {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Attachment {
+    /// Reference `path`, named after its file name.
+    pub fn from_path(path: PathBuf) -> Self {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        Self { name, path }
+    }
+
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+}
+
+/// A small file stored inside the vault, encrypted with everything else.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EmbeddedFile {
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+/// What an item holds besides the common login fields.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub enum ItemKind {
+    #[default]
+    Login,
+    /// An SSH key pair; the private key is masked like a password.
+    SshKey {
+        private_key: String,
+        public_key: String,
+    },
+}
+
+impl ItemKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ItemKind::Login => "Login",
+            ItemKind::SshKey { .. } => "SSH key",
+        }
+    }
+}
+
+/// As with `Group`, `#[serde(default)]` only matters for JSON archives; bincode
+/// vaults in older shapes go through `storage::legacy`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Item {
     pub id: Uuid,
@@ -92,6 +177,34 @@ pub struct Item {
     pub password_history: Vec<PasswordHistoryEntry>,
     pub created_at: DateTime<Utc>,
     pub modified_at: DateTime<Utc>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    #[serde(default)]
+    pub embedded: Vec<EmbeddedFile>,
+    /// Shared secret for time-based one-time passwords.
+    #[serde(default)]
+    pub otp_secret: Option<String>,
+    /// Further URLs for the same login; `url` stays the primary one.
+    #[serde(default)]
+    pub urls: Vec<String>,
+    /// Copies of this item's username and password are not auto-cleared.
+    #[serde(default)]
+    pub no_clipboard_clear: bool,
+    /// Notes are masked like the password until revealed.
+    #[serde(default)]
+    pub notes_secret: bool,
+    #[serde(default)]
+    pub kind: ItemKind,
+    /// Command run from the details panel; see `core::launch`.
+    #[serde(default)]
+    pub launch_command: Option<String>,
+    /// Revealing or copying the password asks for confirmation every time.
+    #[serde(default)]
+    pub sensitive: bool,
+    /// `password` encrypted under the session key while secrets are sealed in memory;
+    /// `password` itself is then empty. Never written to disk.
+    #[serde(skip)]
+    pub sealed_password: Option<SealedSecret>,
 }
 
 impl Item {
@@ -109,8 +222,37 @@ impl Item {
             password_history: Vec::new(),
             created_at: now,
             modified_at: now,
+            attachments: Vec::new(),
+            embedded: Vec::new(),
+            otp_secret: None,
+            no_clipboard_clear: false,
+            notes_secret: false,
+            kind: ItemKind::Login,
+            launch_command: None,
+            sensitive: false,
+            urls: Vec::new(),
+            sealed_password: None,
         }
     }
+
+    /// The primary URL followed by the additional ones, skipping blanks.
+    pub fn all_urls(&self) -> Vec<&str> {
+        std::iter::once(self.url.as_str())
+            .chain(self.urls.iter().map(String::as_str))
+            .filter(|url| !url.trim().is_empty())
+            .collect()
+    }
+
+    pub fn has_totp(&self) -> bool {
+        self.otp_secret
+            .as_deref()
+            .is_some_and(|s| !s.trim().is_empty())
+    }
+
+    /// Case-insensitive tag membership check.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -132,6 +274,23 @@ mod tests {
         assert_eq!(params, decoded);
     }
 
+    #[test]
+    fn test_weak_kdf_params() {
+        let params = |memory_cost_kib, time_cost| KdfParams {
+            memory_cost_kib,
+            time_cost,
+            parallelism: 1,
+        };
+        assert!(!KdfParams::default().is_weak());
+        assert!(KdfParams::test_params().is_weak());
+        assert!(!params(19 * 1024, 2).is_weak());
+        assert!(params(19 * 1024, 1).is_weak());
+        assert!(!params(46 * 1024, 1).is_weak());
+        // More passes make up for less memory, but only down to the floor.
+        assert!(!params(12 * 1024, 4).is_weak());
+        assert!(params(4 * 1024, 10).is_weak());
+    }
+
     #[test]
     fn test_group_roundtrip() {
         let group = Group::new("Test Group".to_string(), None);
@@ -151,12 +310,32 @@ mod tests {
         item.password_history.push(PasswordHistoryEntry {
             password: "old_pass".to_string(),
             changed_at: Utc::now(),
+            sealed: None,
         });
+        item.attachments
+            .push(Attachment::from_path(PathBuf::from("/keys/id_ed25519.pem")));
         let encoded = bincode::serialize(&item).unwrap();
         let decoded: Item = bincode::deserialize(&encoded).unwrap();
         assert_eq!(item, decoded);
     }
 
+    #[test]
+    fn test_all_urls_lists_primary_first() {
+        let mut item = Item::new("Site".to_string(), None);
+        assert!(item.all_urls().is_empty());
+        item.urls = vec!["b.example".to_string(), " ".to_string()];
+        assert_eq!(item.all_urls(), ["b.example"]);
+        item.url = "a.example".to_string();
+        assert_eq!(item.all_urls(), ["a.example", "b.example"]);
+    }
+
+    #[test]
+    fn test_attachment_named_after_file() {
+        let attachment = Attachment::from_path(PathBuf::from("/docs/recovery-codes.pdf"));
+        assert_eq!(attachment.name, "recovery-codes.pdf");
+        assert!(!attachment.exists());
+    }
+
     #[test]
     fn test_vault_payload_roundtrip() {
         let mut payload = VaultPayload::default();
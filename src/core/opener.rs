@@ -0,0 +1,93 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::error::{Result, VaulturaError};
+
+/// The platform's "open with default application" program.
+fn opener() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(windows) {
+        "explorer"
+    } else {
+        "xdg-open"
+    }
+}
+
+/// Open a web URL in the default browser. URLs without a scheme are opened as https.
+///
+/// Anything other than http(s) is refused so item data cannot launch local files or apps.
+pub fn open_url(url: &str) -> Result<()> {
+    let url = browser_url(url)?;
+    Command::new(opener())
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+fn browser_url(url: &str) -> Result<String> {
+    let url = url.trim();
+    let lower = url.to_ascii_lowercase();
+    if lower.starts_with("https://") || lower.starts_with("http://") {
+        Ok(url.to_string())
+    } else if url.is_empty() || has_scheme(url) {
+        Err(VaulturaError::Config(format!("Not a web URL: {url}")))
+    } else {
+        Ok(format!("https://{url}"))
+    }
+}
+
+/// Whether `url` starts with `scheme:`; `host:port` does not count.
+fn has_scheme(url: &str) -> bool {
+    let Some((scheme, rest)) = url.split_once(':') else {
+        return false;
+    };
+    let scheme_like = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    scheme_like && !rest.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// Open `path` with the default application, without waiting for it to exit.
+///
+/// The path is passed as a single argument, never through a shell.
+pub fn open_path(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Err(VaulturaError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} does not exist", path.display()),
+        )));
+    }
+    Command::new(opener())
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_browser_url_adds_https_and_refuses_other_schemes() {
+        assert_eq!(
+            browser_url("example.com/login").unwrap(),
+            "https://example.com/login"
+        );
+        assert_eq!(browser_url(" http://intranet ").unwrap(), "http://intranet");
+        assert!(browser_url("file:///etc/passwd").is_err());
+        assert!(browser_url("javascript:alert(document.cookie)").is_err());
+        assert_eq!(
+            browser_url("localhost:3000").unwrap(),
+            "https://localhost:3000"
+        );
+        assert!(browser_url("").is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use std::io;
+use std::process::{Command, Stdio};
+
+use zeroize::Zeroizing;
+
+use crate::error::{Result, VaulturaError};
+
+/// Run `command` and take its standard output as the master password.
+///
+/// The command is split on whitespace and run without a shell, like launch commands.
+/// Its stdin and stderr stay attached to the terminal so tools such as `pass` can ask
+/// for their own passphrase. The output is never logged or put in an error message.
+pub fn read_password(command: &str) -> Result<Zeroizing<String>> {
+    read_password_with(command, |program, args| {
+        let output = Command::new(program)
+            .args(args)
+            .stdin(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .output()?;
+        let stdout = Zeroizing::new(output.stdout);
+        if !output.status.success() {
+            return Err(io::Error::other(format!(
+                "{program} exited with {}",
+                output.status
+            )));
+        }
+        Ok(stdout)
+    })
+}
+
+/// `read_password` with the process execution supplied by the caller.
+fn read_password_with(
+    command: &str,
+    run: impl FnOnce(&str, &[&str]) -> io::Result<Zeroizing<Vec<u8>>>,
+) -> Result<Zeroizing<String>> {
+    let mut words = command.split_whitespace();
+    let program = words
+        .next()
+        .ok_or_else(|| VaulturaError::Config("Password command is empty".to_string()))?;
+    let args: Vec<&str> = words.collect();
+    password_from_stdout(run(program, &args)?)
+}
+
+/// The password is the command's output minus one trailing newline; other whitespace
+/// is kept, since it may be part of the password.
+fn password_from_stdout(stdout: Zeroizing<Vec<u8>>) -> Result<Zeroizing<String>> {
+    let text = std::str::from_utf8(&stdout)
+        .map_err(|_| VaulturaError::Config("Password command printed invalid UTF-8".to_string()))?;
+    let password = text
+        .strip_suffix('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .unwrap_or(text);
+    if password.is_empty() {
+        return Err(VaulturaError::Config(
+            "Password command printed nothing".to_string(),
+        ));
+    }
+    Ok(Zeroizing::new(password.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stdout_loses_only_the_trailing_newline() {
+        let password =
+            |out: &[u8]| password_from_stdout(Zeroizing::new(out.to_vec())).map(|p| p.to_string());
+        assert_eq!(password(b"hunter2\n").unwrap(), "hunter2");
+        assert_eq!(password(b"hunter2\r\n").unwrap(), "hunter2");
+        assert_eq!(password(b"hunter2").unwrap(), "hunter2");
+        assert_eq!(password(b" two words \n\n").unwrap(), " two words \n");
+        assert!(password(b"\n").is_err());
+        assert!(password(&[0xff, 0xfe]).is_err());
+    }
+
+    #[test]
+    fn test_command_is_split_without_a_shell() {
+        let password =
+            read_password_with("secret-tool lookup service vaultura", |program, args| {
+                assert_eq!(program, "secret-tool");
+                assert_eq!(args, ["lookup", "service", "vaultura"]);
+                Ok(Zeroizing::new(b"s3cret\n".to_vec()))
+            })
+            .unwrap();
+        assert_eq!(password.as_str(), "s3cret");
+
+        assert!(read_password_with("  ", |_, _| unreachable!()).is_err());
+        let failed = read_password_with("pass show vault", |_, _| Err(io::Error::other("exit 1")));
+        assert!(failed.is_err());
+    }
+}
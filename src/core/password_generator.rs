@@ -1,5 +1,7 @@
 use rand::Rng;
 
+use crate::error::{Result, VaulturaError};
+
 #[derive(Debug, Clone)]
 pub struct PasswordConfig {
     pub length: usize,
@@ -8,6 +10,8 @@ pub struct PasswordConfig {
     pub digits: bool,
     pub symbols: bool,
     pub exclude_ambiguous: bool,
+    /// Characters dropped from the charset when `exclude_ambiguous` is set.
+    pub ambiguous_chars: String,
 }
 
 impl Default for PasswordConfig {
@@ -19,6 +23,7 @@ impl Default for PasswordConfig {
             digits: true,
             symbols: true,
             exclude_ambiguous: false,
+            ambiguous_chars: DEFAULT_AMBIGUOUS.to_string(),
         }
     }
 }
@@ -27,9 +32,13 @@ const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
 const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
 const DIGITS: &str = "0123456789";
 const SYMBOLS: &str = "!@#$%^&*()-_=+[]{}|;:,.<>?";
-const AMBIGUOUS: &str = "0O1lI";
+/// Characters that are easily confused with one another or mangled when
+/// typed or pasted: look-alike letters and digits, quotes, pipes and backticks.
+pub const DEFAULT_AMBIGUOUS: &str = "0Oo1lIi|`'\";:,.";
 
-pub fn generate_password(config: &PasswordConfig) -> String {
+/// Random password for `config`. Fails when excluding the ambiguous characters
+/// leaves nothing to pick from, rather than quietly using characters the user excluded.
+pub fn generate_password(config: &PasswordConfig) -> Result<String> {
     let mut charset = String::new();
 
     if config.uppercase {
@@ -50,10 +59,16 @@ pub fn generate_password(config: &PasswordConfig) -> String {
     }
 
     if config.exclude_ambiguous {
-        charset = charset
+        let filtered: String = charset
             .chars()
-            .filter(|c| !AMBIGUOUS.contains(*c))
+            .filter(|c| !is_excluded(*c, config))
             .collect();
+        if filtered.is_empty() {
+            return Err(VaulturaError::Generator(
+                "every selected character is in the ambiguous set".to_string(),
+            ));
+        }
+        charset = filtered;
     }
 
     let chars: Vec<char> = charset.chars().collect();
@@ -65,25 +80,47 @@ pub fn generate_password(config: &PasswordConfig) -> String {
             .collect();
 
         if meets_requirements(&password, config) {
-            return password;
+            return Ok(password);
         }
     }
 }
 
+/// Words passphrases are drawn from, one per line: the 2048-word English list from
+/// Bitcoin's BIP-0039 (`bip-0039/english.txt` in github.com/bitcoin/bips), so each
+/// word adds exactly 11 bits. The words are short, common and unique in their first
+/// four letters.
+const PASSPHRASE_WORDS: &str = include_str!("passphrase_words.txt");
+
+/// Random passphrase of `words` words from the built-in list, joined by `-`.
+pub fn generate_passphrase(words: usize) -> String {
+    let list: Vec<&str> = PASSPHRASE_WORDS.lines().collect();
+    let mut rng = rand::thread_rng();
+    (0..words)
+        .map(|_| list[rng.gen_range(0..list.len())])
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn is_excluded(c: char, config: &PasswordConfig) -> bool {
+    config.exclude_ambiguous && config.ambiguous_chars.contains(c)
+}
+
+/// Whether `class` still has characters left once the ambiguous set is removed.
+fn class_available(class: &str, config: &PasswordConfig) -> bool {
+    class.chars().any(|c| !is_excluded(c, config))
+}
+
 fn meets_requirements(password: &str, config: &PasswordConfig) -> bool {
-    if config.uppercase && !password.chars().any(|c| c.is_ascii_uppercase()) {
-        return false;
-    }
-    if config.lowercase && !password.chars().any(|c| c.is_ascii_lowercase()) {
-        return false;
-    }
-    if config.digits && !password.chars().any(|c| c.is_ascii_digit()) {
-        return false;
-    }
-    if config.symbols && !password.chars().any(|c| SYMBOLS.contains(c)) {
-        return false;
-    }
-    true
+    let classes = [
+        (config.uppercase, UPPERCASE),
+        (config.lowercase, LOWERCASE),
+        (config.digits, DIGITS),
+        (config.symbols, SYMBOLS),
+    ];
+    classes
+        .iter()
+        .filter(|(enabled, class)| *enabled && class_available(class, config))
+        .all(|(_, class)| password.chars().any(|c| class.contains(c)))
 }
 
 #[cfg(test)]
@@ -93,7 +130,7 @@ mod tests {
     #[test]
     fn test_default_config_generates_valid_password() {
         let config = PasswordConfig::default();
-        let password = generate_password(&config);
+        let password = generate_password(&config).unwrap();
         assert_eq!(password.len(), 20);
         assert!(password.chars().any(|c| c.is_ascii_uppercase()));
         assert!(password.chars().any(|c| c.is_ascii_lowercase()));
@@ -107,7 +144,7 @@ mod tests {
             length: 8,
             ..Default::default()
         };
-        let password = generate_password(&config);
+        let password = generate_password(&config).unwrap();
         assert_eq!(password.len(), 8);
     }
 
@@ -120,8 +157,9 @@ mod tests {
             digits: false,
             symbols: false,
             exclude_ambiguous: false,
+            ..Default::default()
         };
-        let password = generate_password(&config);
+        let password = generate_password(&config).unwrap();
         assert!(password.chars().all(|c| c.is_ascii_lowercase()));
     }
 
@@ -134,8 +172,9 @@ mod tests {
             digits: true,
             symbols: false,
             exclude_ambiguous: false,
+            ..Default::default()
         };
-        let password = generate_password(&config);
+        let password = generate_password(&config).unwrap();
         assert!(password.chars().all(|c| c.is_ascii_digit()));
     }
 
@@ -148,8 +187,9 @@ mod tests {
             digits: true,
             symbols: false,
             exclude_ambiguous: true,
+            ..Default::default()
         };
-        let password = generate_password(&config);
+        let password = generate_password(&config).unwrap();
         assert!(!password.contains('0'));
         assert!(!password.contains('O'));
         assert!(!password.contains('1'));
@@ -157,11 +197,90 @@ mod tests {
         assert!(!password.contains('I'));
     }
 
+    #[test]
+    fn test_default_ambiguous_set_excludes_expanded_characters() {
+        let config = PasswordConfig {
+            length: 128,
+            exclude_ambiguous: true,
+            ..Default::default()
+        };
+        for _ in 0..10 {
+            let password = generate_password(&config).unwrap();
+            for c in [
+                '0', 'O', 'o', '1', 'l', 'I', 'i', '|', '`', ';', ':', ',', '.',
+            ] {
+                assert!(!password.contains(c), "{c:?} in {password}");
+            }
+            assert!(password.chars().any(|c| SYMBOLS.contains(c)));
+        }
+    }
+
+    #[test]
+    fn test_custom_ambiguous_set_is_honored() {
+        let config = PasswordConfig {
+            length: 128,
+            symbols: false,
+            exclude_ambiguous: true,
+            ambiguous_chars: "abcXYZ".to_string(),
+            ..Default::default()
+        };
+        let password = generate_password(&config).unwrap();
+        assert!(!password.chars().any(|c| "abcXYZ".contains(c)));
+
+        // Removing a whole class drops its requirement instead of looping forever.
+        let config = PasswordConfig {
+            ambiguous_chars: DIGITS.to_string(),
+            ..config
+        };
+        let password = generate_password(&config).unwrap();
+        assert!(!password.chars().any(|c| c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn test_excluding_every_character_is_an_error() {
+        let config = PasswordConfig {
+            uppercase: false,
+            symbols: false,
+            digits: false,
+            exclude_ambiguous: true,
+            ambiguous_chars: LOWERCASE.to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            generate_password(&config),
+            Err(VaulturaError::Generator(_))
+        ));
+    }
+
     #[test]
     fn test_uniqueness() {
         let config = PasswordConfig::default();
-        let p1 = generate_password(&config);
-        let p2 = generate_password(&config);
+        let p1 = generate_password(&config).unwrap();
+        let p2 = generate_password(&config).unwrap();
         assert_ne!(p1, p2);
     }
+
+    #[test]
+    fn test_passphrase_words_are_unique_lowercase_words() {
+        let words: Vec<&str> = PASSPHRASE_WORDS.lines().collect();
+        assert_eq!(words.len(), 2048);
+        assert!(words
+            .iter()
+            .all(|w| !w.is_empty() && w.chars().all(|c| c.is_ascii_lowercase())));
+        let unique: std::collections::HashSet<_> = words.iter().collect();
+        assert_eq!(unique.len(), words.len());
+    }
+
+    #[test]
+    fn test_passphrase_has_requested_word_count() {
+        for count in [1, 4, 7] {
+            let phrase = generate_passphrase(count);
+            let words: Vec<&str> = phrase.split('-').collect();
+            assert_eq!(words.len(), count);
+            assert!(words
+                .iter()
+                .all(|w| PASSPHRASE_WORDS.lines().any(|known| known == *w)));
+        }
+        assert_eq!(generate_passphrase(0), "");
+    }
 }
@@ -0,0 +1,96 @@
+//! Spelling a password out character by character, for reading it aloud.
+
+const NATO: [&str; 26] = [
+    "Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliett",
+    "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo", "Sierra", "Tango",
+    "Uniform", "Victor", "Whiskey", "X-ray", "Yankee", "Zulu",
+];
+
+/// Each character as "position:name", e.g. `["1:alfa", "2:capital Bravo", "3:digit 3"]`.
+///
+/// Letters use the NATO alphabet, lower case as is and upper case as "capital";
+/// digits read "digit N"; ASCII symbols are named, anything else is given as U+XXXX.
+pub fn spell_tokens(password: &str) -> Vec<String> {
+    password
+        .chars()
+        .enumerate()
+        .map(|(i, c)| format!("{}:{}", i + 1, char_name(c)))
+        .collect()
+}
+
+/// `spell_tokens` joined with ", ".
+pub fn spell_out(password: &str) -> String {
+    spell_tokens(password).join(", ")
+}
+
+fn char_name(c: char) -> String {
+    if c.is_ascii_lowercase() {
+        return NATO[(c as u8 - b'a') as usize].to_lowercase();
+    }
+    if c.is_ascii_uppercase() {
+        return format!("capital {}", NATO[(c as u8 - b'A') as usize]);
+    }
+    if c.is_ascii_digit() {
+        return format!("digit {c}");
+    }
+    let name = match c {
+        ' ' => "space",
+        '!' => "exclamation mark",
+        '"' => "double quote",
+        '#' => "hash",
+        '$' => "dollar",
+        '%' => "percent",
+        '&' => "ampersand",
+        '\'' => "apostrophe",
+        '(' => "left parenthesis",
+        ')' => "right parenthesis",
+        '*' => "asterisk",
+        '+' => "plus",
+        ',' => "comma",
+        '-' => "hyphen",
+        '.' => "period",
+        '/' => "slash",
+        ':' => "colon",
+        ';' => "semicolon",
+        '<' => "less than",
+        '=' => "equals",
+        '>' => "greater than",
+        '?' => "question mark",
+        '@' => "at sign",
+        '[' => "left bracket",
+        '\\' => "backslash",
+        ']' => "right bracket",
+        '^' => "caret",
+        '_' => "underscore",
+        '`' => "backtick",
+        '{' => "left brace",
+        '|' => "vertical bar",
+        '}' => "right brace",
+        '~' => "tilde",
+        _ => return format!("U+{:04X}", c as u32),
+    };
+    name.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spell_out_letters_digits_and_symbols() {
+        assert_eq!(
+            spell_tokens("aB3!"),
+            [
+                "1:alfa",
+                "2:capital Bravo",
+                "3:digit 3",
+                "4:exclamation mark"
+            ]
+        );
+        assert_eq!(
+            spell_out("x _é"),
+            "1:x-ray, 2:space, 3:underscore, 4:U+00E9"
+        );
+        assert_eq!(spell_out(""), "");
+    }
+}
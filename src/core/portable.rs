@@ -0,0 +1,306 @@
+//! Plaintext CSV/JSON encodings used inside export archives, and the secret-free inventory.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::models::{Group, Item};
+use crate::error::{Result, VaulturaError};
+
+const CSV_HEADER: [&str; 7] = [
+    "title", "username", "password", "url", "notes", "tags", "group",
+];
+
+/// Separator for the tags column in CSV exports.
+const TAG_SEPARATOR: char = ';';
+
+#[derive(Serialize)]
+struct JsonExport<'a> {
+    groups: &'a [Group],
+    items: &'a [Item],
+}
+
+#[derive(Deserialize)]
+struct JsonImport {
+    groups: Vec<Group>,
+    items: Vec<Item>,
+}
+
+/// One item as it appears in a CSV export, with its group referenced by name.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CsvRecord {
+    pub title: String,
+    pub username: String,
+    pub password: String,
+    pub url: String,
+    pub notes: String,
+    pub tags: Vec<String>,
+    pub group: Option<String>,
+}
+
+/// Rows read from a CSV export, with a note for each row that was repaired or skipped.
+#[derive(Debug, Default)]
+pub struct CsvImport {
+    pub records: Vec<CsvRecord>,
+    /// Never quotes field contents, which may be secrets.
+    pub warnings: Vec<String>,
+}
+
+/// Vault structure without any secrets, for sharing or auditing.
+#[derive(Serialize)]
+struct Inventory<'a> {
+    groups: Vec<InventoryGroup<'a>>,
+    items: Vec<InventoryItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct InventoryGroup<'a> {
+    name: &'a str,
+    parent: Option<&'a str>,
+    created_at: DateTime<Utc>,
+}
+
+/// Deliberately lists only non-secret fields: no password, history, notes or files.
+#[derive(Serialize)]
+struct InventoryItem<'a> {
+    title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<&'a str>,
+    url: &'a str,
+    tags: &'a [String],
+    group: Option<&'a str>,
+    created_at: DateTime<Utc>,
+    modified_at: DateTime<Utc>,
+}
+
+/// Encode the inventory as pretty JSON; usernames are left out unless requested.
+pub fn to_inventory_json(
+    groups: &[Group],
+    items: &[Item],
+    include_usernames: bool,
+) -> Result<Vec<u8>> {
+    let group_name = |id: Option<uuid::Uuid>| {
+        id.and_then(|id| groups.iter().find(|g| g.id == id))
+            .map(|g| g.name.as_str())
+    };
+    let inventory = Inventory {
+        groups: groups
+            .iter()
+            .map(|g| InventoryGroup {
+                name: &g.name,
+                parent: group_name(g.parent_id),
+                created_at: g.created_at,
+            })
+            .collect(),
+        items: items
+            .iter()
+            .map(|item| InventoryItem {
+                title: &item.title,
+                username: include_usernames.then_some(item.username.as_str()),
+                url: &item.url,
+                tags: &item.tags,
+                group: group_name(item.group_id),
+                created_at: item.created_at,
+                modified_at: item.modified_at,
+            })
+            .collect(),
+    };
+    serde_json::to_vec_pretty(&inventory).map_err(|e| VaulturaError::Export(e.to_string()))
+}
+
+pub fn to_json(groups: &[Group], items: &[Item]) -> Result<Vec<u8>> {
+    serde_json::to_vec_pretty(&JsonExport { groups, items })
+        .map_err(|e| VaulturaError::Export(e.to_string()))
+}
+
+pub fn from_json(data: &[u8]) -> Result<(Vec<Group>, Vec<Item>)> {
+    let parsed: JsonImport =
+        serde_json::from_slice(data).map_err(|e| VaulturaError::Import(e.to_string()))?;
+    Ok((parsed.groups, parsed.items))
+}
+
+pub fn to_csv(groups: &[Group], items: &[Item]) -> Result<Vec<u8>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let export_err = |e: csv::Error| VaulturaError::Export(e.to_string());
+
+    writer.write_record(CSV_HEADER).map_err(export_err)?;
+    for item in items {
+        let group = item
+            .group_id
+            .and_then(|gid| groups.iter().find(|g| g.id == gid))
+            .map(|g| g.name.as_str())
+            .unwrap_or("");
+        let tags = item.tags.join(&TAG_SEPARATOR.to_string());
+        writer
+            .write_record([
+                item.title.as_str(),
+                &item.username,
+                &item.password,
+                &item.url,
+                &item.notes,
+                &tags,
+                group,
+            ])
+            .map_err(export_err)?;
+    }
+
+    writer
+        .into_inner()
+        .map_err(|e| VaulturaError::Export(e.to_string()))
+}
+
+/// Read a CSV export. Invalid UTF-8 is replaced and NUL bytes are dropped rather than
+/// failing the import; rows the parser cannot read are skipped. Each is reported in
+/// the warnings.
+pub fn from_csv(data: &[u8]) -> Result<CsvImport> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(data);
+    let mut import = CsvImport::default();
+    for row in reader.byte_records() {
+        let row = match row {
+            Ok(row) => row,
+            Err(e) if matches!(e.kind(), csv::ErrorKind::Io(_)) => {
+                return Err(VaulturaError::Import(e.to_string()));
+            }
+            Err(e) => {
+                let line = e.position().map_or(0, |p| p.line());
+                import
+                    .warnings
+                    .push(format!("line {line}: skipped unreadable row"));
+                continue;
+            }
+        };
+        let line = row.position().map_or(0, |p| p.line());
+        let mut invalid_utf8 = false;
+        let mut nul_bytes = false;
+        let field = |i: usize| -> String {
+            let bytes = row.get(i).unwrap_or(b"");
+            let text = String::from_utf8_lossy(bytes);
+            invalid_utf8 |= matches!(text, std::borrow::Cow::Owned(_));
+            nul_bytes |= text.contains('\0');
+            text.replace('\0', "")
+        };
+        let record = csv_record(field);
+        if invalid_utf8 {
+            import
+                .warnings
+                .push(format!("line {line}: replaced invalid UTF-8"));
+        }
+        if nul_bytes {
+            import
+                .warnings
+                .push(format!("line {line}: removed NUL bytes"));
+        }
+        import.records.push(record);
+    }
+    Ok(import)
+}
+
+/// Build a record from the export's columns, read through `field`.
+fn csv_record(mut field: impl FnMut(usize) -> String) -> CsvRecord {
+    let title = field(0);
+    let username = field(1);
+    let password = field(2);
+    let url = field(3);
+    let notes = field(4);
+    let tags = field(5);
+    let group = field(6);
+    CsvRecord {
+        title,
+        username,
+        password,
+        url,
+        notes,
+        tags: tags
+            .split(TAG_SEPARATOR)
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect(),
+        group: (!group.is_empty()).then_some(group),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> (Vec<Group>, Vec<Item>) {
+        let group = Group::new("Work".to_string(), None);
+        let mut item = Item::new("Wiki, internal".to_string(), Some(group.id));
+        item.username = "alice".to_string();
+        item.password = "p\"w,1".to_string();
+        item.notes = "line one\nline two".to_string();
+        item.tags = vec!["docs".to_string(), "team".to_string()];
+        let loose = Item::new("Loose".to_string(), None);
+        (vec![group], vec![item, loose])
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let (groups, items) = sample();
+        let data = to_json(&groups, &items).unwrap();
+        let (read_groups, read_items) = from_json(&data).unwrap();
+        assert_eq!(read_groups, groups);
+        assert_eq!(read_items, items);
+    }
+
+    #[test]
+    fn test_csv_roundtrip_escapes_fields() {
+        let (groups, items) = sample();
+        let data = to_csv(&groups, &items).unwrap();
+        let import = from_csv(&data).unwrap();
+        assert!(import.warnings.is_empty());
+        let records = import.records;
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].title, "Wiki, internal");
+        assert_eq!(records[0].password, "p\"w,1");
+        assert_eq!(records[0].notes, "line one\nline two");
+        assert_eq!(records[0].tags, vec!["docs", "team"]);
+        assert_eq!(records[0].group.as_deref(), Some("Work"));
+        assert_eq!(records[1].group, None);
+    }
+
+    #[test]
+    fn test_csv_with_bad_bytes_imports_with_warnings() {
+        let mut data = b"title,username,password,url,notes,tags,group\n".to_vec();
+        data.extend_from_slice(b"Caf\xe9,alice,pw\xff,,,,\n");
+        data.extend_from_slice(b"Null,bo\0b,secret,,,,\n");
+        data.extend_from_slice(b"Short,carol\n");
+
+        let import = from_csv(&data).unwrap();
+        let titles: Vec<&str> = import.records.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, ["Caf\u{fffd}", "Null", "Short"]);
+        assert_eq!(import.records[0].password, "pw\u{fffd}");
+        assert_eq!(import.records[1].username, "bob");
+        assert_eq!(import.records[2].username, "carol");
+        assert_eq!(
+            import.warnings,
+            [
+                "line 2: replaced invalid UTF-8",
+                "line 3: removed NUL bytes"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inventory_omits_secrets() {
+        let group = Group::new("Work".to_string(), None);
+        let mut item = Item::new("GitHub".to_string(), Some(group.id));
+        item.username = "octo".to_string();
+        item.password = "hunter2-secret".to_string();
+        item.notes = "recovery words".to_string();
+        let groups = [group];
+        let items = [item];
+
+        let json = String::from_utf8(to_inventory_json(&groups, &items, false).unwrap()).unwrap();
+        assert!(json.contains("GitHub"));
+        assert!(json.contains("\"group\": \"Work\""));
+        assert!(!json.contains("hunter2-secret"));
+        assert!(!json.contains("recovery words"));
+        assert!(!json.contains("octo"));
+
+        let json = String::from_utf8(to_inventory_json(&groups, &items, true).unwrap()).unwrap();
+        assert!(json.contains("octo"));
+        assert!(!json.contains("hunter2-secret"));
+    }
+}
@@ -0,0 +1,137 @@
+//! What a QR code shown for moving a field to a phone contains.
+
+use zeroize::Zeroizing;
+
+use crate::core::models::Item;
+#[cfg(feature = "qr")]
+use crate::error::{Result, VaulturaError};
+
+/// Item fields that can be shown as a QR code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrField {
+    Password,
+    Username,
+    Url,
+    /// The TOTP secret, as an `otpauth://` URI authenticator apps can import.
+    Totp,
+}
+
+impl QrField {
+    pub const ALL: [QrField; 4] = [Self::Password, Self::Username, Self::Url, Self::Totp];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Password => "Password",
+            Self::Username => "Username",
+            Self::Url => "URL",
+            Self::Totp => "2FA secret",
+        }
+    }
+
+    /// Whether showing the code puts a secret on screen.
+    pub fn is_secret(self) -> bool {
+        matches!(self, Self::Password | Self::Totp)
+    }
+}
+
+/// The text to encode for `field`, or `None` when the item has no such value.
+///
+/// Fields are encoded exactly as stored, except the TOTP secret, which becomes an
+/// `otpauth://totp/` URI labelled with the item title and username.
+pub fn field_data(item: &Item, field: QrField) -> Option<Zeroizing<String>> {
+    let data = match field {
+        QrField::Password => item.password.clone(),
+        QrField::Username => item.username.clone(),
+        QrField::Url => item.url.clone(),
+        QrField::Totp => {
+            let secret: String = item
+                .otp_secret
+                .as_deref()?
+                .chars()
+                .filter(|c| !c.is_whitespace() && *c != '-')
+                .map(|c| c.to_ascii_uppercase())
+                .collect();
+            if secret.is_empty() {
+                return None;
+            }
+            let label = if item.username.is_empty() {
+                item.title.clone()
+            } else {
+                format!("{}:{}", item.title, item.username)
+            };
+            format!(
+                "otpauth://totp/{}?secret={}&issuer={}",
+                percent_encode(&label),
+                Zeroizing::new(secret).as_str(),
+                percent_encode(&item.title)
+            )
+        }
+    };
+    (!data.is_empty()).then(|| Zeroizing::new(data))
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters.
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// Draw `data` as a QR code in half-block characters, two modules per terminal row,
+/// dark modules drawn in the background colour so it scans on dark terminals.
+#[cfg(feature = "qr")]
+pub fn render(data: &str) -> Result<Vec<String>> {
+    use qrcode::render::unicode::Dense1x2;
+    use qrcode::QrCode;
+
+    let code = QrCode::new(data.as_bytes()).map_err(|e| VaulturaError::Config(e.to_string()))?;
+    let image = code
+        .render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .build();
+    Ok(image.lines().map(str::to_string).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_data_encodes_the_value_as_is() {
+        let mut item = Item::new("Bank".to_string(), None);
+        item.username = "alice".to_string();
+        item.password = "p@ss wörd!".to_string();
+        assert_eq!(
+            field_data(&item, QrField::Password).unwrap().as_str(),
+            "p@ss wörd!"
+        );
+        assert_eq!(
+            field_data(&item, QrField::Username).unwrap().as_str(),
+            "alice"
+        );
+        assert!(field_data(&item, QrField::Url).is_none());
+        assert!(field_data(&item, QrField::Totp).is_none());
+
+        item.otp_secret = Some("jbsw y3dp".to_string());
+        assert_eq!(
+            field_data(&item, QrField::Totp).unwrap().as_str(),
+            "otpauth://totp/Bank%3Aalice?secret=JBSWY3DP&issuer=Bank"
+        );
+    }
+
+    #[cfg(feature = "qr")]
+    #[test]
+    fn test_render_draws_a_square_code() {
+        let lines = render("hunter2").unwrap();
+        let width = lines[0].chars().count();
+        assert!(lines.iter().all(|l| l.chars().count() == width));
+        // Two modules per row, so about half as many rows as columns.
+        assert!(lines.len() * 2 >= width && lines.len() * 2 <= width + 2);
+    }
+}
@@ -0,0 +1,80 @@
+//! Cleanup for credentials that arrive through imports.
+//!
+//! Text copied through word processors and spreadsheets picks up invisible characters
+//! and typographic substitutions that make a password silently stop working. Only
+//! usernames and passwords go through here; notes keep whatever they contain.
+
+/// Remove invisible characters from `value` and undo typographic substitutions.
+///
+/// Dropped: zero-width spaces and joiners, the byte-order mark, soft hyphens,
+/// directional marks and control characters. Curly quotes become straight quotes and
+/// no-break spaces become plain spaces. All other characters, including non-ASCII
+/// letters, are kept as they are. Returns `None` when nothing needed changing.
+pub fn clean_credential(value: &str) -> Option<String> {
+    if !value
+        .chars()
+        .any(|c| is_invisible(c) || replacement(c).is_some())
+    {
+        return None;
+    }
+    Some(
+        value
+            .chars()
+            .filter(|&c| !is_invisible(c))
+            .map(|c| replacement(c).unwrap_or(c))
+            .collect(),
+    )
+}
+
+/// Clean `value` in place, returning whether it changed.
+pub fn clean_in_place(value: &mut String) -> bool {
+    match clean_credential(value) {
+        Some(cleaned) => {
+            *value = cleaned;
+            true
+        }
+        None => false,
+    }
+}
+
+fn is_invisible(c: char) -> bool {
+    c.is_control()
+        || matches!(
+            c,
+            '\u{00AD}'                      // soft hyphen
+                | '\u{200B}'..='\u{200F}'   // zero-width space/joiners, LTR/RTL marks
+                | '\u{202A}'..='\u{202E}'   // directional embeddings and overrides
+                | '\u{2060}'..='\u{2064}'   // word joiner, invisible operators
+                | '\u{2066}'..='\u{2069}'   // directional isolates
+                | '\u{FEFF}' // byte-order mark
+        )
+}
+
+fn replacement(c: char) -> Option<char> {
+    match c {
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => Some('\''),
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => Some('"'),
+        '\u{00A0}' | '\u{2007}' | '\u{202F}' => Some(' '),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_width_space_is_removed_from_password() {
+        assert_eq!(
+            clean_credential("hunter\u{200B}2").as_deref(),
+            Some("hunter2")
+        );
+        assert_eq!(
+            clean_credential("\u{FEFF}it\u{2019}s\u{00A0}me\t").as_deref(),
+            Some("it's me")
+        );
+        assert_eq!(clean_credential("hunter2"), None);
+        // Legitimate non-ASCII text is left alone.
+        assert_eq!(clean_credential("pässwörd-日本"), None);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::core::models::Item;
+
+/// Template used when no `item_copy_template` is configured.
+pub const DEFAULT_ITEM_TEMPLATE: &str = "{title}: {username} / {password}";
+
+/// Render `item` through `template`, replacing `{title}`, `{username}`, `{password}`,
+/// `{url}`, `{notes}` and `{tags}` (comma-separated).
+///
+/// Unknown placeholders and unmatched braces are kept as written.
+pub fn format_item(item: &Item, template: &str) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let name = &after[..end];
+        match field(item, name) {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Whether rendering `template` would include the item's password.
+pub fn includes_password(template: &str) -> bool {
+    template.contains("{password}")
+}
+
+fn field(item: &Item, name: &str) -> Option<String> {
+    let value = match name {
+        "title" => item.title.clone(),
+        "username" => item.username.clone(),
+        "password" => item.password.clone(),
+        "url" => item.url.clone(),
+        "notes" => item.notes.clone(),
+        "tags" => item.tags.join(", "),
+        _ => return None,
+    };
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item() -> Item {
+        let mut item = Item::new("GitHub".to_string(), None);
+        item.username = "octo".to_string();
+        item.password = "hunter2".to_string();
+        item.url = "https://github.com".to_string();
+        item.notes = "2FA on".to_string();
+        item.tags = vec!["dev".to_string(), "work".to_string()];
+        item
+    }
+
+    #[test]
+    fn test_all_placeholders_substituted() {
+        let rendered = format_item(
+            &item(),
+            "{title}|{username}|{password}|{url}|{notes}|{tags}",
+        );
+        assert_eq!(
+            rendered,
+            "GitHub|octo|hunter2|https://github.com|2FA on|dev, work"
+        );
+        assert_eq!(
+            format_item(&item(), DEFAULT_ITEM_TEMPLATE),
+            "GitHub: octo / hunter2"
+        );
+    }
+
+    #[test]
+    fn test_unknown_placeholders_render_literally() {
+        assert_eq!(
+            format_item(&item(), "{title} {totp} {}"),
+            "GitHub {totp} {}"
+        );
+        assert_eq!(format_item(&item(), "{title} {user"), "GitHub {user");
+        assert_eq!(format_item(&item(), "a } b"), "a } b");
+    }
+
+    #[test]
+    fn test_includes_password() {
+        assert!(includes_password(DEFAULT_ITEM_TEMPLATE));
+        assert!(!includes_password("{title} {url}"));
+    }
+}
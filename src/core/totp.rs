@@ -0,0 +1,134 @@
+//! Time-based one-time passwords (RFC 6238) with the common defaults: HMAC-SHA1,
+//! 30-second windows and 6 digits.
+
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use zeroize::Zeroizing;
+
+use crate::error::{Result, VaulturaError};
+
+/// Length of a TOTP window in seconds.
+pub const PERIOD_SECS: u64 = 30;
+
+const DIGITS: u32 = 6;
+
+/// With fewer seconds than this left in the window, a copy takes the next code,
+/// since the current one would expire before it could be pasted.
+pub const ROLLOVER_MARGIN_SECS: u64 = 5;
+
+/// A code picked for copying at a given moment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopiedCode {
+    pub code: String,
+    /// The code belongs to the window after the current one.
+    pub next_window: bool,
+    /// Seconds until the copied code's window starts (0 for the current one)
+    /// plus its whole length, i.e. how long it stays valid.
+    pub valid_for_secs: u64,
+}
+
+/// The code for the window containing `unix_time`.
+pub fn code_at(secret: &str, unix_time: u64) -> Result<String> {
+    let key = decode_base32(secret)?;
+    Ok(hotp(&key, unix_time / PERIOD_SECS))
+}
+
+/// The code to copy at `unix_time`: the current one, or the next one when the
+/// current window ends within `ROLLOVER_MARGIN_SECS`.
+pub fn code_for_copy(secret: &str, unix_time: u64) -> Result<CopiedCode> {
+    let key = decode_base32(secret)?;
+    let counter = unix_time / PERIOD_SECS;
+    let remaining = PERIOD_SECS - unix_time % PERIOD_SECS;
+    Ok(if remaining < ROLLOVER_MARGIN_SECS {
+        CopiedCode {
+            code: hotp(&key, counter + 1),
+            next_window: true,
+            valid_for_secs: remaining + PERIOD_SECS,
+        }
+    } else {
+        CopiedCode {
+            code: hotp(&key, counter),
+            next_window: false,
+            valid_for_secs: remaining,
+        }
+    })
+}
+
+/// HOTP (RFC 4226) for `counter`, truncated to `DIGITS` decimal digits.
+fn hotp(key: &[u8], counter: u64) -> String {
+    let mut hmac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC takes any key length");
+    hmac.update(&counter.to_be_bytes());
+    let mac = hmac.finalize().into_bytes();
+    let offset = (mac[19] & 0x0f) as usize;
+    let binary = u32::from_be_bytes(mac[offset..offset + 4].try_into().unwrap()) & 0x7fff_ffff;
+    format!(
+        "{:0width$}",
+        binary % 10u32.pow(DIGITS),
+        width = DIGITS as usize
+    )
+}
+
+/// Decode an RFC 4648 base32 secret, ignoring case, spaces, dashes and padding.
+fn decode_base32(secret: &str) -> Result<Zeroizing<Vec<u8>>> {
+    let invalid = || VaulturaError::Config("TOTP secret is not valid base32".to_string());
+    let mut out = Zeroizing::new(Vec::with_capacity(secret.len() * 5 / 8));
+    let mut buffer = 0u64;
+    let mut bits = 0;
+    for c in secret.chars() {
+        let value = match c.to_ascii_uppercase() {
+            ' ' | '-' | '=' => continue,
+            c @ 'A'..='Z' => c as u64 - 'A' as u64,
+            c @ '2'..='7' => c as u64 - '2' as u64 + 26,
+            _ => return Err(invalid()),
+        };
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    if out.is_empty() {
+        return Err(invalid());
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The RFC 6238 SHA-1 test key, "12345678901234567890", in base32.
+    const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn test_rfc_6238_vectors() {
+        // RFC 6238 lists 8-digit codes; these are their last 6 digits.
+        assert_eq!(code_at(RFC_SECRET, 59).unwrap(), "287082");
+        assert_eq!(code_at(RFC_SECRET, 1111111109).unwrap(), "081804");
+        assert_eq!(code_at(RFC_SECRET, 2000000000).unwrap(), "279037");
+        assert_eq!(
+            code_at("gezd gnbv-gy3t qojq gezd gnbv gy3t qojq", 59).unwrap(),
+            "287082"
+        );
+        assert!(code_at("not base32!", 59).is_err());
+    }
+
+    #[test]
+    fn test_copy_takes_next_code_near_rollover() {
+        let window_start = 1111111110 - 1111111110 % PERIOD_SECS;
+        let current = code_at(RFC_SECRET, window_start).unwrap();
+        let next = code_at(RFC_SECRET, window_start + PERIOD_SECS).unwrap();
+        assert_ne!(current, next);
+
+        let late = code_for_copy(RFC_SECRET, window_start + 28).unwrap();
+        assert_eq!(late.code, next);
+        assert!(late.next_window);
+        assert_eq!(late.valid_for_secs, 32);
+
+        let early = code_for_copy(RFC_SECRET, window_start + 5).unwrap();
+        assert_eq!(early.code, current);
+        assert!(!early.next_window);
+        assert_eq!(early.valid_for_secs, 25);
+    }
+}
@@ -0,0 +1,74 @@
+/// Extract the host portion of a URL-ish string, without scheme, credentials, port, or path.
+pub fn host(url: &str) -> Option<&str> {
+    let url = url.trim();
+    let rest = match url.find("://") {
+        Some(idx) => &url[idx + 3..],
+        None => url,
+    };
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host_port = authority.rsplit('@').next().unwrap_or("");
+    let host = host_port.split(':').next().unwrap_or("");
+    if host.is_empty() {
+        None
+    } else {
+        Some(host)
+    }
+}
+
+/// Approximate the registrable domain by keeping the last two labels of the host.
+pub fn registrable_domain(url: &str) -> Option<String> {
+    let host = host(url)?.trim_end_matches('.').to_lowercase();
+    let labels: Vec<&str> = host.split('.').filter(|l| !l.is_empty()).collect();
+    match labels.len() {
+        0 => None,
+        1 => Some(labels[0].to_string()),
+        n => Some(labels[n - 2..].join(".")),
+    }
+}
+
+/// First letter of the registrable domain, uppercased, for use as a list badge.
+pub fn domain_initial(url: &str) -> Option<char> {
+    registrable_domain(url)?
+        .chars()
+        .find(|c| c.is_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_initial_strips_www_and_path() {
+        assert_eq!(domain_initial("https://www.github.com/x"), Some('G'));
+    }
+
+    #[test]
+    fn test_domain_initial_uses_registrable_domain() {
+        assert_eq!(domain_initial("https://accounts.google.com"), Some('G'));
+        assert_eq!(
+            domain_initial("user:pw@mail.example.org:8443/inbox"),
+            Some('E')
+        );
+        assert_eq!(domain_initial("amazon.com"), Some('A'));
+    }
+
+    #[test]
+    fn test_domain_initial_without_url() {
+        assert_eq!(domain_initial(""), None);
+        assert_eq!(domain_initial("   "), None);
+        assert_eq!(domain_initial("https://"), None);
+    }
+
+    #[test]
+    fn test_registrable_domain() {
+        assert_eq!(
+            registrable_domain("https://www.github.com/x").as_deref(),
+            Some("github.com")
+        );
+        assert_eq!(
+            registrable_domain("localhost:3000").as_deref(),
+            Some("localhost")
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use rand::Rng;
+
+use crate::core::url;
+use crate::error::{Result, VaulturaError};
+
+const HANDLE_FIRST: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const HANDLE_REST: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Random lowercase alphanumeric handle that starts with a letter.
+pub fn generate_handle(len: usize) -> String {
+    let mut rng = rand::thread_rng();
+    (0..len)
+        .map(|i| {
+            let set = if i == 0 { HANDLE_FIRST } else { HANDLE_REST };
+            set[rng.gen_range(0..set.len())] as char
+        })
+        .collect()
+}
+
+/// Plus-addressed alias: `me@x.com` with tag `github` becomes `me+github@x.com`.
+///
+/// The tag is lowercased and reduced to letters, digits, `-` and `_`.
+pub fn generate_plus_alias(base_email: &str, tag: &str) -> Result<String> {
+    let base_email = base_email.trim();
+    let (local, domain) = base_email
+        .split_once('@')
+        .filter(|(local, domain)| !local.is_empty() && !domain.is_empty() && !domain.contains('@'))
+        .ok_or_else(|| {
+            VaulturaError::Config(format!("\"{base_email}\" is not an email address"))
+        })?;
+    let tag = alias_tag(tag);
+    if tag.is_empty() {
+        return Err(VaulturaError::Config("Alias tag is empty".to_string()));
+    }
+    Ok(format!("{local}+{tag}@{domain}"))
+}
+
+/// Tag for an item's alias: the site name from its URL, else its title.
+pub fn alias_tag_for(url: &str, title: &str) -> String {
+    let site = url::registrable_domain(url)
+        .and_then(|domain| domain.split('.').next().map(str::to_string))
+        .unwrap_or_default();
+    let tag = alias_tag(&site);
+    if tag.is_empty() {
+        alias_tag(title)
+    } else {
+        tag
+    }
+}
+
+fn alias_tag(raw: &str) -> String {
+    raw.chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_length_and_charset() {
+        for len in [1, 8, 24] {
+            let handle = generate_handle(len);
+            assert_eq!(handle.len(), len);
+            assert!(handle.chars().next().unwrap().is_ascii_lowercase());
+            assert!(handle
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
+        }
+        assert_eq!(generate_handle(0), "");
+    }
+
+    #[test]
+    fn test_plus_alias() {
+        assert_eq!(
+            generate_plus_alias("me@x.com", "github").unwrap(),
+            "me+github@x.com"
+        );
+        assert_eq!(
+            generate_plus_alias(" me@x.com ", "My Bank!").unwrap(),
+            "me+mybank@x.com"
+        );
+        assert!(generate_plus_alias("me.x.com", "github").is_err());
+        assert!(generate_plus_alias("@x.com", "github").is_err());
+        assert!(generate_plus_alias("me@x.com", "!!").is_err());
+    }
+
+    #[test]
+    fn test_alias_tag_prefers_site_name() {
+        assert_eq!(
+            alias_tag_for("https://www.github.com/login", "Code"),
+            "github"
+        );
+        assert_eq!(alias_tag_for("", "Work VPN"), "workvpn");
+    }
+}
@@ -1,10 +1,28 @@
+use std::collections::HashSet;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use secrecy::{ExposeSecret, SecretBox};
+use subtle::ConstantTimeEq;
 use uuid::Uuid;
-
-use crate::core::models::{Group, Item, KdfParams, PasswordHistoryEntry, VaultPayload};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::core::changes::{ChangeSummary, Snapshot};
+use crate::core::models::{
+    Attachment, EmbeddedFile, Group, Item, ItemKind, KdfParams, PasswordHistoryEntry, VaultMeta,
+    VaultPayload,
+};
+use crate::core::{portable, sanitize};
+use crate::crypto::session::SessionKey;
+use crate::crypto::shamir::{self, Share};
 use crate::error::{Result, VaulturaError};
+use crate::storage::archive::{self, ArchiveFormat};
+use crate::storage::atomic;
+use crate::storage::format::ARCHIVE_MAGIC;
+use crate::storage::legacy;
+use crate::storage::sealed;
 use crate::storage::vault_file;
 
 /// Draft for creating or editing items (used by the UI layer).
@@ -14,32 +32,481 @@ pub struct ItemDraft {
     pub username: String,
     pub password: String,
     pub url: String,
+    pub urls: Vec<String>,
     pub notes: String,
     pub tags: Vec<String>,
     pub group_id: Option<Uuid>,
+    pub attachments: Vec<Attachment>,
+    pub otp_secret: Option<String>,
+    pub notes_secret: bool,
+    pub kind: ItemKind,
+    pub launch_command: Option<String>,
+    pub sensitive: bool,
+}
+
+/// How `VaultService::import` treats incoming items whose id already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Keep the existing item and drop the incoming one.
+    #[default]
+    SkipExisting,
+    /// Add the incoming item under a fresh id with " (imported)" appended to its title.
+    ImportAsNew,
+    /// Replace the existing item when the incoming one was modified more recently.
+    PreferNewer,
+}
+
+/// How far an [`ImportJob`] has got, counted in items.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportProgress {
+    pub processed: usize,
+    pub total: usize,
+}
+
+impl ImportProgress {
+    pub fn is_done(&self) -> bool {
+        self.processed >= self.total
+    }
+
+    /// Status-bar text such as "Importing 430/2000…".
+    pub fn status(&self) -> String {
+        format!("Importing {}/{}…", self.processed, self.total)
+    }
+}
+
+/// The contents of an import file, decrypted and parsed but not yet merged.
+pub enum ImportData {
+    /// A vault export or JSON archive: ids are kept and merged like `import`.
+    Items {
+        groups: Vec<Group>,
+        items: Vec<Item>,
+    },
+    /// A CSV archive: each row becomes a new item, grouped by name.
+    Csv(portable::CsvImport),
+}
+
+impl ImportData {
+    /// Decrypt and parse a vault export or an encrypted archive, told apart by their
+    /// magic bytes. No vault is involved, so this can run on a worker thread while the
+    /// slow key derivation would otherwise freeze the UI.
+    pub fn read(path: &Path, password: &str) -> Result<Self> {
+        let mut magic = [0u8; 4];
+        std::fs::File::open(path).and_then(|mut file| file.read_exact(&mut magic))?;
+        if &magic != ARCHIVE_MAGIC {
+            let payload = vault_file::import_vault(path, password)?;
+            return Ok(Self::Items {
+                groups: payload.groups,
+                items: payload.items,
+            });
+        }
+        Self::read_archive(path, password)
+    }
+
+    /// Decrypt and parse an encrypted archive in either of its formats.
+    fn read_archive(path: &Path, password: &str) -> Result<Self> {
+        let (format, contents) = archive::read_archive(path, password)?;
+        let contents = Zeroizing::new(contents);
+        Ok(match format {
+            ArchiveFormat::Json => {
+                let (groups, items) = portable::from_json(&contents)?;
+                Self::Items { groups, items }
+            }
+            ArchiveFormat::Csv => Self::Csv(portable::from_csv(&contents)?),
+        })
+    }
+}
+
+/// An import applied a batch at a time with `VaultService::import_step`, so the caller
+/// can report progress in between. `VaultService::cancel_import` puts the vault back
+/// exactly as it was before `begin_import`; `finish_import` keeps the result.
+pub struct ImportJob {
+    groups: Vec<Group>,
+    pending: PendingImport,
+    mode: ImportMode,
+    progress: ImportProgress,
+    before: PayloadBackup,
+    /// Groups and items (or CSV rows) added so far.
+    imported: usize,
+    skipped_items: usize,
+    /// Groups left out for the group quota; references to them are dropped.
+    skipped_groups: HashSet<Uuid>,
+    /// Items and CSV rows left without their group because of the group quota.
+    ungrouped: usize,
+}
+
+enum PendingImport {
+    Items(std::vec::IntoIter<Item>),
+    Rows(std::vec::IntoIter<portable::CsvRecord>),
+}
+
+impl ImportJob {
+    pub fn progress(&self) -> ImportProgress {
+        self.progress
+    }
+}
+
+impl Drop for ImportJob {
+    fn drop(&mut self) {
+        match &mut self.pending {
+            PendingImport::Items(items) => items.as_mut_slice().iter_mut().for_each(wipe_item),
+            PendingImport::Rows(rows) => {
+                for row in rows.as_mut_slice() {
+                    row.password.zeroize();
+                    row.notes.zeroize();
+                }
+            }
+        }
+    }
+}
+
+/// What one `merge_batch` added and what the quotas made it skip.
+struct MergedBatch {
+    count: usize,
+    skipped_items: usize,
+    ungrouped: usize,
+}
+
+/// The vault as held in memory before an import (sealed passwords included), and
+/// whether it had unsaved changes then.
+#[derive(Default)]
+struct PayloadBackup {
+    payload: VaultPayload,
+    dirty: bool,
+}
+
+impl Drop for PayloadBackup {
+    fn drop(&mut self) {
+        wipe_secrets(&mut self.payload);
+    }
+}
+
+/// What `VaultService::compact` should remove besides orphaned references.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompactOptions {
+    /// Delete every item's previous passwords.
+    pub clear_history: bool,
+    /// Report what would be removed without changing or saving the vault.
+    pub dry_run: bool,
+}
+
+/// What a compaction removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactReport {
+    pub history_entries_removed: usize,
+    /// Group and parent references pointing at groups that no longer exist.
+    pub orphaned_references_removed: usize,
+    pub bytes_before: u64,
+    /// Equal to `bytes_before` for a dry run.
+    pub bytes_after: u64,
+    /// Items and groups that were (or, for a dry run, would be) changed.
+    pub affected_items: Vec<Uuid>,
+    pub affected_groups: Vec<Uuid>,
+    pub dry_run: bool,
+}
+
+impl CompactReport {
+    pub fn bytes_removed(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+}
+
+/// What a compaction touches; shared by real and dry runs so previews match.
+struct CompactPlan {
+    history_entries: usize,
+    /// Items whose password history is cleared.
+    history_items: Vec<Uuid>,
+    /// Groups whose parent no longer exists.
+    orphaned_parents: Vec<Uuid>,
+    /// Items whose group no longer exists.
+    orphaned_items: Vec<Uuid>,
+}
+
+impl CompactPlan {
+    fn select(payload: &VaultPayload, options: CompactOptions) -> Self {
+        let with_history = payload
+            .items
+            .iter()
+            .filter(|i| options.clear_history && !i.password_history.is_empty());
+        let group_ids: HashSet<Uuid> = payload.groups.iter().map(|g| g.id).collect();
+        Self {
+            history_entries: with_history.clone().map(|i| i.password_history.len()).sum(),
+            history_items: with_history.map(|i| i.id).collect(),
+            orphaned_parents: payload
+                .groups
+                .iter()
+                .filter(|g| g.parent_id.is_some_and(|p| !group_ids.contains(&p)))
+                .map(|g| g.id)
+                .collect(),
+            orphaned_items: payload
+                .items
+                .iter()
+                .filter(|i| i.group_id.is_some_and(|g| !group_ids.contains(&g)))
+                .map(|i| i.id)
+                .collect(),
+        }
+    }
+
+    fn affected_items(&self) -> Vec<Uuid> {
+        let mut ids = self.history_items.clone();
+        for id in &self.orphaned_items {
+            if !ids.contains(id) {
+                ids.push(*id);
+            }
+        }
+        ids
+    }
+}
+
+/// Item text fields `VaultService::replace_in_field` can rewrite; secrets are never
+/// rewritten in bulk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextField {
+    Title,
+    Username,
+    Url,
+    Notes,
+}
+
+impl TextField {
+    fn get(self, item: &Item) -> &str {
+        match self {
+            Self::Title => &item.title,
+            Self::Username => &item.username,
+            Self::Url => &item.url,
+            Self::Notes => &item.notes,
+        }
+    }
+
+    fn get_mut(self, item: &mut Item) -> &mut String {
+        match self {
+            Self::Title => &mut item.title,
+            Self::Username => &mut item.username,
+            Self::Url => &mut item.url,
+            Self::Notes => &mut item.notes,
+        }
+    }
+}
+
+/// What a tag rename or field replacement changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BulkEditReport {
+    /// Items retagged, or occurrences replaced.
+    pub changes: usize,
+    /// Items that were (or, for a dry run, would be) changed.
+    pub affected_items: Vec<Uuid>,
+    pub dry_run: bool,
+}
+
+/// What `VaultService::dedupe` merged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DedupeReport {
+    /// The item each set of duplicates was (or would be) merged into.
+    pub kept_items: Vec<Uuid>,
+    /// Duplicates deleted once merged.
+    pub removed_items: Vec<Uuid>,
+    pub dry_run: bool,
+}
+
+impl ItemDraft {
+    /// Strip leading and trailing whitespace from the username and password.
+    pub fn trim_credentials(&mut self) {
+        self.username = self.username.trim().to_string();
+        self.password = self.password.trim().to_string();
+    }
 }
 
 pub struct VaultService {
     vault_path: PathBuf,
+    /// Vault opened instead when the entered password does not unlock `vault_path`.
+    decoy_path: Option<PathBuf>,
+    /// Whether the decoy vault is the one currently unlocked.
+    decoy_active: bool,
     password: Option<String>,
     kdf_params: KdfParams,
     payload: Option<VaultPayload>,
     dirty: bool,
+    /// Re-read and decrypt the file after each save; see `vault_file::write_vault_verified`.
+    verify_after_write: bool,
+    /// Keep item passwords encrypted under `session_key` while unlocked.
+    seal_passwords: bool,
+    session_key: Option<SessionKey>,
+    /// Pad the saved plaintext to a multiple of this many bytes (0 = no padding).
+    pad_to_block: usize,
+    /// State at unlock or the last save, compared against by `diff_since_save`.
+    saved_snapshot: Option<Snapshot>,
+    /// Strip invisible characters from imported usernames and passwords; see `core::sanitize`.
+    sanitize_imports: bool,
+    /// Usernames and passwords changed by sanitizing during the last import.
+    sanitized_fields: usize,
+    /// Rows of the last CSV import that were repaired or skipped (see `portable::from_csv`),
+    /// and anything the last import left out to stay within `max_items`/`max_groups`.
+    import_warnings: Vec<String>,
+    /// Most items and groups the vault may hold; `None` is unlimited.
+    max_items: Option<usize>,
+    max_groups: Option<usize>,
+    /// Keep the vault as it was before each import so `undo_last_import` can restore it.
+    backup_before_import: bool,
+    import_backup: Option<PayloadBackup>,
 }
 
 impl VaultService {
     pub fn new(vault_path: PathBuf, kdf_params: KdfParams) -> Self {
         Self {
             vault_path,
+            decoy_path: None,
+            decoy_active: false,
             password: None,
             kdf_params,
             payload: None,
             dirty: false,
+            verify_after_write: false,
+            seal_passwords: false,
+            session_key: None,
+            pad_to_block: 0,
+            saved_snapshot: None,
+            sanitize_imports: true,
+            sanitized_fields: 0,
+            import_warnings: Vec::new(),
+            max_items: None,
+            max_groups: None,
+            backup_before_import: true,
+            import_backup: None,
+        }
+    }
+
+    pub fn set_pad_to_block(&mut self, block: usize) {
+        self.pad_to_block = block;
+    }
+
+    pub fn set_verify_after_write(&mut self, verify: bool) {
+        self.verify_after_write = verify;
+    }
+
+    pub fn set_sanitize_imports(&mut self, sanitize: bool) {
+        self.sanitize_imports = sanitize;
+    }
+
+    pub fn set_backup_before_import(&mut self, backup: bool) {
+        self.backup_before_import = backup;
+        if !backup {
+            self.import_backup = None;
+        }
+    }
+
+    /// Cap how many items and groups the vault may hold. Creating past a limit fails
+    /// with `QuotaExceeded`; imports stop adding at the limit and say what they left out.
+    pub fn set_quotas(&mut self, max_items: Option<usize>, max_groups: Option<usize>) {
+        self.max_items = max_items;
+        self.max_groups = max_groups;
+    }
+
+    /// How many more items and groups fit under the quotas (`usize::MAX` when unlimited).
+    fn quota_room(&self) -> Result<(usize, usize)> {
+        let payload = self.payload()?;
+        let room = |limit: Option<usize>, used: usize| {
+            limit.map_or(usize::MAX, |l| l.saturating_sub(used))
+        };
+        Ok((
+            room(self.max_items, payload.items.len()),
+            room(self.max_groups, payload.groups.len()),
+        ))
+    }
+
+    /// Whether `undo_last_import` has an import to revert.
+    pub fn can_undo_import(&self) -> bool {
+        self.import_backup.is_some()
+    }
+
+    /// Put the vault back exactly as it was before the last import. Edits made since
+    /// that import are discarded with it. The vault is left dirty if it was then, or
+    /// if it has been saved since, so the next save writes the restored state. Returns
+    /// false when there is nothing to undo.
+    pub fn undo_last_import(&mut self) -> Result<bool> {
+        let Some(mut backup) = self.import_backup.take() else {
+            return Ok(false);
+        };
+        self.restore(&mut backup)?;
+        Ok(true)
+    }
+
+    fn backup(&self) -> Result<PayloadBackup> {
+        Ok(PayloadBackup {
+            payload: self.payload()?.clone(),
+            dirty: self.dirty,
+        })
+    }
+
+    /// Record the pre-import state for `undo_last_import`, when enabled.
+    fn backup_for_undo(&mut self) -> Result<()> {
+        self.import_backup = if self.backup_before_import {
+            Some(self.backup()?)
+        } else {
+            None
+        };
+        Ok(())
+    }
+
+    fn restore(&mut self, backup: &mut PayloadBackup) -> Result<()> {
+        let payload = self.payload_mut()?;
+        wipe_secrets(payload);
+        *payload = std::mem::take(&mut backup.payload);
+        self.dirty = backup.dirty;
+        self.sanitized_fields = 0;
+        self.import_warnings.clear();
+        Ok(())
+    }
+
+    /// How many usernames and passwords the last import cleaned up.
+    pub fn sanitized_fields(&self) -> usize {
+        self.sanitized_fields
+    }
+
+    /// What the last import had to repair or skip, one message per problem.
+    pub fn import_warnings(&self) -> &[String] {
+        &self.import_warnings
+    }
+
+    /// Clean a username and password imported from a foreign format (CSV, KeePass) when
+    /// `sanitize_imports` is on. Vaultura's own exports are merged exactly as saved.
+    fn sanitize_credentials(&mut self, username: &mut String, password: &mut String) {
+        if self.sanitize_imports {
+            self.sanitized_fields += usize::from(sanitize::clean_in_place(username))
+                + usize::from(sanitize::clean_in_place(password));
+        }
+    }
+
+    /// Hold item passwords encrypted in memory, decrypting them only inside
+    /// `with_password` (or for an explicit `unsealed_item` copy).
+    ///
+    /// Only the password field is sealed; notes, SSH keys and TOTP secrets are not.
+    pub fn set_seal_passwords(&mut self, seal: bool) -> Result<()> {
+        self.seal_passwords = seal;
+        if seal {
+            return self.seal_items();
+        }
+        if let (Some(key), Some(payload)) = (self.session_key.take(), self.payload.as_mut()) {
+            for item in &mut payload.items {
+                unseal(&key, item)?;
+            }
         }
+        Ok(())
     }
 
+    pub fn set_decoy_path(&mut self, path: Option<PathBuf>) {
+        self.decoy_path = path;
+    }
+
+    /// Path of the vault that is (or would be) open; the decoy's path while it is unlocked.
     pub fn vault_path(&self) -> &Path {
-        &self.vault_path
+        match self.decoy_path {
+            Some(ref decoy) if self.decoy_active => decoy,
+            _ => &self.vault_path,
+        }
+    }
+
+    /// Key derivation parameters of the open vault file.
+    pub fn kdf_params(&self) -> &KdfParams {
+        &self.kdf_params
     }
 
     pub fn vault_exists(&self) -> bool {
@@ -59,27 +526,78 @@ impl VaultService {
         vault_file::create_vault(&self.vault_path, password, &self.kdf_params)?;
         self.password = Some(password.to_string());
         self.payload = Some(VaultPayload::default());
-        self.dirty = false;
+        self.mark_saved();
         Ok(())
     }
 
     /// Unlock an existing vault.
+    ///
+    /// If a decoy vault is configured and the password does not open the real one,
+    /// the decoy is opened instead; success looks like a normal unlock. Both files are
+    /// always tried, so the time an unlock takes does not tell which one opened.
     pub fn unlock(&mut self, password: &str) -> Result<()> {
-        let (payload, kdf_params) = vault_file::read_vault(&self.vault_path, password)?;
+        let real = vault_file::read_vault(&self.vault_path, password);
+        let decoy = self
+            .decoy_path
+            .as_deref()
+            .map(|path| vault_file::read_vault(path, password));
+        let (payload, kdf_params) = match (real, decoy) {
+            (Ok(opened), decoy) => {
+                if let Some(Ok((mut unused, _))) = decoy {
+                    wipe_secrets(&mut unused);
+                }
+                self.decoy_active = false;
+                opened
+            }
+            (Err(VaulturaError::WrongPassword), Some(decoy)) => {
+                let opened = decoy.map_err(|_| VaulturaError::WrongPassword)?;
+                self.decoy_active = true;
+                opened
+            }
+            (Err(e), _) => return Err(e),
+        };
         self.password = Some(password.to_string());
         self.kdf_params = kdf_params;
         self.payload = Some(payload);
-        self.dirty = false;
+        self.import_backup = None;
+        self.seal_items()?;
+        self.mark_saved();
         Ok(())
     }
 
     /// Lock the vault, wiping decrypted data from memory.
+    ///
+    /// The master password and every item's secrets are zeroized before being dropped.
     pub fn lock(&mut self) {
-        self.payload = None;
-        self.password = None;
+        if let Some(mut payload) = self.payload.take() {
+            wipe_secrets(&mut payload);
+        }
+        if let Some(mut password) = self.password.take() {
+            password.zeroize();
+        }
+        self.session_key = None;
+        self.saved_snapshot = None;
+        self.import_backup = None;
+        self.decoy_active = false;
         self.dirty = false;
     }
 
+    /// Whether any decrypted state or key material is still held.
+    #[cfg(test)]
+    pub(crate) fn holds_secrets(&self) -> bool {
+        self.payload.is_some()
+            || self.password.is_some()
+            || self.session_key.is_some()
+            || self.import_backup.is_some()
+    }
+
+    /// Check a re-entered master password against the one used to unlock the vault,
+    /// in time that does not depend on where the two differ.
+    pub fn verify_password(&self, password: &str) -> Result<bool> {
+        let current = self.password.as_deref().ok_or(VaulturaError::VaultLocked)?;
+        Ok(current.as_bytes().ct_eq(password.as_bytes()).into())
+    }
+
     /// Save the current payload to disk.
     pub fn save(&mut self) -> Result<()> {
         let password = self
@@ -87,12 +605,133 @@ impl VaultService {
             .as_ref()
             .ok_or(VaulturaError::VaultLocked)?
             .clone();
-        let payload = self.payload.as_ref().ok_or(VaulturaError::VaultLocked)?;
-        vault_file::write_vault(&self.vault_path, &password, &self.kdf_params, payload)?;
+        self.with_plain_payload(|payload| {
+            if self.verify_after_write {
+                vault_file::write_vault_verified(
+                    self.vault_path(),
+                    &password,
+                    &self.kdf_params,
+                    payload,
+                    self.pad_to_block,
+                )
+            } else {
+                vault_file::write_vault_padded(
+                    self.vault_path(),
+                    &password,
+                    &self.kdf_params,
+                    payload,
+                    self.pad_to_block,
+                )
+            }
+        })?;
+        self.mark_saved();
+        Ok(())
+    }
+
+    /// Items and groups added, modified or removed since unlock or the last save.
+    pub fn diff_since_save(&self) -> Vec<ChangeSummary> {
+        match (&self.saved_snapshot, &self.payload) {
+            (Some(snapshot), Some(payload)) => snapshot.diff(payload),
+            _ => Vec::new(),
+        }
+    }
+
+    fn mark_saved(&mut self) {
         self.dirty = false;
+        // The file on disk now holds the imported items, so going back to the
+        // pre-import state is an unsaved change.
+        if let Some(backup) = &mut self.import_backup {
+            backup.dirty = true;
+        }
+        self.saved_snapshot = self.payload.as_ref().map(Snapshot::take);
+    }
+
+    /// Re-read the open vault from disk, discarding unsaved in-memory edits.
+    ///
+    /// A vault that has never been written is reset to an empty payload. The discarded
+    /// payload is wiped, and the pre-import state goes with it.
+    pub fn reload(&mut self) -> Result<()> {
+        let password = self.password.as_deref().ok_or(VaulturaError::VaultLocked)?;
+        let payload = if self.vault_path().exists() {
+            let (payload, kdf_params) = vault_file::read_vault(self.vault_path(), password)?;
+            self.kdf_params = kdf_params;
+            payload
+        } else {
+            VaultPayload::default()
+        };
+        if let Some(mut old) = self.payload.take() {
+            wipe_secrets(&mut old);
+        }
+        self.payload = Some(payload);
+        self.import_backup = None;
+        self.seal_items()?;
+        self.mark_saved();
         Ok(())
     }
 
+    /// Trim the vault per `options`, drop references to missing groups, and save.
+    ///
+    /// Refused while there are unsaved changes, so that only the compaction is written.
+    /// The trimmed payload replaces the open one only once it is on disk.
+    ///
+    /// With `options.dry_run` the report is computed from the same selection but
+    /// nothing is changed or written.
+    pub fn compact(&mut self, options: CompactOptions) -> Result<CompactReport> {
+        let bytes_before = file_len(self.vault_path());
+        let plan = CompactPlan::select(self.payload()?, options);
+        let mut report = CompactReport {
+            history_entries_removed: plan.history_entries,
+            orphaned_references_removed: plan.orphaned_parents.len() + plan.orphaned_items.len(),
+            bytes_before,
+            bytes_after: bytes_before,
+            affected_items: plan.affected_items(),
+            affected_groups: plan.orphaned_parents.clone(),
+            dry_run: options.dry_run,
+        };
+        if options.dry_run {
+            return Ok(report);
+        }
+        if self.is_dirty() {
+            return Err(VaulturaError::UnsavedChanges);
+        }
+
+        let mut compacted = self.payload()?.clone();
+        for item in &mut compacted.items {
+            if plan.history_items.contains(&item.id) {
+                for entry in &mut item.password_history {
+                    entry.password.zeroize();
+                }
+                item.password_history.clear();
+            }
+            if plan.orphaned_items.contains(&item.id) {
+                item.group_id = None;
+            }
+        }
+        for group in &mut compacted.groups {
+            if plan.orphaned_parents.contains(&group.id) {
+                group.parent_id = None;
+            }
+        }
+
+        let mut previous = self.payload.replace(compacted);
+        if let Err(e) = self.save() {
+            let mut compacted = std::mem::replace(&mut self.payload, previous);
+            compacted.iter_mut().for_each(wipe_secrets);
+            return Err(e);
+        }
+        previous.iter_mut().for_each(wipe_secrets);
+        report.bytes_after = file_len(self.vault_path());
+        Ok(report)
+    }
+
+    fn item_mut(&mut self, id: Uuid) -> Result<&mut Item> {
+        self.payload_mut()?
+            .items
+            .iter_mut()
+            .find(|i| i.id == id)
+            .ok_or(VaulturaError::ItemNotFound(id))
+    }
+
     fn payload(&self) -> Result<&VaultPayload> {
         self.payload.as_ref().ok_or(VaulturaError::VaultLocked)
     }
@@ -101,6 +740,96 @@ impl VaultService {
         self.payload.as_mut().ok_or(VaulturaError::VaultLocked)
     }
 
+    /// Encrypt any plaintext item passwords, current and historic, under the session key,
+    /// when sealing is on.
+    fn seal_items(&mut self) -> Result<()> {
+        if !self.seal_passwords {
+            return Ok(());
+        }
+        let Some(payload) = self.payload.as_mut() else {
+            return Ok(());
+        };
+        let key = self.session_key.get_or_insert_with(SessionKey::generate);
+        for item in &mut payload.items {
+            if !item.password.is_empty() {
+                item.sealed_password = Some(key.seal(&item.password)?);
+                item.password.zeroize();
+            }
+            for entry in &mut item.password_history {
+                if !entry.password.is_empty() {
+                    entry.sealed = Some(key.seal(&entry.password)?);
+                    entry.password.zeroize();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn plain_password(&self, item: &Item) -> Result<Zeroizing<String>> {
+        match (&item.sealed_password, &self.session_key) {
+            (Some(sealed), Some(key)) => key.open(sealed),
+            _ => Ok(Zeroizing::new(item.password.clone())),
+        }
+    }
+
+    /// Run `f` on the item's password, decrypting it only for the duration of the call.
+    pub fn with_password<T>(&self, id: Uuid, f: impl FnOnce(&str) -> T) -> Result<T> {
+        let password = self.plain_password(self.get_item(id)?)?;
+        Ok(f(&password))
+    }
+
+    /// A copy of the item with its password in plaintext, for display or editing.
+    pub fn unsealed_item(&self, id: Uuid) -> Result<Item> {
+        let mut item = self.get_item(id)?.clone();
+        if let Some(key) = &self.session_key {
+            unseal(key, &mut item)?;
+        }
+        Ok(item)
+    }
+
+    /// Run `f` on the payload with every password in plaintext, as written to disk.
+    ///
+    /// When passwords are sealed this works on a temporary copy that is wiped afterwards.
+    fn with_plain_payload<T>(&self, f: impl FnOnce(&VaultPayload) -> Result<T>) -> Result<T> {
+        let payload = self.payload()?;
+        let Some(key) = &self.session_key else {
+            return f(payload);
+        };
+        let mut plain = payload.clone();
+        let result = plain
+            .items
+            .iter_mut()
+            .try_for_each(|item| unseal(key, item))
+            .and_then(|()| f(&plain));
+        wipe_secrets(&mut plain);
+        result
+    }
+
+    pub fn meta(&self) -> Result<&VaultMeta> {
+        Ok(&self.payload()?.meta)
+    }
+
+    /// Set (or with `None`, remove) this vault's own auto-lock timeout.
+    pub fn set_auto_lock_override(&mut self, secs: Option<u64>) -> Result<()> {
+        self.payload_mut()?.meta.auto_lock_secs_override = secs;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Turn recording of previous passwords on or off for this vault. Turning it off
+    /// keeps the history already recorded.
+    pub fn set_record_password_history(&mut self, record: bool) -> Result<()> {
+        self.payload_mut()?.meta.record_password_history = record;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Whether the unlocked vault has neither items nor groups; false while locked.
+    pub fn is_empty(&self) -> bool {
+        self.payload()
+            .is_ok_and(|payload| payload.items.is_empty() && payload.groups.is_empty())
+    }
+
     // --- Groups ---
 
     pub fn groups(&self) -> Result<&[Group]> {
@@ -108,6 +837,12 @@ impl VaultService {
     }
 
     pub fn create_group(&mut self, name: String, parent_id: Option<Uuid>) -> Result<Uuid> {
+        if self.quota_room()?.1 == 0 {
+            return Err(VaulturaError::QuotaExceeded {
+                kind: "groups",
+                limit: self.max_groups.unwrap_or_default(),
+            });
+        }
         let group = Group::new(name, parent_id);
         let id = group.id;
         self.payload_mut()?.groups.push(group);
@@ -128,6 +863,20 @@ impl VaultService {
         Ok(())
     }
 
+    pub fn set_group_description(&mut self, id: Uuid, description: String) -> Result<()> {
+        let group = self
+            .payload_mut()?
+            .groups
+            .iter_mut()
+            .find(|g| g.id == id)
+            .ok_or(VaulturaError::GroupNotFound(id))?;
+        if group.description != description {
+            group.description = description;
+            self.dirty = true;
+        }
+        Ok(())
+    }
+
     pub fn delete_group(&mut self, id: Uuid) -> Result<()> {
         let payload = self.payload_mut()?;
         let existed = payload.groups.len();
@@ -172,33 +921,46 @@ impl VaultService {
     }
 
     pub fn create_item(&mut self, draft: ItemDraft) -> Result<Uuid> {
+        if self.quota_room()?.0 == 0 {
+            return Err(VaulturaError::QuotaExceeded {
+                kind: "items",
+                limit: self.max_items.unwrap_or_default(),
+            });
+        }
         let mut item = Item::new(draft.title, draft.group_id);
         item.username = draft.username;
         item.password = draft.password;
         item.url = draft.url;
         item.notes = draft.notes;
         item.tags = draft.tags;
+        item.attachments = draft.attachments;
+        item.otp_secret = draft.otp_secret;
+        item.urls = draft.urls;
+        item.notes_secret = draft.notes_secret;
+        item.kind = draft.kind;
+        item.launch_command = draft.launch_command;
+        item.sensitive = draft.sensitive;
         let id = item.id;
         self.payload_mut()?.items.push(item);
         self.dirty = true;
+        self.seal_items()?;
         Ok(id)
     }
 
     pub fn update_item(&mut self, id: Uuid, draft: ItemDraft) -> Result<()> {
-        let payload = self.payload_mut()?;
-        let item = payload
-            .items
-            .iter_mut()
-            .find(|i| i.id == id)
-            .ok_or(VaulturaError::ItemNotFound(id))?;
+        let current = self.plain_password(self.get_item(id)?)?;
+        let record_history = self.meta()?.record_password_history;
+        let item = self.item_mut(id)?;
 
         // Track password history if password changed
-        if item.password != draft.password && !item.password.is_empty() {
+        if record_history && *current != draft.password && !current.is_empty() {
             item.password_history.push(PasswordHistoryEntry {
-                password: item.password.clone(),
+                password: current.to_string(),
                 changed_at: Utc::now(),
+                sealed: None,
             });
         }
+        item.sealed_password = None;
 
         item.title = draft.title;
         item.username = draft.username;
@@ -207,9 +969,16 @@ impl VaultService {
         item.notes = draft.notes;
         item.tags = draft.tags;
         item.group_id = draft.group_id;
+        item.attachments = draft.attachments;
+        item.otp_secret = draft.otp_secret;
+        item.urls = draft.urls;
+        item.notes_secret = draft.notes_secret;
+        item.kind = draft.kind;
+        item.launch_command = draft.launch_command;
+        item.sensitive = draft.sensitive;
         item.modified_at = Utc::now();
         self.dirty = true;
-        Ok(())
+        self.seal_items()
     }
 
     pub fn delete_item(&mut self, id: Uuid) -> Result<()> {
@@ -223,34 +992,335 @@ impl VaultService {
         Ok(())
     }
 
-    /// Case-insensitive multi-token AND search across title, username, url, notes, and tags.
-    pub fn search(&self, query: &str) -> Result<Vec<&Item>> {
-        let payload = self.payload()?;
-        if query.is_empty() {
-            return Ok(payload.items.iter().collect());
-        }
+    /// Move the item above the previous item in its group. The stored order is the
+    /// manual order, so lists follow it unless sorted. False if it is already first.
+    pub fn move_item_up(&mut self, id: Uuid) -> Result<bool> {
+        self.move_item(id, true)
+    }
 
-        let tokens: Vec<String> = query
-            .to_lowercase()
-            .split_whitespace()
-            .map(String::from)
-            .collect();
+    /// Move the item below the next item in its group; false if it is already last.
+    pub fn move_item_down(&mut self, id: Uuid) -> Result<bool> {
+        self.move_item(id, false)
+    }
 
-        Ok(payload
-            .items
+    fn move_item(&mut self, id: Uuid, up: bool) -> Result<bool> {
+        let items = &mut self.payload_mut()?.items;
+        let pos = items
             .iter()
-            .filter(|item| {
-                let searchable = format!(
-                    "{} {} {} {} {}",
-                    item.title,
-                    item.username,
-                    item.url,
-                    item.notes,
-                    item.tags.join(" ")
-                )
-                .to_lowercase();
-
-                tokens
+            .position(|i| i.id == id)
+            .ok_or(VaulturaError::ItemNotFound(id))?;
+        let group_id = items[pos].group_id;
+        let neighbour = if up {
+            items[..pos].iter().rposition(|i| i.group_id == group_id)
+        } else {
+            items[pos + 1..]
+                .iter()
+                .position(|i| i.group_id == group_id)
+                .map(|offset| pos + 1 + offset)
+        };
+        let Some(neighbour) = neighbour else {
+            return Ok(false);
+        };
+        items.swap(pos, neighbour);
+        self.dirty = true;
+        Ok(true)
+    }
+
+    /// Put a previously deleted item back, keeping its id and timestamps. Fails with
+    /// `QuotaExceeded` if the vault has since filled up to `max_items`.
+    pub fn restore_item(&mut self, item: Item) -> Result<()> {
+        if self.quota_room()?.0 == 0 {
+            return Err(VaulturaError::QuotaExceeded {
+                kind: "items",
+                limit: self.max_items.unwrap_or_default(),
+            });
+        }
+        let payload = self.payload_mut()?;
+        if payload.items.iter().any(|i| i.id == item.id) {
+            return Err(VaulturaError::Integrity(format!(
+                "item {} already exists",
+                item.id
+            )));
+        }
+        payload.items.push(item);
+        self.dirty = true;
+        self.seal_items()
+    }
+
+    /// Keep (or stop keeping) this item's copied credentials on the clipboard.
+    pub fn set_no_clipboard_clear(&mut self, id: Uuid, keep: bool) -> Result<()> {
+        self.item_mut(id)?.no_clipboard_clear = keep;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Groups of two or more items sharing a title and username (and, with
+    /// `match_password`, a password), in vault order.
+    pub fn find_duplicates(&self, match_password: bool) -> Result<Vec<Vec<Uuid>>> {
+        let mut groups: Vec<(&Item, Zeroizing<String>, Vec<Uuid>)> = Vec::new();
+        for item in &self.payload()?.items {
+            let password = if match_password {
+                self.plain_password(item)?
+            } else {
+                Zeroizing::new(String::new())
+            };
+            match groups.iter_mut().find(|(first, first_password, _)| {
+                first.title == item.title
+                    && first.username == item.username
+                    && *first_password == password
+            }) {
+                Some((_, _, ids)) => ids.push(item.id),
+                None => groups.push((item, password, vec![item.id])),
+            }
+        }
+        Ok(groups
+            .into_iter()
+            .map(|(_, _, ids)| ids)
+            .filter(|ids| ids.len() > 1)
+            .collect())
+    }
+
+    /// Combine `ids` into the most recently modified of them, deleting the rest.
+    ///
+    /// The kept item gets the union of their tags, URLs and attachments, the earliest
+    /// creation time, and every other password in its history, whether or not history
+    /// recording is on. Empty fields are filled from the other items. Returns the kept
+    /// item's id.
+    pub fn merge_items(&mut self, ids: &[Uuid]) -> Result<Uuid> {
+        let mut items = ids
+            .iter()
+            .map(|&id| self.unsealed_item(id))
+            .collect::<Result<Vec<Item>>>()?;
+        items.sort_by_key(merge_rank);
+        items.dedup_by_key(|item| item.id);
+        if items.len() < 2 {
+            return Err(VaulturaError::Config(
+                "Select at least two items to merge".to_string(),
+            ));
+        }
+
+        let mut others = items.split_off(1);
+        let mut merged = items.remove(0);
+        for other in &mut others {
+            merged.created_at = merged.created_at.min(other.created_at);
+            for tag in other.tags.drain(..) {
+                if !merged.has_tag(&tag) {
+                    merged.tags.push(tag);
+                }
+            }
+            for url in std::mem::take(&mut other.urls)
+                .into_iter()
+                .chain(std::iter::once(std::mem::take(&mut other.url)))
+            {
+                if merged.url.is_empty() {
+                    merged.url = url;
+                } else if !url.is_empty() && !merged.all_urls().contains(&url.as_str()) {
+                    merged.urls.push(url);
+                }
+            }
+            for attachment in other.attachments.drain(..) {
+                if !merged.attachments.contains(&attachment) {
+                    merged.attachments.push(attachment);
+                }
+            }
+            for file in other.embedded.drain(..) {
+                if !merged.embedded.contains(&file) {
+                    merged.embedded.push(file);
+                }
+            }
+            // Notes are appended, never dropped, and stay secret if either side was.
+            if !other.notes.is_empty() && other.notes != merged.notes {
+                if merged.notes.is_empty() {
+                    merged.notes = std::mem::take(&mut other.notes);
+                } else {
+                    merged.notes.push_str("\n\n");
+                    merged.notes.push_str(&other.notes);
+                }
+                merged.notes_secret |= other.notes_secret;
+            }
+            if !merged.has_totp() {
+                merged.otp_secret = other.otp_secret.take();
+            }
+            if merged.launch_command.is_none() {
+                merged.launch_command = other.launch_command.take();
+            }
+            merged.sensitive |= other.sensitive;
+
+            merged.password_history.append(&mut other.password_history);
+            // Kept even when history recording is off: the merge deletes the other
+            // item, and this is the only place its password survives.
+            if !other.password.is_empty() && other.password != merged.password {
+                merged.password_history.push(PasswordHistoryEntry {
+                    password: std::mem::take(&mut other.password),
+                    changed_at: other.modified_at,
+                    sealed: None,
+                });
+            }
+        }
+        merged
+            .password_history
+            .sort_by_key(|entry| entry.changed_at);
+        merged
+            .password_history
+            .dedup_by(|a, b| a.password == b.password && a.changed_at == b.changed_at);
+        merged.modified_at = Utc::now();
+
+        let id = merged.id;
+        let payload = self.payload_mut()?;
+        payload
+            .items
+            .retain(|item| !others.iter().any(|other| other.id == item.id));
+        if let Some(slot) = payload.items.iter_mut().find(|item| item.id == id) {
+            *slot = merged;
+        }
+        for other in &mut others {
+            other.password.zeroize();
+            if let Some(ref mut secret) = other.otp_secret {
+                secret.zeroize();
+            }
+        }
+        self.dirty = true;
+        self.seal_items()?;
+        Ok(id)
+    }
+
+    /// Merge every set of duplicates found by `find_duplicates` with `merge_items`.
+    ///
+    /// With `dry_run` the report names the items that would be kept and deleted, and
+    /// nothing is changed.
+    pub fn dedupe(&mut self, match_password: bool, dry_run: bool) -> Result<DedupeReport> {
+        let mut report = DedupeReport {
+            dry_run,
+            ..Default::default()
+        };
+        for ids in self.find_duplicates(match_password)? {
+            let Some(kept) = ids
+                .iter()
+                .map(|&id| self.get_item(id))
+                .collect::<Result<Vec<_>>>()?
+                .into_iter()
+                .min_by_key(|item| merge_rank(item))
+                .map(|item| item.id)
+            else {
+                continue;
+            };
+            if !dry_run {
+                self.merge_items(&ids)?;
+            }
+            report.kept_items.push(kept);
+            report
+                .removed_items
+                .extend(ids.into_iter().filter(|&id| id != kept));
+        }
+        Ok(report)
+    }
+
+    /// Rename the tag `from` (case-insensitive) to `to` on every item carrying it.
+    ///
+    /// With `dry_run` the report counts the items that would be retagged, and nothing
+    /// is changed.
+    pub fn rename_tag(&mut self, from: &str, to: &str, dry_run: bool) -> Result<BulkEditReport> {
+        let to = to.trim();
+        if to.is_empty() {
+            return Err(VaulturaError::Config(
+                "Tag name cannot be empty".to_string(),
+            ));
+        }
+        let retagged: Vec<(Uuid, Vec<String>)> = self
+            .payload()?
+            .items
+            .iter()
+            .filter_map(|item| {
+                let tags = renamed_tags(&item.tags, from, to);
+                (tags != item.tags).then_some((item.id, tags))
+            })
+            .collect();
+        let report = BulkEditReport {
+            changes: retagged.len(),
+            affected_items: retagged.iter().map(|(id, _)| *id).collect(),
+            dry_run,
+        };
+        if dry_run || retagged.is_empty() {
+            return Ok(report);
+        }
+
+        for (id, tags) in retagged {
+            let item = self.item_mut(id)?;
+            item.tags = tags;
+            item.modified_at = Utc::now();
+        }
+        self.dirty = true;
+        Ok(report)
+    }
+
+    /// Replace every occurrence of `find` in `field` with `replace`, across all items.
+    ///
+    /// With `dry_run` the report counts the occurrences that would be replaced, and
+    /// nothing is changed.
+    pub fn replace_in_field(
+        &mut self,
+        field: TextField,
+        find: &str,
+        replace: &str,
+        dry_run: bool,
+    ) -> Result<BulkEditReport> {
+        if find.is_empty() {
+            return Err(VaulturaError::Config("Nothing to replace".to_string()));
+        }
+        let mut report = BulkEditReport {
+            dry_run,
+            ..Default::default()
+        };
+        for item in &self.payload()?.items {
+            let count = field.get(item).matches(find).count();
+            if count > 0 {
+                report.changes += count;
+                report.affected_items.push(item.id);
+            }
+        }
+        if dry_run || report.affected_items.is_empty() {
+            return Ok(report);
+        }
+
+        for &id in &report.affected_items {
+            let item = self.item_mut(id)?;
+            let value = field.get_mut(item);
+            // Notes may be secret, so the old text is wiped.
+            *value = Zeroizing::new(std::mem::take(value)).replace(find, replace);
+            item.modified_at = Utc::now();
+        }
+        self.dirty = true;
+        Ok(report)
+    }
+
+    /// Case-insensitive multi-token AND search across title, username, url, notes, and tags.
+    pub fn search(&self, query: &str) -> Result<Vec<&Item>> {
+        let payload = self.payload()?;
+        if query.is_empty() {
+            return Ok(payload.items.iter().collect());
+        }
+
+        let tokens: Vec<String> = query
+            .to_lowercase()
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+
+        Ok(payload
+            .items
+            .iter()
+            .filter(|item| {
+                let searchable = format!(
+                    "{} {} {} {} {}",
+                    item.title,
+                    item.username,
+                    item.url,
+                    item.notes,
+                    item.tags.join(" ")
+                )
+                .to_lowercase();
+
+                tokens
                     .iter()
                     .all(|token| searchable.contains(token.as_str()))
             })
@@ -269,34 +1339,582 @@ impl VaultService {
         }
     }
 
+    /// Every tag in the vault once, ignoring case, sorted; the first casing seen wins.
+    pub fn all_tags(&self) -> Result<Vec<String>> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in self.payload()?.items.iter().flat_map(|i| &i.tags) {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.clone());
+            }
+        }
+        tags.sort_by_key(|t| t.to_lowercase());
+        Ok(tags)
+    }
+
+    /// Items carrying `tag` (case-insensitive).
+    pub fn items_with_tag(&self, tag: &str) -> Result<Vec<&Item>> {
+        Ok(self
+            .payload()?
+            .items
+            .iter()
+            .filter(|i| i.has_tag(tag))
+            .collect())
+    }
+
+    /// Search within a group, optionally narrowed to items carrying `tag`.
+    pub fn filter_items(
+        &self,
+        query: &str,
+        tag: Option<&str>,
+        group_id: Option<Uuid>,
+    ) -> Result<Vec<&Item>> {
+        let mut results = self.search_in_group(query, group_id)?;
+        if let Some(tag) = tag {
+            let tagged: Vec<Uuid> = self.items_with_tag(tag)?.iter().map(|i| i.id).collect();
+            results.retain(|i| tagged.contains(&i.id));
+        }
+        Ok(results)
+    }
+
+    /// Items that have a TOTP secret (the "2FA" view).
+    pub fn items_with_totp(&self) -> Result<Vec<&Item>> {
+        Ok(self
+            .payload()?
+            .items
+            .iter()
+            .filter(|i| i.has_totp())
+            .collect())
+    }
+
+    // --- Embedded files ---
+
+    /// Copy the file at `path` into the item, refusing files larger than `max_bytes`.
+    ///
+    /// The limit is enforced on what is actually read, so a file that grows meanwhile,
+    /// or a pipe or device that reports no size, cannot get past it.
+    pub fn embed_file(&mut self, item_id: Uuid, path: &Path, max_bytes: u64) -> Result<()> {
+        let name = Attachment::from_path(path.to_path_buf()).name;
+        let mut file = std::fs::File::open(path)?;
+        let mut bytes = Vec::new();
+        (&mut file)
+            .take(max_bytes.saturating_add(1))
+            .read_to_end(&mut bytes)?;
+        if bytes.len() as u64 > max_bytes {
+            bytes.zeroize();
+            let size = file.metadata().map_or(0, |m| m.len());
+            return Err(VaulturaError::FileTooLarge {
+                name,
+                size: size.max(max_bytes + 1),
+                limit: max_bytes,
+            });
+        }
+        let item = self.item_mut(item_id)?;
+        item.embedded.push(EmbeddedFile { name, bytes });
+        item.modified_at = Utc::now();
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Write an embedded file into `dir` under its stored name, which must not exist
+    /// there yet, and return the path written.
+    ///
+    /// The name may come from an imported vault, so anything but a plain file name
+    /// (a path, `.`, `..` or an empty name) is refused rather than followed.
+    pub fn export_embedded(&self, item_id: Uuid, index: usize, dir: &Path) -> Result<PathBuf> {
+        let file = self
+            .get_item(item_id)?
+            .embedded
+            .get(index)
+            .ok_or_else(|| VaulturaError::Export(format!("no embedded file #{index}")))?;
+        let mut components = Path::new(&file.name).components();
+        let name = match (components.next(), components.next()) {
+            (Some(std::path::Component::Normal(name)), None) => name,
+            _ => {
+                return Err(VaulturaError::Export(format!(
+                    "{:?} is not a plain file name",
+                    file.name
+                )))
+            }
+        };
+        let dest = dir.join(name);
+        let mut out = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&dest)?;
+        out.write_all(&file.bytes)?;
+        Ok(dest)
+    }
+
+    /// Combined size of every embedded file in the vault.
+    pub fn embedded_total_bytes(&self) -> Result<u64> {
+        Ok(self
+            .payload()?
+            .items
+            .iter()
+            .flat_map(|item| &item.embedded)
+            .map(|file| file.bytes.len() as u64)
+            .sum())
+    }
+
     // --- Import/Export ---
 
     pub fn export(&self, path: &Path, password: &str) -> Result<()> {
+        self.with_plain_payload(|payload| {
+            vault_file::export_vault(path, password, &self.kdf_params, payload)
+        })
+    }
+
+    pub fn import(&mut self, path: &Path, password: &str, mode: ImportMode) -> Result<usize> {
+        let imported = vault_file::import_vault(path, password)?;
+        self.backup_for_undo()?;
+        self.merge(imported.groups, imported.items, mode)
+    }
+
+    /// Encrypt the whole vault under a random data key and split that key into `shares`
+    /// parts, `threshold` of which are needed to open the export. Returns the shares
+    /// as text; they are the only way back in, so nothing else is written.
+    pub fn export_sealed(&self, path: &Path, threshold: u8, shares: u8) -> Result<Vec<String>> {
+        let mut key = vec![0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        let key = SecretBox::new(Box::new(key));
+        let parts = shamir::split(key.expose_secret(), threshold, shares)?;
+
+        let plaintext = Zeroizing::new(self.with_plain_payload(|payload| {
+            bincode::serialize(payload).map_err(VaulturaError::from)
+        })?);
+        sealed::write_sealed(path, &key, threshold, &plaintext)?;
+        Ok(parts.iter().map(Share::encode).collect())
+    }
+
+    /// Merge a sealed export back in, given at least its threshold of shares.
+    pub fn import_sealed(&mut self, path: &Path, shares: &[String]) -> Result<usize> {
+        let threshold = sealed::read_threshold(path)?;
+        let parts = shares
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                Share::decode(s).map_err(|_| {
+                    VaulturaError::Import(format!("share {} is not a valid share", i + 1))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if parts.iter().any(|p| p.threshold != threshold) {
+            return Err(VaulturaError::Import(
+                "a share belongs to a different export".to_string(),
+            ));
+        }
+        let key = shamir::combine(&parts)?;
+        let key = SecretBox::new(Box::new(key.to_vec()));
+        let (plaintext, payload_version) = sealed::read_sealed(path, &key)?;
+        let plaintext = Zeroizing::new(plaintext);
+        let payload = legacy::decode_payload(&plaintext, payload_version)?;
+        self.backup_for_undo()?;
+        self.merge(payload.groups, payload.items, ImportMode::SkipExisting)
+    }
+
+    /// Write an unencrypted JSON inventory of groups and items that contains no secrets.
+    pub fn export_inventory(&self, path: &Path, include_usernames: bool) -> Result<()> {
         let payload = self.payload()?;
-        vault_file::export_vault(path, password, &self.kdf_params, payload)
+        let json = portable::to_inventory_json(&payload.groups, &payload.items, include_usernames)?;
+        atomic::write(path, &json)
+    }
+
+    /// Export groups and items as CSV or JSON, encrypted into a standalone archive.
+    pub fn export_encrypted_archive(
+        &self,
+        path: &Path,
+        password: &str,
+        format: ArchiveFormat,
+    ) -> Result<()> {
+        let contents = Zeroizing::new(self.with_plain_payload(|payload| match format {
+            ArchiveFormat::Csv => portable::to_csv(&payload.groups, &payload.items),
+            ArchiveFormat::Json => portable::to_json(&payload.groups, &payload.items),
+        })?);
+        archive::write_archive(path, password, &self.kdf_params, format, &contents)
+    }
+
+    /// Import an archive written by `export_encrypted_archive`.
+    ///
+    /// JSON archives keep ids and merge like `import`; CSV rows become new items,
+    /// with groups matched (or created) by name. Damaged CSV rows are repaired or
+    /// skipped rather than failing the import; see `import_warnings`.
+    pub fn import_encrypted_archive(&mut self, path: &Path, password: &str) -> Result<usize> {
+        let data = ImportData::read_archive(path, password)?;
+        let mut job = self.begin_import_data(data, ImportMode::SkipExisting)?;
+        if let Err(e) = self.import_step(&mut job, usize::MAX) {
+            self.cancel_import(job)?;
+            return Err(e);
+        }
+        Ok(self.finish_import(job))
     }
 
-    pub fn import(&mut self, path: &Path, password: &str) -> Result<usize> {
+    /// Start an import of `groups` and `items`, to be applied with `import_step`.
+    pub fn begin_import(
+        &mut self,
+        groups: Vec<Group>,
+        items: Vec<Item>,
+        mode: ImportMode,
+    ) -> Result<ImportJob> {
+        self.begin_import_data(ImportData::Items { groups, items }, mode)
+    }
+
+    /// Start an import of whatever `ImportData::read` found; see `begin_import`.
+    ///
+    /// CSV rows always become new items, so `mode` only applies to the other formats.
+    pub fn begin_import_data(&mut self, data: ImportData, mode: ImportMode) -> Result<ImportJob> {
+        let before = self.backup()?;
+        self.sanitized_fields = 0;
+        self.import_warnings.clear();
+        let (groups, pending, total) = match data {
+            ImportData::Items { groups, items } => {
+                let total = items.len();
+                (groups, PendingImport::Items(items.into_iter()), total)
+            }
+            ImportData::Csv(import) => {
+                self.import_warnings = import.warnings;
+                let total = import.records.len();
+                (
+                    Vec::new(),
+                    PendingImport::Rows(import.records.into_iter()),
+                    total,
+                )
+            }
+        };
+        Ok(ImportJob {
+            groups,
+            progress: ImportProgress {
+                processed: 0,
+                total,
+            },
+            pending,
+            mode,
+            before,
+            imported: 0,
+            skipped_items: 0,
+            skipped_groups: HashSet::new(),
+            ungrouped: 0,
+        })
+    }
+
+    /// Start importing a vault file; see `import`.
+    pub fn begin_import_vault(
+        &mut self,
+        path: &Path,
+        password: &str,
+        mode: ImportMode,
+    ) -> Result<ImportJob> {
         let imported = vault_file::import_vault(path, password)?;
+        self.begin_import(imported.groups, imported.items, mode)
+    }
+
+    /// Merge up to `batch` more items of `job` (its groups go with the first batch).
+    ///
+    /// Call it at least once, even for a job with no items, so the groups are merged.
+    pub fn import_step(&mut self, job: &mut ImportJob, batch: usize) -> Result<ImportProgress> {
+        let groups = std::mem::take(&mut job.groups);
+        let batch = batch.max(1);
+        match &mut job.pending {
+            PendingImport::Items(items) => {
+                let items: Vec<Item> = items.by_ref().take(batch).collect();
+                job.progress.processed += items.len();
+                let merged = self.merge_batch(groups, items, job.mode, &mut job.skipped_groups)?;
+                job.imported += merged.count;
+                job.skipped_items += merged.skipped_items;
+                job.ungrouped += merged.ungrouped;
+            }
+            PendingImport::Rows(rows) => {
+                for mut row in rows.by_ref().take(batch) {
+                    job.progress.processed += 1;
+                    if self.quota_room()?.0 == 0 {
+                        job.skipped_items += 1;
+                        continue;
+                    }
+                    self.sanitize_credentials(&mut row.username, &mut row.password);
+                    let group_id = match row.group.map(|name| self.group_id_by_name(name)) {
+                        Some(Ok(id)) => Some(id),
+                        Some(Err(VaulturaError::QuotaExceeded { .. })) => {
+                            job.ungrouped += 1;
+                            None
+                        }
+                        Some(Err(e)) => return Err(e),
+                        None => None,
+                    };
+                    self.create_item(ItemDraft {
+                        title: row.title,
+                        username: row.username,
+                        password: row.password,
+                        url: row.url,
+                        urls: Vec::new(),
+                        notes: row.notes,
+                        tags: row.tags,
+                        group_id,
+                        attachments: Vec::new(),
+                        otp_secret: None,
+                        notes_secret: false,
+                        kind: ItemKind::Login,
+                        launch_command: None,
+                        sensitive: false,
+                    })?;
+                    job.imported += 1;
+                }
+            }
+        }
+        Ok(job.progress)
+    }
+
+    /// Keep what `job` imported, noting anything the quotas left out in
+    /// `import_warnings`, and return how many groups and items were added. The vault
+    /// as it was before the job becomes the backup `undo_last_import` restores.
+    pub fn finish_import(&mut self, mut job: ImportJob) -> usize {
+        self.note_quota_skips(job.skipped_items, job.skipped_groups.len(), job.ungrouped);
+        if self.backup_before_import {
+            self.import_backup = Some(std::mem::take(&mut job.before));
+        }
+        job.imported
+    }
+
+    /// Abandon `job`, restoring the groups, items and unsaved-changes flag it started from.
+    pub fn cancel_import(&mut self, mut job: ImportJob) -> Result<()> {
+        self.restore(&mut job.before)
+    }
+
+    /// Add groups and items whose ids are not already present.
+    fn merge(&mut self, groups: Vec<Group>, items: Vec<Item>, mode: ImportMode) -> Result<usize> {
+        self.sanitized_fields = 0;
+        self.import_warnings.clear();
+        let mut skipped_groups = HashSet::new();
+        let merged = self.merge_batch(groups, items, mode, &mut skipped_groups)?;
+        self.note_quota_skips(merged.skipped_items, skipped_groups.len(), merged.ungrouped);
+        Ok(merged.count)
+    }
+
+    /// `merge` for one batch of an import, leaving the quota warnings to the caller.
+    ///
+    /// Groups left out for the quota are added to `skipped_groups`, and references to
+    /// any group in it are dropped from this and later batches.
+    fn merge_batch(
+        &mut self,
+        groups: Vec<Group>,
+        items: Vec<Item>,
+        mode: ImportMode,
+        skipped_groups: &mut HashSet<Uuid>,
+    ) -> Result<MergedBatch> {
+        let (mut item_room, mut group_room) = self.quota_room()?;
+        let (mut skipped_items, mut ungrouped) = (0, 0);
+        let skipped_before = skipped_groups.len();
         let payload = self.payload_mut()?;
-        let count = imported.items.len() + imported.groups.len();
+        let count = items.len() + groups.len();
 
-        for group in imported.groups {
+        let mut added = Vec::new();
+        for group in groups {
             if !payload.groups.iter().any(|g| g.id == group.id) {
-                payload.groups.push(group);
+                if group_room == 0 {
+                    skipped_groups.insert(group.id);
+                    continue;
+                }
+                group_room -= 1;
+                added.push(group);
             }
         }
-        for item in imported.items {
-            if !payload.items.iter().any(|i| i.id == item.id) {
+        for mut group in added {
+            group.parent_id = group.parent_id.filter(|id| !skipped_groups.contains(id));
+            payload.groups.push(group);
+        }
+        let mut ungroup = |item: &mut Item| {
+            if item.group_id.is_some_and(|id| skipped_groups.contains(&id)) {
+                item.group_id = None;
+                ungrouped += 1;
+            }
+        };
+        for mut item in items {
+            let exists = payload.items.iter().any(|i| i.id == item.id);
+            if (!exists || mode == ImportMode::ImportAsNew) && item_room == 0 {
+                skipped_items += 1;
+                continue;
+            }
+            let Some(existing) = payload.items.iter_mut().find(|i| i.id == item.id) else {
+                item_room -= 1;
+                ungroup(&mut item);
                 payload.items.push(item);
+                continue;
+            };
+            match mode {
+                ImportMode::SkipExisting => {}
+                ImportMode::ImportAsNew => {
+                    item_room -= 1;
+                    item.id = Uuid::new_v4();
+                    item.title.push_str(" (imported)");
+                    ungroup(&mut item);
+                    payload.items.push(item);
+                }
+                ImportMode::PreferNewer => {
+                    if item.modified_at > existing.modified_at {
+                        ungroup(&mut item);
+                        *existing = item;
+                    }
+                }
             }
         }
 
+        self.dirty = true;
+        self.seal_items()?;
+        Ok(MergedBatch {
+            count: count - skipped_items - (skipped_groups.len() - skipped_before),
+            skipped_items,
+            ungrouped,
+        })
+    }
+
+    /// Record in `import_warnings` what an import left out to stay within the quotas,
+    /// and how many items it left without their (skipped) group.
+    fn note_quota_skips(&mut self, items: usize, groups: usize, ungrouped: usize) {
+        let max_items = self.max_items.unwrap_or_default();
+        let max_groups = self.max_groups.unwrap_or_default();
+        if items > 0 {
+            self.import_warnings.push(format!(
+                "Skipped {}: the vault is limited to {}",
+                count_of(items, "item"),
+                count_of(max_items, "item")
+            ));
+        }
+        if groups > 0 {
+            self.import_warnings.push(format!(
+                "Skipped {}: the vault is limited to {}",
+                count_of(groups, "group"),
+                count_of(max_groups, "group")
+            ));
+        }
+        if ungrouped > 0 {
+            self.import_warnings.push(format!(
+                "Left {} ungrouped: the vault is limited to {}",
+                count_of(ungrouped, "item"),
+                count_of(max_groups, "group")
+            ));
+        }
+    }
+
+    fn group_id_by_name(&mut self, name: String) -> Result<Uuid> {
+        match self.payload()?.groups.iter().find(|g| g.name == name) {
+            Some(group) => Ok(group.id),
+            None => self.create_group(name, None),
+        }
+    }
+
+    /// Import groups and entries from a KeePass KDBX file (password-only key).
+    #[cfg(feature = "kdbx")]
+    pub fn import_kdbx(&mut self, path: &Path, password: &str) -> Result<usize> {
+        let mut imported = crate::storage::kdbx::read_kdbx(path, password)?;
+        self.backup_for_undo()?;
+        self.import_warnings.clear();
+        let (item_room, group_room) = self.quota_room()?;
+        let skipped_items = imported.items.len().saturating_sub(item_room);
+        let skipped_groups = imported.groups.len().saturating_sub(group_room);
+        imported.items.truncate(item_room);
+        imported.groups.truncate(group_room);
+        let count = imported.items.len() + imported.groups.len();
+        let kept: HashSet<Uuid> = imported.groups.iter().map(|g| g.id).collect();
+        for group in &mut imported.groups {
+            group.parent_id = group.parent_id.filter(|id| kept.contains(id));
+        }
+        self.payload_mut()?.groups.extend(imported.groups);
+        self.sanitized_fields = 0;
+        let mut ungrouped = 0;
+        for mut draft in imported.items {
+            self.sanitize_credentials(&mut draft.username, &mut draft.password);
+            if draft.group_id.is_some_and(|id| !kept.contains(&id)) {
+                draft.group_id = None;
+                ungrouped += 1;
+            }
+            self.create_item(draft)?;
+        }
+        self.note_quota_skips(skipped_items, skipped_groups, ungrouped);
         self.dirty = true;
         Ok(count)
     }
 }
 
+/// `count` followed by `noun`, with an "s" unless there is exactly one.
+fn count_of(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+/// Decrypt a sealed password back into `item.password`.
+fn unseal(key: &SessionKey, item: &mut Item) -> Result<()> {
+    if let Some(sealed) = item.sealed_password.take() {
+        item.password = key.open(&sealed)?.to_string();
+    }
+    for entry in &mut item.password_history {
+        if let Some(sealed) = entry.sealed.take() {
+            entry.password = key.open(&sealed)?.to_string();
+        }
+    }
+    Ok(())
+}
+
+/// Zeroize every secret held in `payload`.
+fn wipe_secrets(payload: &mut VaultPayload) {
+    payload.items.iter_mut().for_each(wipe_item);
+}
+
+/// Zeroize the secrets of one item.
+fn wipe_item(item: &mut Item) {
+    item.password.zeroize();
+    item.notes.zeroize();
+    if let Some(ref mut secret) = item.otp_secret {
+        secret.zeroize();
+    }
+    if let ItemKind::SshKey {
+        ref mut private_key,
+        ..
+    } = item.kind
+    {
+        private_key.zeroize();
+    }
+    for entry in &mut item.password_history {
+        entry.password.zeroize();
+    }
+    for file in &mut item.embedded {
+        file.bytes.zeroize();
+    }
+}
+
+/// Sort key putting first the item `merge_items` keeps: the most recently modified.
+fn merge_rank(item: &Item) -> (std::cmp::Reverse<DateTime<Utc>>, Uuid) {
+    (std::cmp::Reverse(item.modified_at), item.id)
+}
+
+/// `tags` with `from` renamed to `to`, merged into `to` where both are present.
+/// Returned unchanged when `from` is absent.
+fn renamed_tags(tags: &[String], from: &str, to: &str) -> Vec<String> {
+    if !tags.iter().any(|t| t.eq_ignore_ascii_case(from)) {
+        return tags.to_vec();
+    }
+    let mut renamed: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        if tag.eq_ignore_ascii_case(from) || tag.eq_ignore_ascii_case(to) {
+            if !renamed.iter().any(|t| t.eq_ignore_ascii_case(to)) {
+                renamed.push(to.to_string());
+            }
+        } else {
+            renamed.push(tag.clone());
+        }
+    }
+    renamed
+}
+
+/// Move items with TOTP ahead of the rest, keeping the existing order within each part.
+pub fn sort_totp_first(items: &mut [&Item]) {
+    items.sort_by_key(|item| !item.has_totp());
+}
+
+fn file_len(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -318,6 +1936,293 @@ mod tests {
         (dir, svc)
     }
 
+    #[test]
+    fn test_auto_lock_override_round_trips() {
+        let (_dir, mut svc) = setup();
+        assert_eq!(svc.meta().unwrap().auto_lock_secs_override, None);
+
+        svc.set_auto_lock_override(Some(60)).unwrap();
+        svc.save().unwrap();
+        svc.lock();
+        assert!(svc.meta().is_err());
+
+        svc.unlock("password").unwrap();
+        assert_eq!(svc.meta().unwrap().auto_lock_secs_override, Some(60));
+    }
+
+    #[test]
+    fn test_is_empty_until_something_is_added() {
+        let (_dir, mut svc) = setup();
+        assert!(svc.is_empty());
+        let id = svc.create_group("Work".to_string(), None).unwrap();
+        assert!(!svc.is_empty());
+        svc.delete_group(id).unwrap();
+        assert!(svc.is_empty());
+        svc.lock();
+        assert!(!svc.is_empty());
+    }
+
+    #[test]
+    fn test_move_item_swaps_within_its_group() {
+        let (_dir, mut svc) = setup();
+        let work = svc.create_group("Work".to_string(), None).unwrap();
+        let in_work = |svc: &mut VaultService, title: &str| {
+            svc.create_item(ItemDraft {
+                title: title.to_string(),
+                group_id: Some(work),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let mail = in_work(&mut svc, "Mail");
+        login(&mut svc, "Bank", "me", "pw");
+        let vpn = in_work(&mut svc, "VPN");
+        let titles = |svc: &VaultService| -> Vec<String> {
+            svc.items_in_group(Some(work))
+                .unwrap()
+                .iter()
+                .map(|i| i.title.clone())
+                .collect()
+        };
+        svc.save().unwrap();
+
+        // Skips over the item in another group.
+        assert!(svc.move_item_up(vpn).unwrap());
+        assert!(svc.is_dirty());
+        assert_eq!(titles(&svc), ["VPN", "Mail"]);
+        assert_eq!(svc.items().unwrap()[1].title, "Bank");
+
+        assert!(!svc.move_item_up(vpn).unwrap());
+        assert!(!svc.move_item_down(mail).unwrap());
+        assert!(svc.move_item_down(vpn).unwrap());
+        assert_eq!(titles(&svc), ["Mail", "VPN"]);
+        assert!(matches!(
+            svc.move_item_up(Uuid::new_v4()),
+            Err(VaulturaError::ItemNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_group_description_round_trips() {
+        let (_dir, mut svc) = setup();
+        let id = svc.create_group("Work".to_string(), None).unwrap();
+        assert_eq!(svc.groups().unwrap()[0].description, "");
+
+        svc.set_group_description(id, "Office accounts".to_string())
+            .unwrap();
+        assert!(svc.is_dirty());
+        svc.save().unwrap();
+        svc.lock();
+
+        svc.unlock("password").unwrap();
+        assert_eq!(svc.groups().unwrap()[0].description, "Office accounts");
+    }
+
+    #[test]
+    fn test_password_history_follows_vault_setting() {
+        let (_dir, mut svc) = setup();
+        assert!(svc.meta().unwrap().record_password_history);
+        let id = login(&mut svc, "Mail", "me", "first");
+        let change = |svc: &mut VaultService, password: &str| {
+            let draft = ItemDraft {
+                title: "Mail".to_string(),
+                username: "me".to_string(),
+                password: password.to_string(),
+                ..Default::default()
+            };
+            svc.update_item(id, draft).unwrap();
+        };
+
+        change(&mut svc, "second");
+        assert_eq!(svc.get_item(id).unwrap().password_history.len(), 1);
+
+        svc.set_record_password_history(false).unwrap();
+        svc.save().unwrap();
+        svc.lock();
+        svc.unlock("password").unwrap();
+        assert!(!svc.meta().unwrap().record_password_history);
+        change(&mut svc, "third");
+        let item = svc.get_item(id).unwrap();
+        assert_eq!(item.password, "third");
+        assert_eq!(item.password_history.len(), 1);
+        assert_eq!(item.password_history[0].password, "first");
+    }
+
+    #[test]
+    fn test_diff_since_save_reports_added_modified_removed() {
+        use crate::core::changes::{ChangeKind, ChangeTarget};
+
+        let (_dir, mut svc) = setup();
+        assert!(svc.diff_since_save().is_empty());
+
+        let id = svc
+            .create_item(ItemDraft {
+                title: "GitHub".to_string(),
+                password: "secret".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        let changes = svc.diff_since_save();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, ChangeKind::Added);
+        assert_eq!(changes[0].target, ChangeTarget::Item);
+        assert_eq!(changes[0].describe(), "+ item 'GitHub'");
+
+        svc.save().unwrap();
+        assert!(svc.diff_since_save().is_empty());
+
+        svc.update_item(
+            id,
+            ItemDraft {
+                title: "GitHub".to_string(),
+                password: "rotated".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let changes = svc.diff_since_save();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, ChangeKind::Modified);
+        assert!(!changes[0].describe().contains("rotated"));
+
+        svc.save().unwrap();
+        svc.delete_item(id).unwrap();
+        let changes = svc.diff_since_save();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, ChangeKind::Removed);
+        assert_eq!(changes[0].name, "GitHub");
+    }
+
+    #[test]
+    fn test_lock_drops_all_secrets() {
+        let (_dir, mut svc) = setup();
+        svc.create_item(ItemDraft {
+            title: "Login".to_string(),
+            password: "hunter2".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(svc.holds_secrets());
+
+        svc.lock();
+        assert!(!svc.is_unlocked());
+        assert!(!svc.holds_secrets());
+        assert!(matches!(svc.items(), Err(VaulturaError::VaultLocked)));
+    }
+
+    #[test]
+    fn test_wipe_item_clears_embedded_files() {
+        let mut item = Item::new("Bank".to_string(), None);
+        item.password = "hunter2".to_string();
+        item.embedded.push(EmbeddedFile {
+            name: "codes.txt".to_string(),
+            bytes: b"1234-5678".to_vec(),
+        });
+        wipe_item(&mut item);
+        assert!(item.password.is_empty());
+        assert!(item.embedded[0].bytes.is_empty());
+    }
+
+    #[test]
+    fn test_sealed_passwords_are_not_stored_in_plaintext() {
+        let (_dir, mut svc) = setup();
+        svc.set_seal_passwords(true).unwrap();
+        let id = svc
+            .create_item(ItemDraft {
+                title: "Login".to_string(),
+                password: "hunter2".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let stored = svc.get_item(id).unwrap();
+        assert!(stored.password.is_empty());
+        let sealed = stored.sealed_password.as_ref().unwrap();
+        assert!(!sealed
+            .ciphertext()
+            .windows("hunter2".len())
+            .any(|w| w == b"hunter2"));
+
+        assert_eq!(svc.with_password(id, str::to_string).unwrap(), "hunter2");
+        assert_eq!(svc.unsealed_item(id).unwrap().password, "hunter2");
+
+        svc.update_item(
+            id,
+            ItemDraft {
+                title: "Login".to_string(),
+                password: "correct horse".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let entry = &svc.get_item(id).unwrap().password_history[0];
+        assert!(entry.password.is_empty());
+        assert!(!entry
+            .sealed
+            .as_ref()
+            .unwrap()
+            .ciphertext()
+            .windows("hunter2".len())
+            .any(|w| w == b"hunter2"));
+        assert_eq!(
+            svc.unsealed_item(id).unwrap().password_history[0].password,
+            "hunter2"
+        );
+    }
+
+    #[test]
+    fn test_sealed_passwords_survive_save_update_and_unseal() {
+        let (_dir, mut svc) = setup();
+        svc.set_seal_passwords(true).unwrap();
+        let id = svc
+            .create_item(ItemDraft {
+                title: "Login".to_string(),
+                password: "old".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        svc.update_item(
+            id,
+            ItemDraft {
+                title: "Login".to_string(),
+                password: "new".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            svc.unsealed_item(id).unwrap().password_history[0].password,
+            "old"
+        );
+
+        svc.save().unwrap();
+        svc.lock();
+        svc.unlock("password").unwrap();
+        assert_eq!(svc.with_password(id, str::to_string).unwrap(), "new");
+
+        svc.set_seal_passwords(false).unwrap();
+        let item = svc.get_item(id).unwrap();
+        assert_eq!(item.password, "new");
+        assert_eq!(item.password_history[0].password, "old");
+    }
+
+    #[test]
+    fn test_verified_save_round_trips() {
+        let (_dir, mut svc) = setup();
+        svc.set_verify_after_write(true);
+        svc.create_item(ItemDraft {
+            title: "Login".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        svc.save().unwrap();
+        assert!(!vault_file::backup_path(svc.vault_path()).exists());
+
+        svc.lock();
+        svc.unlock("password").unwrap();
+        assert_eq!(svc.items().unwrap().len(), 1);
+    }
+
     #[test]
     fn test_create_and_unlock() {
         let dir = TempDir::new().unwrap();
@@ -348,6 +2253,19 @@ mod tests {
         assert!(matches!(result, Err(VaulturaError::WrongPassword)));
     }
 
+    #[test]
+    fn test_verify_password() {
+        let (_dir, mut svc) = setup();
+        assert!(svc.verify_password("password").unwrap());
+        assert!(!svc.verify_password("wrong").unwrap());
+
+        svc.lock();
+        assert!(matches!(
+            svc.verify_password("password"),
+            Err(VaulturaError::VaultLocked)
+        ));
+    }
+
     #[test]
     fn test_crud_groups() {
         let (_dir, mut svc) = setup();
@@ -363,6 +2281,93 @@ mod tests {
         assert!(svc.groups().unwrap().is_empty());
     }
 
+    fn login(svc: &mut VaultService, title: &str, username: &str, password: &str) -> Uuid {
+        svc.create_item(ItemDraft {
+            title: title.to_string(),
+            username: username.to_string(),
+            password: password.to_string(),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_find_duplicates_groups_by_title_and_username() {
+        let (_dir, mut svc) = setup();
+        let a = login(&mut svc, "Mail", "me", "one");
+        login(&mut svc, "Mail", "you", "one");
+        let b = login(&mut svc, "Mail", "me", "two");
+        let c = login(&mut svc, "Mail", "me", "one");
+
+        assert_eq!(svc.find_duplicates(false).unwrap(), vec![vec![a, b, c]]);
+        assert_eq!(svc.find_duplicates(true).unwrap(), vec![vec![a, c]]);
+    }
+
+    #[test]
+    fn test_merge_items_unions_and_keeps_newest_password() {
+        let (_dir, mut svc) = setup();
+        svc.set_seal_passwords(true).unwrap();
+        let old = svc
+            .create_item(ItemDraft {
+                title: "Mail".to_string(),
+                username: "me".to_string(),
+                password: "old".to_string(),
+                url: "mail.example.com".to_string(),
+                tags: vec!["email".to_string(), "Work".to_string()],
+                notes: "recovery codes in drawer".to_string(),
+                notes_secret: true,
+                ..Default::default()
+            })
+            .unwrap();
+        let newer = svc
+            .create_item(ItemDraft {
+                title: "Mail".to_string(),
+                username: "me".to_string(),
+                password: "new".to_string(),
+                url: "webmail.example.com".to_string(),
+                tags: vec!["work".to_string(), "imported".to_string()],
+                notes: "IMAP needs an app password".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        let created = svc.get_item(old).unwrap().created_at;
+        svc.item_mut(newer).unwrap().modified_at += chrono::Duration::seconds(1);
+
+        let kept = svc.merge_items(&[old, newer]).unwrap();
+        assert_eq!(kept, newer);
+        assert!(svc.get_item(old).is_err());
+        assert_eq!(svc.items().unwrap().len(), 1);
+
+        let item = svc.unsealed_item(kept).unwrap();
+        assert_eq!(item.password, "new");
+        assert_eq!(item.password_history.len(), 1);
+        assert_eq!(item.password_history[0].password, "old");
+        assert_eq!(item.tags, ["work", "imported", "email"]);
+        assert_eq!(item.all_urls(), ["webmail.example.com", "mail.example.com"]);
+        assert_eq!(
+            item.notes,
+            "IMAP needs an app password\n\nrecovery codes in drawer"
+        );
+        assert!(item.notes_secret);
+        assert_eq!(item.created_at, created);
+
+        assert!(svc.merge_items(&[kept, kept]).is_err());
+
+        // With history recording off, the deleted item's password is still kept.
+        svc.set_record_password_history(false).unwrap();
+        let other = svc
+            .create_item(ItemDraft {
+                title: "Mail".to_string(),
+                password: "other".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        svc.item_mut(kept).unwrap().modified_at += chrono::Duration::seconds(5);
+        assert_eq!(svc.merge_items(&[kept, other]).unwrap(), kept);
+        let item = svc.unsealed_item(kept).unwrap();
+        assert!(item.password_history.iter().any(|e| e.password == "other"));
+    }
+
     #[test]
     fn test_crud_items() {
         let (_dir, mut svc) = setup();
@@ -372,9 +2377,16 @@ mod tests {
             username: "user@example.com".to_string(),
             password: "secret".to_string(),
             url: "https://github.com".to_string(),
+            urls: Vec::new(),
             notes: "My GitHub account".to_string(),
             tags: vec!["dev".to_string()],
             group_id: None,
+            attachments: Vec::new(),
+            otp_secret: None,
+            notes_secret: false,
+            kind: ItemKind::Login,
+            launch_command: None,
+            sensitive: false,
         };
 
         let item_id = svc.create_item(draft).unwrap();
@@ -389,14 +2401,22 @@ mod tests {
             username: "new@example.com".to_string(),
             password: "new_secret".to_string(),
             url: "https://github.com".to_string(),
+            urls: Vec::new(),
             notes: "Updated notes".to_string(),
             tags: vec!["dev".to_string(), "vcs".to_string()],
             group_id: None,
+            attachments: Vec::new(),
+            otp_secret: None,
+            notes_secret: true,
+            kind: ItemKind::Login,
+            launch_command: None,
+            sensitive: false,
         };
         svc.update_item(item_id, update).unwrap();
 
         let item = svc.get_item(item_id).unwrap();
         assert_eq!(item.title, "GitHub Updated");
+        assert!(item.notes_secret);
         assert_eq!(item.password_history.len(), 1);
         assert_eq!(item.password_history[0].password, "secret");
 
@@ -461,12 +2481,88 @@ mod tests {
         })
         .unwrap();
 
-        assert_eq!(svc.search("git").unwrap().len(), 1);
-        assert_eq!(svc.search("user").unwrap().len(), 2);
-        assert_eq!(svc.search("dev").unwrap().len(), 1);
-        assert_eq!(svc.search("GitHub user").unwrap().len(), 1);
-        assert_eq!(svc.search("nonexistent").unwrap().len(), 0);
-        assert_eq!(svc.search("").unwrap().len(), 2);
+        assert_eq!(svc.search("git").unwrap().len(), 1);
+        assert_eq!(svc.search("user").unwrap().len(), 2);
+        assert_eq!(svc.search("dev").unwrap().len(), 1);
+        assert_eq!(svc.search("GitHub user").unwrap().len(), 1);
+        assert_eq!(svc.search("nonexistent").unwrap().len(), 0);
+        assert_eq!(svc.search("").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_items_with_tag_and_filter() {
+        let (_dir, mut svc) = setup();
+        let group = svc.create_group("Work".to_string(), None).unwrap();
+
+        svc.create_item(ItemDraft {
+            title: "Jira".to_string(),
+            tags: vec!["Work".to_string()],
+            group_id: Some(group),
+            ..Default::default()
+        })
+        .unwrap();
+        svc.create_item(ItemDraft {
+            title: "Jenkins".to_string(),
+            tags: vec!["work".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+        svc.create_item(ItemDraft {
+            title: "Netflix".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(svc.items_with_tag("WORK").unwrap().len(), 2);
+        assert_eq!(svc.all_tags().unwrap(), ["Work"]);
+        assert_eq!(svc.filter_items("", Some("work"), None).unwrap().len(), 2);
+        assert_eq!(
+            svc.filter_items("jen", Some("work"), None).unwrap().len(),
+            1
+        );
+        assert_eq!(
+            svc.filter_items("", Some("work"), Some(group))
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(svc.filter_items("", None, None).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_totp_view_and_sort() {
+        let (_dir, mut svc) = setup();
+        for (title, otp) in [
+            ("Mail", None),
+            ("GitHub", Some("JBSWY3DP")),
+            ("Bank", Some(" ")),
+        ] {
+            svc.create_item(ItemDraft {
+                title: title.to_string(),
+                otp_secret: otp.map(str::to_string),
+                ..Default::default()
+            })
+            .unwrap();
+        }
+        svc.create_item(ItemDraft {
+            title: "AWS".to_string(),
+            otp_secret: Some("GEZDGNBV".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let with_totp: Vec<&str> = svc
+            .items_with_totp()
+            .unwrap()
+            .iter()
+            .map(|i| i.title.as_str())
+            .collect();
+        assert_eq!(with_totp, ["GitHub", "AWS"]);
+
+        let mut items = svc.filter_items("", None, None).unwrap();
+        sort_totp_first(&mut items);
+        let order: Vec<&str> = items.iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(order, ["GitHub", "AWS", "Mail", "Bank"]);
     }
 
     #[test]
@@ -519,6 +2615,409 @@ mod tests {
         assert_eq!(svc.items().unwrap()[0].title, "Persistent");
     }
 
+    #[test]
+    fn test_reload_discards_unsaved_edits() {
+        let (_dir, mut svc) = setup();
+        let kept = svc
+            .create_item(ItemDraft {
+                title: "Saved".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        svc.save().unwrap();
+
+        svc.delete_item(kept).unwrap();
+        svc.create_group("Unsaved".to_string(), None).unwrap();
+        assert!(svc.is_dirty());
+
+        svc.reload().unwrap();
+        assert!(!svc.is_dirty());
+        assert_eq!(svc.items().unwrap().len(), 1);
+        assert_eq!(svc.items().unwrap()[0].title, "Saved");
+        assert!(svc.groups().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_reload_unwritten_vault_clears_payload() {
+        let (dir, mut svc) = setup();
+        svc.create_group("Work".to_string(), None).unwrap();
+        std::fs::remove_file(dir.path().join("test.vault")).unwrap();
+
+        svc.reload().unwrap();
+        assert!(svc.groups().unwrap().is_empty());
+
+        svc.lock();
+        assert!(matches!(svc.reload(), Err(VaulturaError::VaultLocked)));
+    }
+
+    #[test]
+    fn test_compact_clears_history_and_orphans() {
+        let (_dir, mut svc) = setup();
+        let id = svc
+            .create_item(ItemDraft {
+                title: "Site".to_string(),
+                password: "one".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        for pw in ["two", "three"] {
+            let draft = ItemDraft {
+                title: "Site".to_string(),
+                password: pw.to_string(),
+                ..Default::default()
+            };
+            svc.update_item(id, draft).unwrap();
+        }
+        let ghost = Uuid::new_v4();
+        let payload = svc.payload_mut().unwrap();
+        payload.items[0].group_id = Some(ghost);
+        payload
+            .groups
+            .push(Group::new("Child".to_string(), Some(ghost)));
+        svc.save().unwrap();
+
+        let report = svc
+            .compact(CompactOptions {
+                clear_history: false,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(report.history_entries_removed, 0);
+        assert_eq!(report.orphaned_references_removed, 2);
+        assert_eq!(svc.get_item(id).unwrap().password_history.len(), 2);
+        assert_eq!(svc.get_item(id).unwrap().group_id, None);
+
+        let report = svc
+            .compact(CompactOptions {
+                clear_history: true,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(report.history_entries_removed, 2);
+        assert_eq!(report.orphaned_references_removed, 0);
+        assert!(report.bytes_removed() > 0);
+        assert_eq!(
+            report.bytes_removed(),
+            report.bytes_before - report.bytes_after
+        );
+        assert!(svc.get_item(id).unwrap().password_history.is_empty());
+        assert!(!svc.is_dirty());
+    }
+
+    #[test]
+    fn test_dedupe_dry_run_matches_real_run() {
+        let (_dir, mut svc) = setup();
+        let a = login(&mut svc, "Mail", "me", "one");
+        let b = login(&mut svc, "Mail", "me", "two");
+        let c = login(&mut svc, "Bank", "me", "three");
+        let d = login(&mut svc, "Bank", "me", "four");
+        login(&mut svc, "Shop", "me", "five");
+        for id in [a, c] {
+            svc.item_mut(id).unwrap().modified_at -= chrono::Duration::hours(1);
+        }
+        let before = svc.payload().unwrap().clone();
+
+        let preview = svc.dedupe(false, true).unwrap();
+        assert!(preview.dry_run);
+        assert_eq!(preview.kept_items, [b, d]);
+        assert_eq!(preview.removed_items, [a, c]);
+        assert_eq!(svc.payload().unwrap(), &before);
+
+        let applied = svc.dedupe(false, false).unwrap();
+        assert_eq!(applied.kept_items, preview.kept_items);
+        assert_eq!(applied.removed_items, preview.removed_items);
+        assert_eq!(svc.items().unwrap().len(), 3);
+        assert!(svc.get_item(a).is_err());
+        assert!(svc.get_item(c).is_err());
+        assert!(svc.find_duplicates(false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rename_tag_dry_run_matches_real_run() {
+        let (_dir, mut svc) = setup();
+        let tagged = |svc: &mut VaultService, title: &str, tags: &[&str]| {
+            svc.create_item(ItemDraft {
+                title: title.to_string(),
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let a = tagged(&mut svc, "A", &["work", "mail"]);
+        let b = tagged(&mut svc, "B", &["Work", "office"]);
+        tagged(&mut svc, "C", &["office"]);
+        svc.save().unwrap();
+        let before = svc.payload().unwrap().clone();
+
+        let preview = svc.rename_tag("WORK", "office", true).unwrap();
+        assert_eq!(preview.changes, 2);
+        assert_eq!(preview.affected_items, [a, b]);
+        assert_eq!(svc.payload().unwrap(), &before);
+        assert!(!svc.is_dirty());
+
+        let applied = svc.rename_tag("WORK", "office", false).unwrap();
+        assert_eq!(applied.changes, preview.changes);
+        assert_eq!(applied.affected_items, preview.affected_items);
+        assert_eq!(svc.get_item(a).unwrap().tags, ["office", "mail"]);
+        assert_eq!(svc.get_item(b).unwrap().tags, ["office"]);
+        assert!(svc.is_dirty());
+        assert_eq!(svc.rename_tag("work", "office", true).unwrap().changes, 0);
+        assert!(svc.rename_tag("office", " ", false).is_err());
+    }
+
+    #[test]
+    fn test_replace_in_field_dry_run_matches_real_run() {
+        let (_dir, mut svc) = setup();
+        let site = |svc: &mut VaultService, title: &str, url: &str| {
+            svc.create_item(ItemDraft {
+                title: title.to_string(),
+                url: url.to_string(),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let a = site(&mut svc, "A", "http://old.example/http://old.example");
+        site(&mut svc, "B", "https://new.example");
+        let c = site(&mut svc, "http://old.example", "http://old.example/login");
+        svc.save().unwrap();
+        let before = svc.payload().unwrap().clone();
+
+        let preview = svc
+            .replace_in_field(TextField::Url, "http://old", "https://new", true)
+            .unwrap();
+        assert_eq!(preview.changes, 3);
+        assert_eq!(preview.affected_items, [a, c]);
+        assert_eq!(svc.payload().unwrap(), &before);
+
+        let applied = svc
+            .replace_in_field(TextField::Url, "http://old", "https://new", false)
+            .unwrap();
+        assert_eq!(applied.changes, preview.changes);
+        assert_eq!(applied.affected_items, preview.affected_items);
+        assert_eq!(
+            svc.get_item(a).unwrap().url,
+            "https://new.example/https://new.example"
+        );
+        assert_eq!(svc.get_item(c).unwrap().title, "http://old.example");
+        assert!(svc
+            .replace_in_field(TextField::Notes, "", "x", false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_compact_refuses_unsaved_changes_and_keeps_payload_on_failed_save() {
+        let (dir, mut svc) = setup();
+        let id = login(&mut svc, "Site", "me", "one");
+        svc.update_item(
+            id,
+            ItemDraft {
+                title: "Site".to_string(),
+                password: "two".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let options = CompactOptions {
+            clear_history: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            svc.compact(options),
+            Err(VaulturaError::UnsavedChanges)
+        ));
+        assert_eq!(svc.get_item(id).unwrap().password_history.len(), 1);
+        assert!(svc.is_dirty());
+
+        svc.save().unwrap();
+        let before = svc.payload().unwrap().clone();
+        // A directory where the vault file goes makes the write fail.
+        let path = dir.path().join("test.vault");
+        std::fs::remove_file(&path).unwrap();
+        std::fs::create_dir(&path).unwrap();
+        assert!(svc.compact(options).is_err());
+        assert_eq!(svc.payload().unwrap(), &before);
+        assert!(!svc.is_dirty());
+    }
+
+    #[test]
+    fn test_compact_dry_run_matches_real_run() {
+        let (_dir, mut svc) = setup();
+        let id = svc
+            .create_item(ItemDraft {
+                title: "Site".to_string(),
+                password: "one".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        svc.update_item(
+            id,
+            ItemDraft {
+                title: "Site".to_string(),
+                password: "two".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let ghost = Uuid::new_v4();
+        let payload = svc.payload_mut().unwrap();
+        payload.items[0].group_id = Some(ghost);
+        payload
+            .groups
+            .push(Group::new("Child".to_string(), Some(ghost)));
+        let child = payload.groups[0].id;
+        svc.save().unwrap();
+        let before = svc.payload().unwrap().clone();
+        let bytes = std::fs::read(svc.vault_path()).unwrap();
+
+        let options = CompactOptions {
+            clear_history: true,
+            dry_run: true,
+        };
+        let preview = svc.compact(options).unwrap();
+        assert!(preview.dry_run);
+        assert_eq!(preview.history_entries_removed, 1);
+        assert_eq!(preview.orphaned_references_removed, 2);
+        assert_eq!(preview.affected_items, [id]);
+        assert_eq!(preview.affected_groups, [child]);
+        assert_eq!(preview.bytes_removed(), 0);
+        assert_eq!(svc.payload().unwrap(), &before);
+        assert_eq!(std::fs::read(svc.vault_path()).unwrap(), bytes);
+
+        let applied = svc
+            .compact(CompactOptions {
+                dry_run: false,
+                ..options
+            })
+            .unwrap();
+        assert_eq!(
+            applied.history_entries_removed,
+            preview.history_entries_removed
+        );
+        assert_eq!(
+            applied.orphaned_references_removed,
+            preview.orphaned_references_removed
+        );
+        assert_eq!(applied.affected_items, preview.affected_items);
+        assert_eq!(applied.affected_groups, preview.affected_groups);
+        assert!(svc.get_item(id).unwrap().password_history.is_empty());
+    }
+
+    #[test]
+    fn test_embedded_file_survives_save_and_unlock() {
+        let (dir, mut svc) = setup();
+        let source = dir.path().join("codes.txt");
+        std::fs::write(&source, b"1234-5678\n").unwrap();
+        let id = svc
+            .create_item(ItemDraft {
+                title: "Bank".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        svc.embed_file(id, &source, 1024).unwrap();
+        svc.save().unwrap();
+        svc.lock();
+        svc.unlock("password").unwrap();
+
+        let item = svc.get_item(id).unwrap();
+        assert_eq!(item.embedded.len(), 1);
+        assert_eq!(item.embedded[0].name, "codes.txt");
+        assert_eq!(svc.embedded_total_bytes().unwrap(), 10);
+
+        let out = dir.path().join("out");
+        std::fs::create_dir(&out).unwrap();
+        let dest = svc.export_embedded(id, 0, &out).unwrap();
+        assert_eq!(dest, out.join("codes.txt"));
+        assert_eq!(std::fs::read(&dest).unwrap(), b"1234-5678\n");
+        // Existing files are never overwritten.
+        assert!(svc.export_embedded(id, 0, &out).is_err());
+    }
+
+    #[test]
+    fn test_export_embedded_refuses_names_that_leave_the_directory() {
+        let (dir, mut svc) = setup();
+        let id = login(&mut svc, "Server", "", "");
+        let out = dir.path().join("out");
+        std::fs::create_dir(&out).unwrap();
+        let names = [
+            "../escaped.txt",
+            "/tmp/absolute.txt",
+            "a/b.txt",
+            "..",
+            ".",
+            "",
+        ];
+        for name in names {
+            svc.item_mut(id).unwrap().embedded = vec![EmbeddedFile {
+                name: name.to_string(),
+                bytes: b"key".to_vec(),
+            }];
+            assert!(
+                matches!(
+                    svc.export_embedded(id, 0, &out),
+                    Err(VaulturaError::Export(_))
+                ),
+                "{name:?}"
+            );
+        }
+        assert!(!dir.path().join("escaped.txt").exists());
+        assert_eq!(std::fs::read_dir(&out).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_embed_rejects_oversized_file() {
+        let (dir, mut svc) = setup();
+        let source = dir.path().join("big.bin");
+        std::fs::write(&source, vec![0u8; 2048]).unwrap();
+        let id = svc
+            .create_item(ItemDraft {
+                title: "Big".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let err = svc.embed_file(id, &source, 1024).unwrap_err();
+        assert!(matches!(
+            err,
+            VaulturaError::FileTooLarge {
+                size: 2048,
+                limit: 1024,
+                ..
+            }
+        ));
+        assert!(svc.get_item(id).unwrap().embedded.is_empty());
+
+        // A device reports no size but never ends; reading stops past the limit.
+        #[cfg(unix)]
+        assert!(matches!(
+            svc.embed_file(id, Path::new("/dev/zero"), 1024),
+            Err(VaulturaError::FileTooLarge {
+                size: 1025,
+                limit: 1024,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_export_inventory_has_titles_but_no_passwords() {
+        let (dir, mut svc) = setup();
+        svc.create_item(ItemDraft {
+            title: "Mail".to_string(),
+            username: "me@example.com".to_string(),
+            password: "correct-horse".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let path = dir.path().join("inventory.json");
+
+        svc.export_inventory(&path, true).unwrap();
+        let json = std::fs::read_to_string(&path).unwrap();
+        assert!(json.contains("Mail"));
+        assert!(json.contains("me@example.com"));
+        assert!(!json.contains("correct-horse"));
+    }
+
     #[test]
     fn test_export_import() {
         let dir = TempDir::new().unwrap();
@@ -539,12 +3038,219 @@ mod tests {
 
         let mut svc2 = VaultService::new(path2, test_params());
         svc2.create("pass2").unwrap();
-        let count = svc2.import(&export_path, "export_pass").unwrap();
+        let count = svc2
+            .import(&export_path, "export_pass", ImportMode::default())
+            .unwrap();
         assert_eq!(count, 2); // 1 group + 1 item
         assert_eq!(svc2.items().unwrap().len(), 1);
         assert_eq!(svc2.groups().unwrap().len(), 1);
     }
 
+    /// An export holding an edited copy of `shared` and a new `extra` item.
+    fn colliding_export(dir: &TempDir, svc: &mut VaultService) -> (PathBuf, Uuid) {
+        let shared = svc
+            .create_item(ItemDraft {
+                title: "Shared".to_string(),
+                password: "old".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        svc.save().unwrap();
+
+        let export_path = dir.path().join("export.vault");
+        let mut other = VaultService::new(dir.path().join("other.vault"), test_params());
+        other.create("pass").unwrap();
+        let mut edited = svc.get_item(shared).unwrap().clone();
+        edited.password = "new".to_string();
+        edited.modified_at += chrono::Duration::seconds(60);
+        let extra = Item::new("Extra".to_string(), None);
+        other
+            .merge(Vec::new(), vec![edited, extra], ImportMode::SkipExisting)
+            .unwrap();
+        other.export(&export_path, "export_pass").unwrap();
+        (export_path, shared)
+    }
+
+    #[test]
+    fn test_import_skip_existing_keeps_local_copy() {
+        let (dir, mut svc) = setup();
+        let (export_path, shared) = colliding_export(&dir, &mut svc);
+
+        svc.import(&export_path, "export_pass", ImportMode::SkipExisting)
+            .unwrap();
+        assert_eq!(svc.items().unwrap().len(), 2);
+        assert_eq!(svc.get_item(shared).unwrap().password, "old");
+    }
+
+    #[test]
+    fn test_import_as_new_renames_collisions() {
+        let (dir, mut svc) = setup();
+        let (export_path, shared) = colliding_export(&dir, &mut svc);
+
+        svc.import(&export_path, "export_pass", ImportMode::ImportAsNew)
+            .unwrap();
+        let items = svc.items().unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(svc.get_item(shared).unwrap().password, "old");
+        let copy = items
+            .iter()
+            .find(|i| i.title == "Shared (imported)")
+            .unwrap();
+        assert_ne!(copy.id, shared);
+        assert_eq!(copy.password, "new");
+    }
+
+    #[test]
+    fn test_import_prefer_newer_replaces_older_local_copy() {
+        let (dir, mut svc) = setup();
+        let (export_path, shared) = colliding_export(&dir, &mut svc);
+
+        svc.import(&export_path, "export_pass", ImportMode::PreferNewer)
+            .unwrap();
+        assert_eq!(svc.items().unwrap().len(), 2);
+        assert_eq!(svc.get_item(shared).unwrap().password, "new");
+
+        // Once the local copy is newer than the file's, importing again keeps it.
+        let local = svc.item_mut(shared).unwrap();
+        local.password = "newest".to_string();
+        local.modified_at += chrono::Duration::seconds(60);
+        svc.import(&export_path, "export_pass", ImportMode::PreferNewer)
+            .unwrap();
+        assert_eq!(svc.get_item(shared).unwrap().password, "newest");
+    }
+
+    #[test]
+    fn test_create_past_quota_fails() {
+        let (_dir, mut svc) = setup();
+        svc.set_quotas(Some(1), Some(1));
+        svc.create_group("Work".to_string(), None).unwrap();
+        login(&mut svc, "One", "", "");
+
+        assert!(matches!(
+            svc.create_item(ItemDraft::default()),
+            Err(VaulturaError::QuotaExceeded {
+                kind: "items",
+                limit: 1
+            })
+        ));
+        assert!(matches!(
+            svc.create_group("Home".to_string(), None),
+            Err(VaulturaError::QuotaExceeded {
+                kind: "groups",
+                limit: 1
+            })
+        ));
+        assert_eq!(svc.items().unwrap().len(), 1);
+        assert_eq!(svc.groups().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_import_stops_at_remaining_quota() {
+        let (dir, mut svc) = setup();
+        login(&mut svc, "Local", "", "");
+        let export_path = dir.path().join("export.vault");
+        let mut other = VaultService::new(dir.path().join("other.vault"), test_params());
+        other.create("pass").unwrap();
+        for title in ["A", "B", "C"] {
+            login(&mut other, title, "", "");
+        }
+        other.export(&export_path, "export_pass").unwrap();
+
+        svc.set_quotas(Some(3), None);
+        let count = svc
+            .import(&export_path, "export_pass", ImportMode::SkipExisting)
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(svc.items().unwrap().len(), 3);
+        assert_eq!(
+            svc.import_warnings(),
+            ["Skipped 1 item: the vault is limited to 3 items"]
+        );
+    }
+
+    #[test]
+    fn test_import_drops_references_to_groups_skipped_for_quota() {
+        let (dir, mut svc) = setup();
+        svc.create_group("Local".to_string(), None).unwrap();
+        let export_path = dir.path().join("export.vault");
+        let mut other = VaultService::new(dir.path().join("other.vault"), test_params());
+        other.create("pass").unwrap();
+        let sub = other.create_group("Sub".to_string(), None).unwrap();
+        let work = other.create_group("Work".to_string(), None).unwrap();
+        other
+            .update_group(sub, "Sub".to_string(), Some(work))
+            .unwrap();
+        for (title, group_id) in [("A", Some(sub)), ("B", Some(work)), ("C", Some(work))] {
+            other
+                .create_item(ItemDraft {
+                    title: title.to_string(),
+                    group_id,
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        other.export(&export_path, "export_pass").unwrap();
+
+        // Room for one more group: "Sub" is kept, "Work" is not. Items arrive in
+        // batches after the groups and are still unlinked from the skipped one.
+        svc.set_quotas(None, Some(2));
+        let mut job = svc
+            .begin_import_vault(&export_path, "export_pass", ImportMode::SkipExisting)
+            .unwrap();
+        while !svc.import_step(&mut job, 1).unwrap().is_done() {}
+        assert_eq!(svc.finish_import(job), 4);
+
+        let groups = svc.groups().unwrap();
+        assert!(groups.iter().all(|g| g.id != work));
+        let kept = groups.iter().find(|g| g.id == sub).unwrap();
+        assert_eq!(kept.parent_id, None);
+        let items = svc.items().unwrap();
+        assert!(items.iter().all(|i| i.group_id != Some(work)));
+        assert_eq!(items.iter().filter(|i| i.group_id == Some(sub)).count(), 1);
+        assert_eq!(
+            svc.import_warnings(),
+            [
+                "Skipped 1 group: the vault is limited to 2 groups",
+                "Left 2 items ungrouped: the vault is limited to 2 groups"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_restore_item_respects_item_quota() {
+        let (_dir, mut svc) = setup();
+        let id = login(&mut svc, "Old", "", "");
+        let deleted = svc.get_item(id).unwrap().clone();
+        svc.delete_item(id).unwrap();
+        login(&mut svc, "New", "", "");
+
+        svc.set_quotas(Some(1), None);
+        assert!(matches!(
+            svc.restore_item(deleted.clone()),
+            Err(VaulturaError::QuotaExceeded {
+                kind: "items",
+                limit: 1
+            })
+        ));
+        svc.set_quotas(None, None);
+        svc.restore_item(deleted).unwrap();
+        assert_eq!(svc.items().unwrap().len(), 2);
+    }
+
+    #[cfg(feature = "kdbx")]
+    #[test]
+    fn test_import_kdbx() {
+        let (_dir, mut svc) = setup();
+        let fixture =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sample.kdbx");
+
+        let count = svc.import_kdbx(&fixture, "fixture").unwrap();
+        assert_eq!(count, 5); // 2 groups + 3 entries
+        assert_eq!(svc.groups().unwrap().len(), 2);
+        assert_eq!(svc.items().unwrap().len(), 3);
+        assert!(svc.is_dirty());
+    }
+
     #[test]
     fn test_vault_locked_errors() {
         let dir = TempDir::new().unwrap();
@@ -555,4 +3261,365 @@ mod tests {
         assert!(matches!(svc.groups(), Err(VaulturaError::VaultLocked)));
         assert!(matches!(svc.search("x"), Err(VaulturaError::VaultLocked)));
     }
+
+    #[test]
+    fn test_encrypted_archive_json_roundtrip() {
+        let (dir, mut svc) = setup();
+        let group = svc.create_group("Work".to_string(), None).unwrap();
+        svc.create_item(ItemDraft {
+            title: "GitHub".to_string(),
+            password: "s3cret".to_string(),
+            tags: vec!["dev".to_string()],
+            group_id: Some(group),
+            ..Default::default()
+        })
+        .unwrap();
+        let archive_path = dir.path().join("share.vlta");
+        svc.export_encrypted_archive(&archive_path, "share", ArchiveFormat::Json)
+            .unwrap();
+
+        let other_path = dir.path().join("other.vault");
+        let mut other = VaultService::new(other_path, test_params());
+        other.create("other").unwrap();
+        assert!(matches!(
+            other.import_encrypted_archive(&archive_path, "nope"),
+            Err(VaulturaError::WrongPassword)
+        ));
+        assert_eq!(
+            other
+                .import_encrypted_archive(&archive_path, "share")
+                .unwrap(),
+            2
+        );
+        assert_eq!(other.items().unwrap(), svc.items().unwrap());
+        assert_eq!(other.groups().unwrap(), svc.groups().unwrap());
+
+        // Importing the same archive again does not duplicate anything.
+        other
+            .import_encrypted_archive(&archive_path, "share")
+            .unwrap();
+        assert_eq!(other.items().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_sealed_export_needs_threshold_shares() {
+        let (dir, mut svc) = setup();
+        svc.create_item(ItemDraft {
+            title: "Estate".to_string(),
+            password: "s3cret".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let sealed_path = dir.path().join("estate.vlts");
+        let shares = svc.export_sealed(&sealed_path, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        let other_path = dir.path().join("other.vault");
+        let mut other = VaultService::new(other_path, test_params());
+        other.create("other").unwrap();
+        assert!(other.import_sealed(&sealed_path, &shares[1..3]).is_err());
+        assert!(other.items().unwrap().is_empty());
+
+        let picked = [shares[4].clone(), shares[0].clone(), shares[2].clone()];
+        assert_eq!(other.import_sealed(&sealed_path, &picked).unwrap(), 1);
+        assert_eq!(other.items().unwrap(), svc.items().unwrap());
+
+        // Shares of another export do not open this one.
+        let foreign = svc.export_sealed(&dir.path().join("b.vlts"), 3, 3).unwrap();
+        assert!(other.import_sealed(&sealed_path, &foreign).is_err());
+
+        // A mistyped share is reported by position, never echoed back.
+        let mut typo = picked.clone();
+        typo[1].push('x');
+        let error = other.import_sealed(&sealed_path, &typo).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Import error: share 2 is not a valid share"
+        );
+    }
+
+    #[test]
+    fn test_encrypted_archive_csv_roundtrip() {
+        let (dir, mut svc) = setup();
+        let group = svc.create_group("Work".to_string(), None).unwrap();
+        svc.create_item(ItemDraft {
+            title: "Jira".to_string(),
+            username: "alice".to_string(),
+            group_id: Some(group),
+            ..Default::default()
+        })
+        .unwrap();
+        let archive_path = dir.path().join("share.vlta");
+        svc.export_encrypted_archive(&archive_path, "share", ArchiveFormat::Csv)
+            .unwrap();
+
+        let other_path = dir.path().join("other.vault");
+        let mut other = VaultService::new(other_path, test_params());
+        other.create("other").unwrap();
+        assert_eq!(
+            other
+                .import_encrypted_archive(&archive_path, "share")
+                .unwrap(),
+            1
+        );
+
+        let item = &other.items().unwrap()[0];
+        assert_eq!(item.title, "Jira");
+        assert_eq!(item.username, "alice");
+        let group = &other.groups().unwrap()[0];
+        assert_eq!(group.name, "Work");
+        assert_eq!(item.group_id, Some(group.id));
+    }
+
+    #[test]
+    fn test_import_sanitizes_credentials_but_not_notes() {
+        let (dir, mut svc) = setup();
+        svc.create_item(ItemDraft {
+            title: "Bank".to_string(),
+            username: "alice\u{00A0}".to_string(),
+            password: "hunter\u{200B}2".to_string(),
+            notes: "zero\u{200B}width".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let archive_path = dir.path().join("share.vlta");
+        svc.export_encrypted_archive(&archive_path, "share", ArchiveFormat::Csv)
+            .unwrap();
+
+        let mut other = VaultService::new(dir.path().join("other.vault"), test_params());
+        other.create("other").unwrap();
+        other
+            .import_encrypted_archive(&archive_path, "share")
+            .unwrap();
+        assert_eq!(other.sanitized_fields(), 2);
+        let item = &other.items().unwrap()[0];
+        assert_eq!(item.username, "alice ");
+        assert_eq!(item.password, "hunter2");
+        assert_eq!(item.notes, "zero\u{200B}width");
+
+        // Vaultura's own formats come back exactly as they were saved.
+        let json_path = dir.path().join("own.vlta");
+        svc.export_encrypted_archive(&json_path, "share", ArchiveFormat::Json)
+            .unwrap();
+        let mut own = VaultService::new(dir.path().join("own.vault"), test_params());
+        own.create("own").unwrap();
+        own.import_encrypted_archive(&json_path, "share").unwrap();
+        assert_eq!(own.sanitized_fields(), 0);
+        assert_eq!(own.items().unwrap()[0].password, "hunter\u{200B}2");
+
+        let mut raw = VaultService::new(dir.path().join("raw.vault"), test_params());
+        raw.create("raw").unwrap();
+        raw.set_sanitize_imports(false);
+        raw.import_encrypted_archive(&archive_path, "share")
+            .unwrap();
+        assert_eq!(raw.sanitized_fields(), 0);
+        assert_eq!(raw.items().unwrap()[0].password, "hunter\u{200B}2");
+    }
+
+    #[test]
+    fn test_csv_archive_with_bad_bytes_imports_with_warnings() {
+        let (dir, mut svc) = setup();
+        let mut csv = b"title,username,password,url,notes,tags,group\n".to_vec();
+        csv.extend_from_slice(b"Caf\xe9,alice,pw,,,,\n");
+        csv.extend_from_slice(b"Wiki,bob,secret\0,,,,Work\n");
+        let archive_path = dir.path().join("bad.vlta");
+        archive::write_archive(
+            &archive_path,
+            "share",
+            &test_params(),
+            ArchiveFormat::Csv,
+            &csv,
+        )
+        .unwrap();
+
+        assert_eq!(
+            svc.import_encrypted_archive(&archive_path, "share")
+                .unwrap(),
+            2
+        );
+        assert_eq!(svc.import_warnings().len(), 2);
+        let titles: Vec<&str> = svc
+            .items()
+            .unwrap()
+            .iter()
+            .map(|i| i.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Caf\u{fffd}", "Wiki"]);
+        assert_eq!(svc.items().unwrap()[1].password, "secret");
+
+        svc.undo_last_import().unwrap();
+        assert!(svc.import_warnings().is_empty());
+    }
+
+    fn imported_items(count: usize) -> Vec<Item> {
+        (0..count)
+            .map(|i| {
+                let mut item = Item::new(format!("Imported {i}"), None);
+                item.password = format!("pw{i}");
+                item
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_import_job_progress_accounting() {
+        let (_dir, mut svc) = setup();
+        let group = Group::new("Imports".to_string(), None);
+        let mut job = svc
+            .begin_import(vec![group], imported_items(1000), ImportMode::SkipExisting)
+            .unwrap();
+        assert_eq!(job.progress().status(), "Importing 0/1000…");
+
+        let mut seen = Vec::new();
+        loop {
+            let progress = svc.import_step(&mut job, 300).unwrap();
+            seen.push(progress.processed);
+            if progress.is_done() {
+                break;
+            }
+        }
+        assert_eq!(seen, [300, 600, 900, 1000]);
+        assert_eq!(svc.items().unwrap().len(), 1000);
+        assert_eq!(svc.groups().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_cancel_import_restores_original_vault() {
+        let (_dir, mut svc) = setup();
+        svc.set_seal_passwords(true).unwrap();
+        login(&mut svc, "Mail", "me", "hunter2");
+        svc.save().unwrap();
+        let before = svc.payload().unwrap().clone();
+
+        let mut job = svc
+            .begin_import(Vec::new(), imported_items(50), ImportMode::ImportAsNew)
+            .unwrap();
+        svc.import_step(&mut job, 20).unwrap();
+        assert_eq!(svc.items().unwrap().len(), 21);
+        assert!(svc.is_dirty());
+
+        svc.cancel_import(job).unwrap();
+        assert_eq!(svc.items().unwrap().len(), 1);
+        assert_eq!(*svc.payload().unwrap(), before);
+        assert!(!svc.is_dirty());
+        let id = svc.items().unwrap()[0].id;
+        assert_eq!(svc.with_password(id, str::to_string).unwrap(), "hunter2");
+    }
+
+    #[test]
+    fn test_undo_last_import_restores_payload_exactly() {
+        let (dir, mut svc) = setup();
+        login(&mut svc, "Mail", "me", "hunter2");
+        svc.create_group("Work".to_string(), None).unwrap();
+        svc.save().unwrap();
+        let before = svc.payload().unwrap().clone();
+
+        let mut source = VaultService::new(dir.path().join("source.vault"), test_params());
+        source.create("source").unwrap();
+        source.create_group("Imported".to_string(), None).unwrap();
+        login(&mut source, "Bank", "you", "s3cret");
+        let export_path = dir.path().join("export.vault");
+        source.export(&export_path, "share").unwrap();
+
+        assert!(!svc.can_undo_import());
+        assert_eq!(
+            svc.import(&export_path, "share", ImportMode::SkipExisting)
+                .unwrap(),
+            2
+        );
+        assert_eq!(svc.items().unwrap().len(), 2);
+        assert!(svc.can_undo_import());
+
+        assert!(svc.undo_last_import().unwrap());
+        assert_eq!(*svc.payload().unwrap(), before);
+        assert!(!svc.is_dirty());
+        assert!(!svc.undo_last_import().unwrap());
+
+        // Once the import has been saved, undoing it must be saved too.
+        svc.import(&export_path, "share", ImportMode::SkipExisting)
+            .unwrap();
+        svc.save().unwrap();
+        assert!(svc.undo_last_import().unwrap());
+        assert_eq!(*svc.payload().unwrap(), before);
+        assert!(svc.is_dirty());
+
+        svc.set_backup_before_import(false);
+        svc.import(&export_path, "share", ImportMode::SkipExisting)
+            .unwrap();
+        assert!(!svc.can_undo_import());
+    }
+
+    #[test]
+    fn test_reload_drops_the_import_backup() {
+        let (dir, mut svc) = setup();
+        svc.save().unwrap();
+
+        let mut source = VaultService::new(dir.path().join("source.vault"), test_params());
+        source.create("source").unwrap();
+        login(&mut source, "Bank", "you", "s3cret");
+        let export_path = dir.path().join("export.vault");
+        source.export(&export_path, "share").unwrap();
+        svc.import(&export_path, "share", ImportMode::SkipExisting)
+            .unwrap();
+        assert!(svc.can_undo_import());
+
+        svc.reload().unwrap();
+        assert!(svc.items().unwrap().is_empty());
+        assert!(!svc.can_undo_import());
+        assert!(!svc.undo_last_import().unwrap());
+    }
+
+    #[test]
+    fn test_decoy_password_opens_decoy_vault() {
+        let dir = TempDir::new().unwrap();
+        let real_path = dir.path().join("real.vault");
+        let decoy_path = dir.path().join("decoy.vault");
+
+        let mut real = VaultService::new(real_path.clone(), test_params());
+        real.create("real-pw").unwrap();
+        real.create_item(ItemDraft {
+            title: "Bank".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        real.save().unwrap();
+
+        let mut decoy = VaultService::new(decoy_path.clone(), test_params());
+        decoy.create("duress-pw").unwrap();
+        decoy
+            .create_item(ItemDraft {
+                title: "Forum".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        decoy.save().unwrap();
+
+        let mut svc = VaultService::new(real_path.clone(), test_params());
+        svc.set_decoy_path(Some(decoy_path.clone()));
+
+        svc.unlock("duress-pw").unwrap();
+        assert_eq!(svc.items().unwrap()[0].title, "Forum");
+        assert_eq!(svc.vault_path(), decoy_path);
+        // Edits made while the decoy is open stay in the decoy file.
+        svc.create_item(ItemDraft {
+            title: "News".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        svc.save().unwrap();
+        svc.lock();
+
+        svc.unlock("real-pw").unwrap();
+        assert_eq!(svc.items().unwrap().len(), 1);
+        assert_eq!(svc.items().unwrap()[0].title, "Bank");
+        assert_eq!(svc.vault_path(), real_path);
+        svc.lock();
+
+        assert!(matches!(
+            svc.unlock("neither"),
+            Err(VaulturaError::WrongPassword)
+        ));
+        svc.unlock("duress-pw").unwrap();
+        assert_eq!(svc.items().unwrap().len(), 2);
+    }
 }
@@ -1,2 +1,4 @@
 pub mod aead;
 pub mod kdf;
+pub mod session;
+pub mod shamir;
@@ -0,0 +1,64 @@
+use rand::RngCore;
+use secrecy::SecretBox;
+use zeroize::Zeroizing;
+
+use crate::crypto::aead;
+use crate::error::{Result, VaulturaError};
+
+const KEY_LENGTH: usize = 32;
+
+/// A random key that only ever exists in memory, for the length of one unlock.
+pub struct SessionKey(SecretBox<Vec<u8>>);
+
+/// A secret encrypted under a `SessionKey`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SealedSecret {
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+impl SealedSecret {
+    pub fn ciphertext(&self) -> &[u8] {
+        &self.ciphertext
+    }
+}
+
+impl SessionKey {
+    pub fn generate() -> Self {
+        let mut key = vec![0u8; KEY_LENGTH];
+        rand::thread_rng().fill_bytes(&mut key);
+        Self(SecretBox::new(Box::new(key)))
+    }
+
+    pub fn seal(&self, plaintext: &str) -> Result<SealedSecret> {
+        let (nonce, ciphertext) = aead::encrypt(&self.0, plaintext.as_bytes())?;
+        Ok(SealedSecret { nonce, ciphertext })
+    }
+
+    /// Decrypt `sealed`; the plaintext is wiped when the returned value is dropped.
+    pub fn open(&self, sealed: &SealedSecret) -> Result<Zeroizing<String>> {
+        let bytes = aead::decrypt(&self.0, &sealed.nonce, &sealed.ciphertext)?;
+        String::from_utf8(bytes)
+            .map(Zeroizing::new)
+            .map_err(|e| VaulturaError::Decryption(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let key = SessionKey::generate();
+        let sealed = key.seal("hunter2").unwrap();
+        assert_ne!(sealed.ciphertext(), b"hunter2");
+        assert_eq!(key.open(&sealed).unwrap().as_str(), "hunter2");
+    }
+
+    #[test]
+    fn test_other_session_cannot_open() {
+        let sealed = SessionKey::generate().seal("hunter2").unwrap();
+        assert!(SessionKey::generate().open(&sealed).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+use crate::error::{Result, VaulturaError};
+
+/// Prefix of every encoded share, so a share is recognizable on paper.
+const SHARE_PREFIX: &str = "vltr";
+
+/// One part of a secret split with [`split`]; `threshold` parts recombine it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Share {
+    pub threshold: u8,
+    /// The x coordinate, 1-based; never 0, which is where the secret lives.
+    pub index: u8,
+    pub data: Zeroizing<Vec<u8>>,
+}
+
+impl Share {
+    /// Text form `vltr-<threshold>-<index>-<hex>` for printing and typing back in.
+    pub fn encode(&self) -> String {
+        let hex: String = self.data.iter().map(|b| format!("{b:02x}")).collect();
+        format!("{SHARE_PREFIX}-{}-{}-{hex}", self.threshold, self.index)
+    }
+
+    pub fn decode(text: &str) -> Result<Self> {
+        // Never echo the text: a mistyped share is still most of a secret.
+        let invalid = || VaulturaError::Config("Not a share".to_string());
+        let mut parts = text.trim().splitn(4, '-');
+        if parts.next() != Some(SHARE_PREFIX) {
+            return Err(invalid());
+        }
+        let mut number = || -> Result<u8> {
+            parts
+                .next()
+                .and_then(|p| p.parse().ok())
+                .ok_or_else(invalid)
+        };
+        let threshold = number()?;
+        let index = number()?;
+        let hex = parts.next().ok_or_else(invalid)?;
+        if index == 0 || hex.is_empty() || hex.len() % 2 != 0 {
+            return Err(invalid());
+        }
+        let data = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(invalid)?;
+        Ok(Self {
+            threshold,
+            index,
+            data: Zeroizing::new(data),
+        })
+    }
+}
+
+/// Split `secret` into `shares` parts, any `threshold` of which recombine it.
+///
+/// Shamir's scheme over GF(2^8), applied to each byte independently: fewer
+/// than `threshold` shares reveal nothing about the secret.
+pub fn split(secret: &[u8], threshold: u8, shares: u8) -> Result<Vec<Share>> {
+    if threshold < 2 || threshold > shares {
+        return Err(VaulturaError::Config(format!(
+            "Need 2 <= threshold <= shares, got {threshold} of {shares}"
+        )));
+    }
+    let mut out: Vec<Share> = (1..=shares)
+        .map(|index| Share {
+            threshold,
+            index,
+            data: Zeroizing::new(Vec::with_capacity(secret.len())),
+        })
+        .collect();
+
+    let mut coefficients = Zeroizing::new(vec![0u8; threshold as usize]);
+    for &byte in secret {
+        coefficients[0] = byte;
+        rand::thread_rng().fill_bytes(&mut coefficients[1..]);
+        for share in &mut out {
+            share.data.push(evaluate(&coefficients, share.index));
+        }
+    }
+    Ok(out)
+}
+
+/// Recombine the secret from at least `threshold` distinct shares of one split.
+pub fn combine(shares: &[Share]) -> Result<Zeroizing<Vec<u8>>> {
+    let first = shares
+        .first()
+        .ok_or_else(|| VaulturaError::Config("No shares given".to_string()))?;
+    let threshold = first.threshold;
+    let length = first.data.len();
+    if shares
+        .iter()
+        .any(|s| s.threshold != threshold || s.data.len() != length)
+    {
+        return Err(VaulturaError::Config(
+            "Shares come from different exports".to_string(),
+        ));
+    }
+    let mut indexes: Vec<u8> = shares.iter().map(|s| s.index).collect();
+    indexes.sort_unstable();
+    indexes.dedup();
+    if indexes.len() < threshold as usize {
+        return Err(VaulturaError::Config(format!(
+            "{threshold} different shares are needed, got {}",
+            indexes.len()
+        )));
+    }
+
+    // Lagrange interpolation at x = 0, using exactly `threshold` shares.
+    let mut used: Vec<&Share> = Vec::with_capacity(threshold as usize);
+    for share in shares {
+        if used.len() < threshold as usize && !used.iter().any(|u| u.index == share.index) {
+            used.push(share);
+        }
+    }
+    let mut secret = Zeroizing::new(vec![0u8; length]);
+    for (i, share) in used.iter().enumerate() {
+        let mut basis = 1u8;
+        for (j, other) in used.iter().enumerate() {
+            if i != j {
+                basis = mul(basis, div(other.index, other.index ^ share.index));
+            }
+        }
+        for (byte, &y) in secret.iter_mut().zip(share.data.iter()) {
+            *byte ^= mul(y, basis);
+        }
+    }
+    Ok(secret)
+}
+
+/// Evaluate the polynomial with `coefficients` (constant term first) at `x`.
+fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+    coefficients
+        .iter()
+        .rev()
+        .fold(0, |acc, &coefficient| mul(acc, x) ^ coefficient)
+}
+
+/// Multiplication in GF(2^8) with the AES polynomial x^8 + x^4 + x^3 + x + 1.
+fn mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Division in GF(2^8); `b` is never 0 because share indexes are distinct.
+fn div(a: u8, b: u8) -> u8 {
+    // b^254 is b's inverse, since every nonzero element satisfies b^255 = 1.
+    let mut inverse = 1;
+    for _ in 0..254 {
+        inverse = mul(inverse, b);
+    }
+    mul(a, inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_shares_reconstruct_and_fewer_do_not() {
+        let secret: Vec<u8> = (0..32).collect();
+        let shares = split(&secret, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        for picked in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let subset: Vec<Share> = picked.iter().map(|&i| shares[i].clone()).collect();
+            assert_eq!(*combine(&subset).unwrap(), secret);
+        }
+
+        assert!(combine(&shares[..2]).is_err());
+        // Repeating a share does not count twice.
+        let repeated = [shares[0].clone(), shares[0].clone(), shares[1].clone()];
+        assert!(combine(&repeated).is_err());
+    }
+
+    #[test]
+    fn test_share_text_round_trip() {
+        let shares = split(b"key", 2, 3).unwrap();
+        let text = shares[1].encode();
+        assert!(text.starts_with("vltr-2-2-"));
+        assert_eq!(Share::decode(&format!("  {text}\n")).unwrap(), shares[1]);
+
+        assert!(Share::decode("vltr-2-0-abcd").is_err());
+        assert!(Share::decode("vltr-2-1-abc").is_err());
+        assert!(Share::decode("other-2-1-abcd").is_err());
+    }
+
+    #[test]
+    fn test_invalid_threshold_rejected() {
+        assert!(split(b"key", 1, 3).is_err());
+        assert!(split(b"key", 4, 3).is_err());
+    }
+}
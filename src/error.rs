@@ -17,6 +17,9 @@ pub enum VaulturaError {
     #[error("Vault is locked")]
     VaultLocked,
 
+    #[error("Save or discard unsaved changes first")]
+    UnsavedChanges,
+
     #[error("Encryption error: {0}")]
     Encryption(String),
 
@@ -35,6 +38,24 @@ pub enum VaulturaError {
     #[error("Clipboard error: {0}")]
     Clipboard(String),
 
+    #[error("{name} is {size} bytes, over the {limit}-byte limit for embedded files")]
+    FileTooLarge { name: String, size: u64, limit: u64 },
+
+    #[error("The vault is limited to {limit} {kind}")]
+    QuotaExceeded { kind: &'static str, limit: usize },
+
+    #[error("Cannot generate a password: {0}")]
+    Generator(String),
+
+    #[error("Integrity check failed: {0}")]
+    Integrity(String),
+
+    #[error("Import error: {0}")]
+    Import(String),
+
+    #[error("Export error: {0}")]
+    Export(String),
+
     #[error("Config error: {0}")]
     Config(String),
 
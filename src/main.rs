@@ -1,11 +1,13 @@
 #![forbid(unsafe_code)]
 
-use std::io;
-use std::path::PathBuf;
+use std::io::{self, BufRead, IsTerminal};
+use std::path::{Path, PathBuf};
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
-use vaultura::config::AppConfig;
+use vaultura::config::{self, AppConfig};
+use vaultura::core::integrity;
+use vaultura::core::vault_service::{CompactOptions, VaultService};
 use vaultura::ui::app::App;
 
 #[derive(Parser)]
@@ -16,30 +18,120 @@ use vaultura::ui::app::App;
 )]
 struct Cli {
     /// Path to the vault file
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     vault: Option<PathBuf>,
 
     /// Path to the config file
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check that a vault decrypts and is structurally sound, without starting the UI
+    Verify,
+    /// Drop references to missing groups and optionally password history, then rewrite the vault
+    Compact {
+        /// Also delete every item's previous passwords
+        #[arg(long)]
+        clear_history: bool,
+        /// Print what would be removed without changing the vault
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Write a JSON list of groups and items without passwords, notes or files
+    Inventory {
+        /// Where to write the JSON
+        output: PathBuf,
+        /// Include usernames
+        #[arg(long)]
+        usernames: bool,
+    },
+    /// Export the vault under a key split into shares, printing one share per holder
+    ExportSealed {
+        /// Where to write the sealed export
+        output: PathBuf,
+        /// Shares needed to open the export
+        #[arg(long, default_value_t = 2)]
+        threshold: u8,
+        /// Shares to print
+        #[arg(long, default_value_t = 3)]
+        shares: u8,
+    },
+    /// Merge a sealed export into the vault; shares are read from stdin, one per line
+    ImportSealed {
+        /// The sealed export to open
+        input: PathBuf,
+    },
 }
 
 fn main() -> io::Result<()> {
     let cli = Cli::parse();
 
+    let mut config_warning = None;
     let mut config = if let Some(ref config_path) = cli.config {
         AppConfig::load_from(config_path).unwrap_or_else(|e| {
             eprintln!("Warning: could not load config: {e}");
             AppConfig::default()
         })
     } else {
-        AppConfig::load().unwrap_or_else(|_| AppConfig::default())
+        let config = AppConfig::load().unwrap_or_else(|e| {
+            eprintln!("Warning: could not load config: {e}");
+            AppConfig::default()
+        });
+        // Persist the defaults before env and CLI overrides are layered on top.
+        if let Err(e) = config.ensure_persisted(&config::config_file_path()) {
+            config_warning = Some(e.to_string());
+        }
+        config
     };
 
+    config.apply_env_overrides();
+
     if let Some(vault_path) = cli.vault {
         config.vault_path = vault_path;
     }
 
+    match cli.command {
+        Some(Command::Verify) => {
+            run_verify(&config.vault_path)?;
+            return Ok(());
+        }
+        Some(Command::Compact {
+            clear_history,
+            dry_run,
+        }) => {
+            run_compact(
+                &config,
+                CompactOptions {
+                    clear_history,
+                    dry_run,
+                },
+            )?;
+            return Ok(());
+        }
+        Some(Command::Inventory { output, usernames }) => {
+            run_inventory(&config, &output, usernames)?;
+            return Ok(());
+        }
+        Some(Command::ExportSealed {
+            output,
+            threshold,
+            shares,
+        }) => {
+            run_export_sealed(&config, &output, threshold, shares)?;
+            return Ok(());
+        }
+        Some(Command::ImportSealed { input }) => {
+            run_import_sealed(&config, &input)?;
+            return Ok(());
+        }
+        None => {}
+    }
+
     // Install panic hook that restores terminal
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
@@ -52,8 +144,172 @@ fn main() -> io::Result<()> {
         original_hook(panic_info);
     }));
 
+    let mut app = App::new(config);
+    match config_warning {
+        // Nothing can be written, so UI changes stay in memory.
+        Some(warning) => app = app.with_config_warning(warning),
+        None => app = app.with_config_path(cli.config.unwrap_or_else(config::config_file_path)),
+    }
+    // Before the TUI takes over the terminal, so the command can prompt on it.
+    app.unlock_with_password_command();
     let mut terminal = ratatui::init();
-    let result = App::new(config).run(&mut terminal);
+    let result = app.run(&mut terminal);
     ratatui::restore();
     result
 }
+
+/// Verify the vault headlessly, exiting non-zero on the first problem found.
+fn run_verify(path: &Path) -> io::Result<()> {
+    let password = read_password()?;
+    match integrity::verify_vault(path, &password) {
+        Ok(report) => {
+            println!(
+                "OK: {} ({} groups, {} items)",
+                path.display(),
+                report.groups,
+                report.items
+            );
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("FAILED: {}: {e}", path.display());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Compact the vault headlessly and print what was removed.
+fn run_compact(config: &AppConfig, options: CompactOptions) -> io::Result<()> {
+    let password = read_password()?;
+    let mut service = VaultService::new(config.vault_path.clone(), config.kdf_params());
+    service.set_verify_after_write(config.verify_after_write);
+    service.set_pad_to_block(config.pad_to_block);
+    let result = service
+        .unlock(&password)
+        .and_then(|()| service.compact(options));
+    match result {
+        Ok(report) if report.dry_run => {
+            println!(
+                "Would compact {}: {} history entries from {} items, {} orphaned references ({} groups)",
+                config.vault_path.display(),
+                report.history_entries_removed,
+                report.affected_items.len(),
+                report.orphaned_references_removed,
+                report.affected_groups.len()
+            );
+            Ok(())
+        }
+        Ok(report) => {
+            println!(
+                "Compacted {}: {} history entries, {} orphaned references, {} bytes removed",
+                config.vault_path.display(),
+                report.history_entries_removed,
+                report.orphaned_references_removed,
+                report.bytes_removed()
+            );
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("FAILED: {}: {e}", config.vault_path.display());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Export the secret-free inventory headlessly.
+fn run_inventory(config: &AppConfig, output: &Path, include_usernames: bool) -> io::Result<()> {
+    let password = read_password()?;
+    let mut service = VaultService::new(config.vault_path.clone(), config.kdf_params());
+    let result = service
+        .unlock(&password)
+        .and_then(|()| service.export_inventory(output, include_usernames));
+    match result {
+        Ok(()) => {
+            println!("Wrote inventory to {}", output.display());
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("FAILED: {}: {e}", config.vault_path.display());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Write a sealed export headlessly and print its shares.
+fn run_export_sealed(
+    config: &AppConfig,
+    output: &Path,
+    threshold: u8,
+    shares: u8,
+) -> io::Result<()> {
+    let password = read_password()?;
+    let mut service = VaultService::new(config.vault_path.clone(), config.kdf_params());
+    let result = service
+        .unlock(&password)
+        .and_then(|()| service.export_sealed(output, threshold, shares));
+    match result {
+        Ok(shares) => {
+            println!(
+                "Wrote {}; any {threshold} of these shares open it. Give one to each holder:",
+                output.display()
+            );
+            for share in shares {
+                println!("{share}");
+            }
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("FAILED: {}: {e}", output.display());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Merge a sealed export headlessly, reading shares until an empty line or EOF.
+fn run_import_sealed(config: &AppConfig, input: &Path) -> io::Result<()> {
+    let password = read_password()?;
+    if io::stdin().is_terminal() {
+        eprintln!("Enter shares, one per line, then an empty line:");
+    }
+    let mut shares = Vec::new();
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            break;
+        }
+        shares.push(line);
+    }
+
+    let mut service = VaultService::new(config.vault_path.clone(), config.kdf_params());
+    service.set_verify_after_write(config.verify_after_write);
+    service.set_pad_to_block(config.pad_to_block);
+    service.set_sanitize_imports(config.sanitize_imports);
+    service.set_quotas(config.max_items, config.max_groups);
+    let result = service
+        .unlock(&password)
+        .and_then(|()| service.import_sealed(input, &shares))
+        .and_then(|count| service.save().map(|()| count));
+    match result {
+        Ok(count) => {
+            println!("Imported {count} groups and items from {}", input.display());
+            for warning in service.import_warnings() {
+                println!("{warning}");
+            }
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("FAILED: {}: {e}", input.display());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Prompt without echo on a terminal; read a single line when stdin is piped.
+fn read_password() -> io::Result<String> {
+    if io::stdin().is_terminal() {
+        return rpassword::prompt_password("Master password: ");
+    }
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
@@ -0,0 +1,116 @@
+use std::fs;
+use std::path::Path;
+
+use crate::core::models::KdfParams;
+use crate::error::{Result, VaulturaError};
+use crate::storage::atomic;
+use crate::storage::format::{ARCHIVE_MAGIC, ARCHIVE_VERSION};
+use crate::storage::vault_file;
+
+/// Plaintext format carried inside an export archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Csv,
+    Json,
+}
+
+impl ArchiveFormat {
+    fn content_type(self) -> u8 {
+        match self {
+            Self::Csv => 1,
+            Self::Json => 2,
+        }
+    }
+
+    fn from_content_type(byte: u8) -> Result<Self> {
+        match byte {
+            1 => Ok(Self::Csv),
+            2 => Ok(Self::Json),
+            other => Err(VaulturaError::InvalidVaultFile {
+                reason: format!("Unknown archive content type: {other}"),
+            }),
+        }
+    }
+}
+
+/// Header: magic(4) + version(4) + content type(1).
+const HEADER_LENGTH: usize = 4 + 4 + 1;
+
+/// Encrypt `contents` into a standalone archive:
+/// `[VLTA magic 4B][version u32 LE][content type 1B][salt][kdf_params][nonce][ciphertext]`.
+pub fn write_archive(
+    path: &Path,
+    password: &str,
+    kdf_params: &KdfParams,
+    format: ArchiveFormat,
+    contents: &[u8],
+) -> Result<()> {
+    let mut header = Vec::with_capacity(HEADER_LENGTH);
+    header.extend_from_slice(ARCHIVE_MAGIC);
+    header.extend_from_slice(&ARCHIVE_VERSION.to_le_bytes());
+    header.push(format.content_type());
+
+    let data = vault_file::seal(header, password, kdf_params, contents)?;
+    atomic::write(path, &data)
+}
+
+/// Decrypt an archive written by `write_archive`, returning its format and plaintext.
+pub fn read_archive(path: &Path, password: &str) -> Result<(ArchiveFormat, Vec<u8>)> {
+    let data = fs::read(path)?;
+
+    if data.len() < HEADER_LENGTH {
+        return Err(VaulturaError::InvalidVaultFile {
+            reason: "File too small".to_string(),
+        });
+    }
+    if &data[0..4] != ARCHIVE_MAGIC {
+        return Err(VaulturaError::InvalidVaultFile {
+            reason: "Invalid archive magic bytes".to_string(),
+        });
+    }
+    let version = u32::from_le_bytes(data[4..8].try_into().unwrap());
+    if version != ARCHIVE_VERSION {
+        return Err(VaulturaError::InvalidVaultFile {
+            reason: format!("Unsupported archive version: {version}"),
+        });
+    }
+    let format = ArchiveFormat::from_content_type(data[8])?;
+
+    let (contents, _) = vault_file::unseal(&data[HEADER_LENGTH..], password)?;
+    Ok((format, contents))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_archive_roundtrip_keeps_format() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("export.vlta");
+        let params = KdfParams::test_params();
+
+        write_archive(&path, "share", &params, ArchiveFormat::Csv, b"a,b\n").unwrap();
+        let (format, contents) = read_archive(&path, "share").unwrap();
+        assert_eq!(format, ArchiveFormat::Csv);
+        assert_eq!(contents, b"a,b\n");
+
+        assert!(matches!(
+            read_archive(&path, "wrong"),
+            Err(VaulturaError::WrongPassword)
+        ));
+    }
+
+    #[test]
+    fn test_vault_file_is_not_an_archive() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("vault.vltr");
+        vault_file::create_vault(&path, "pw", &KdfParams::test_params()).unwrap();
+
+        assert!(matches!(
+            read_archive(&path, "pw"),
+            Err(VaulturaError::InvalidVaultFile { .. })
+        ));
+    }
+}
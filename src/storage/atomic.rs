@@ -0,0 +1,60 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+
+/// Write `data` to `path` atomically (temp → fsync → rename).
+///
+/// The temp file sits next to the target so the rename never crosses a
+/// filesystem; readers see either the old contents or the new ones, never a
+/// partial file.
+pub fn write(path: &Path, data: &[u8]) -> Result<()> {
+    let temp_path = temp_path(path);
+
+    let mut file = fs::File::create(&temp_path)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+/// The temporary file `write` stages data in before renaming it over `path`.
+pub(crate) fn temp_path(path: &Path) -> PathBuf {
+    let parent = path.parent().unwrap_or(Path::new("."));
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    parent.join(format!(".{name}.vaultura_tmp_{}", std::process::id()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_temp_path_is_a_sibling_of_the_target() {
+        let target = Path::new("/data/vaultura/config.toml");
+        let temp = temp_path(target);
+        assert_eq!(temp.parent(), target.parent());
+        assert_ne!(temp, target);
+    }
+
+    #[test]
+    fn test_write_replaces_target_and_removes_temp() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("file");
+        fs::write(&path, b"old contents that are longer").unwrap();
+        // A temp file left behind by an interrupted write must not leak into the target.
+        fs::write(temp_path(&path), b"partial").unwrap();
+
+        write(&path, b"new").unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+        assert!(!temp_path(&path).exists());
+    }
+}
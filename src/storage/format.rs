@@ -4,6 +4,12 @@ pub const MAGIC: &[u8; 4] = b"VLTR";
 /// Current vault file format version.
 pub const VERSION: u32 = 1;
 
+/// Version marking a vault whose plaintext is length-prefixed and padded to a block size.
+pub const VERSION_PADDED: u32 = 2;
+
+/// Length of the little-endian u64 plaintext length that precedes padded payloads.
+pub const PADDING_LENGTH_PREFIX: usize = 8;
+
 /// Length of the salt in bytes.
 pub const SALT_LENGTH: usize = 32;
 
@@ -15,3 +21,15 @@ pub const KDF_PARAMS_LENGTH: usize = 12;
 
 /// Minimum file size: magic(4) + version(4) + salt(32) + kdf_params(12) + nonce(24) + at least 1 byte ciphertext.
 pub const MIN_FILE_SIZE: usize = 4 + 4 + SALT_LENGTH + KDF_PARAMS_LENGTH + NONCE_LENGTH + 1;
+
+/// Magic bytes identifying a Vaultura export archive: "VLTA"
+pub const ARCHIVE_MAGIC: &[u8; 4] = b"VLTA";
+
+/// Current export archive format version.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Magic bytes identifying an export sealed under secret-shared key: "VLTS"
+pub const SEALED_MAGIC: &[u8; 4] = b"VLTS";
+
+/// Current sealed export format version.
+pub const SEALED_VERSION: u32 = 1;
@@ -0,0 +1,106 @@
+use std::fs::File;
+use std::path::Path;
+
+use keepass::db::{Entry, Group as KdbxGroup};
+use keepass::{Database, DatabaseKey};
+use uuid::Uuid;
+
+use crate::core::models::{Group, ItemKind};
+use crate::core::vault_service::ItemDraft;
+use crate::error::{Result, VaulturaError};
+
+/// Groups and item drafts read from a KeePass database, ready to merge into a vault.
+#[derive(Debug, Default)]
+pub struct KdbxImport {
+    pub groups: Vec<Group>,
+    pub items: Vec<ItemDraft>,
+}
+
+/// Read a KDBX file unlocked with a password-only composite key.
+///
+/// The KeePass root group is not imported itself: its entries become ungrouped items and
+/// its child groups become top-level groups, with deeper nesting kept via `parent_id`.
+pub fn read_kdbx(path: &Path, password: &str) -> Result<KdbxImport> {
+    let mut file = File::open(path)?;
+    let key = DatabaseKey::new().with_password(password);
+    let db = Database::open(&mut file, key).map_err(|e| VaulturaError::Import(e.to_string()))?;
+
+    let mut import = KdbxImport::default();
+    collect_group(&db.root, None, &mut import);
+    Ok(import)
+}
+
+fn collect_group(group: &KdbxGroup, group_id: Option<Uuid>, import: &mut KdbxImport) {
+    for entry in &group.entries {
+        import.items.push(entry_to_draft(entry, group_id));
+    }
+    for child in &group.groups {
+        let mapped = Group::new(child.name.clone(), group_id);
+        let child_id = mapped.id;
+        import.groups.push(mapped);
+        collect_group(child, Some(child_id), import);
+    }
+}
+
+fn entry_to_draft(entry: &Entry, group_id: Option<Uuid>) -> ItemDraft {
+    let field = |key: &str| entry.get(key).unwrap_or_default().to_string();
+    ItemDraft {
+        title: field("Title"),
+        username: field("UserName"),
+        password: field("Password"),
+        url: field("URL"),
+        urls: Vec::new(),
+        notes: field("Notes"),
+        tags: entry.tags.clone(),
+        group_id,
+        attachments: Vec::new(),
+        otp_secret: None,
+        notes_secret: false,
+        kind: ItemKind::Login,
+        launch_command: None,
+        sensitive: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_path() -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sample.kdbx")
+    }
+
+    #[test]
+    fn test_read_kdbx_maps_entries_and_groups() {
+        let import = read_kdbx(&fixture_path(), "fixture").unwrap();
+
+        assert_eq!(import.groups.len(), 2);
+        let work = import.groups.iter().find(|g| g.name == "Work").unwrap();
+        let servers = import.groups.iter().find(|g| g.name == "Servers").unwrap();
+        assert_eq!(work.parent_id, None);
+        assert_eq!(servers.parent_id, Some(work.id));
+
+        assert_eq!(import.items.len(), 3);
+        let github = import.items.iter().find(|i| i.title == "GitHub").unwrap();
+        assert_eq!(github.username, "octocat");
+        assert_eq!(github.password, "gh-secret");
+        assert_eq!(github.url, "https://github.com");
+        assert_eq!(github.notes, "2FA enabled");
+        assert_eq!(github.group_id, Some(work.id));
+
+        let root = import
+            .items
+            .iter()
+            .find(|i| i.title == "Root Entry")
+            .unwrap();
+        assert_eq!(root.group_id, None);
+        let prod = import.items.iter().find(|i| i.title == "Prod DB").unwrap();
+        assert_eq!(prod.group_id, Some(servers.id));
+    }
+
+    #[test]
+    fn test_read_kdbx_wrong_password() {
+        let result = read_kdbx(&fixture_path(), "wrong");
+        assert!(matches!(result, Err(VaulturaError::Import(_))));
+    }
+}
@@ -0,0 +1,441 @@
+//! Payload shapes written by earlier releases.
+//!
+//! bincode is not self-describing, so a field added to `VaultPayload` (even with
+//! `#[serde(default)]`) makes payloads written before it fail to decode. Each old
+//! shape is spelled out here with a conversion to the current one; when a field is
+//! added, freeze the previous shape as the next `PayloadVn` and try it in
+//! [`decode_payload`].
+
+use bincode::Options;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::models::{
+    Attachment, EmbeddedFile, Group, Item, ItemKind, PasswordHistoryEntry, VaultMeta, VaultPayload,
+};
+use crate::error::Result;
+use crate::storage::format::VERSION;
+
+/// Decode a vault plaintext, falling back to older payload shapes.
+///
+/// The current shape is tried first, then the known old shapes, newest first; the
+/// result is in the current shape, so the vault is upgraded the next time it is
+/// saved. When nothing matches, the error from the current shape is returned.
+pub fn decode_payload(plaintext: &[u8], file_version: u32) -> Result<VaultPayload> {
+    let error = match strict().deserialize::<VaultPayload>(plaintext) {
+        Ok(payload) => return Ok(payload),
+        Err(e) => e,
+    };
+    if let Ok(old) = strict().deserialize::<PayloadV4>(plaintext) {
+        return Ok(old.into());
+    }
+    if let Ok(old) = strict().deserialize::<PayloadV3>(plaintext) {
+        return Ok(old.into());
+    }
+    if let Ok(old) = strict().deserialize::<PayloadV2>(plaintext) {
+        return Ok(old.into());
+    }
+    // Padding came after the first release, so only unpadded files can be v1.
+    if file_version == VERSION {
+        if let Ok(old) = strict().deserialize::<PayloadV1>(plaintext) {
+            return Ok(old.into());
+        }
+    }
+    Err(error.into())
+}
+
+/// `bincode::deserialize`'s encoding, but rejecting trailing bytes: a shorter shape
+/// that happens to parse a prefix of the plaintext must not count as a match.
+fn strict() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+}
+
+/// The payload as written by the first release, before items gained attachments,
+/// OTP secrets, extra URLs, kinds and launch commands, and before vaults carried
+/// their own auto-lock timeout.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PayloadV1 {
+    pub meta: MetaV1,
+    pub groups: Vec<GroupV1>,
+    pub items: Vec<ItemV1>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetaV1 {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GroupV1 {
+    pub id: Uuid,
+    pub name: String,
+    pub parent_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryEntryV1 {
+    pub password: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ItemV1 {
+    pub id: Uuid,
+    pub group_id: Option<Uuid>,
+    pub title: String,
+    pub username: String,
+    pub password: String,
+    pub url: String,
+    pub notes: String,
+    pub tags: Vec<String>,
+    pub password_history: Vec<HistoryEntryV1>,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
+}
+
+impl From<PayloadV1> for VaultPayload {
+    fn from(old: PayloadV1) -> Self {
+        Self {
+            meta: VaultMeta {
+                version: old.meta.version,
+                created_at: old.meta.created_at,
+                modified_at: old.meta.modified_at,
+                ..VaultMeta::default()
+            },
+            groups: old.groups.into_iter().map(Group::from).collect(),
+            items: old.items.into_iter().map(Item::from).collect(),
+        }
+    }
+}
+
+impl From<GroupV1> for Group {
+    fn from(old: GroupV1) -> Self {
+        Self {
+            id: old.id,
+            created_at: old.created_at,
+            ..Group::new(old.name, old.parent_id)
+        }
+    }
+}
+
+impl From<ItemV1> for Item {
+    fn from(old: ItemV1) -> Self {
+        Self {
+            id: old.id,
+            group_id: old.group_id,
+            username: old.username,
+            password: old.password,
+            url: old.url,
+            notes: old.notes,
+            tags: old.tags,
+            password_history: old
+                .password_history
+                .into_iter()
+                .map(|h| PasswordHistoryEntry {
+                    password: h.password,
+                    changed_at: h.changed_at,
+                    sealed: None,
+                })
+                .collect(),
+            created_at: old.created_at,
+            modified_at: old.modified_at,
+            ..Item::new(old.title, old.group_id)
+        }
+    }
+}
+
+/// The payload before groups had descriptions. Types that have not changed since
+/// are used as they are; freeze them here too before changing them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PayloadV4 {
+    pub meta: VaultMeta,
+    pub groups: Vec<GroupV1>,
+    pub items: Vec<Item>,
+}
+
+impl From<PayloadV4> for VaultPayload {
+    fn from(old: PayloadV4) -> Self {
+        Self {
+            meta: old.meta,
+            groups: old.groups.into_iter().map(Group::from).collect(),
+            items: old.items,
+        }
+    }
+}
+
+/// The payload before vaults could turn off password history.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PayloadV3 {
+    pub meta: MetaV2,
+    pub groups: Vec<GroupV1>,
+    pub items: Vec<Item>,
+}
+
+impl From<PayloadV3> for VaultPayload {
+    fn from(old: PayloadV3) -> Self {
+        Self {
+            meta: old.meta.into(),
+            groups: old.groups.into_iter().map(Group::from).collect(),
+            items: old.items,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetaV2 {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
+    pub auto_lock_secs_override: Option<u64>,
+}
+
+impl From<MetaV2> for VaultMeta {
+    fn from(old: MetaV2) -> Self {
+        Self {
+            version: old.version,
+            created_at: old.created_at,
+            modified_at: old.modified_at,
+            auto_lock_secs_override: old.auto_lock_secs_override,
+            ..VaultMeta::default()
+        }
+    }
+}
+
+/// The payload before items could be marked sensitive.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PayloadV2 {
+    pub meta: MetaV2,
+    pub groups: Vec<GroupV1>,
+    pub items: Vec<ItemV2>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ItemV2 {
+    pub id: Uuid,
+    pub group_id: Option<Uuid>,
+    pub title: String,
+    pub username: String,
+    pub password: String,
+    pub url: String,
+    pub notes: String,
+    pub tags: Vec<String>,
+    pub password_history: Vec<PasswordHistoryEntry>,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
+    pub attachments: Vec<Attachment>,
+    pub embedded: Vec<EmbeddedFile>,
+    pub otp_secret: Option<String>,
+    pub urls: Vec<String>,
+    pub no_clipboard_clear: bool,
+    pub notes_secret: bool,
+    pub kind: ItemKind,
+    pub launch_command: Option<String>,
+}
+
+impl From<PayloadV2> for VaultPayload {
+    fn from(old: PayloadV2) -> Self {
+        Self {
+            meta: old.meta.into(),
+            groups: old.groups.into_iter().map(Group::from).collect(),
+            items: old.items.into_iter().map(Item::from).collect(),
+        }
+    }
+}
+
+impl From<ItemV2> for Item {
+    fn from(old: ItemV2) -> Self {
+        Self {
+            id: old.id,
+            group_id: old.group_id,
+            username: old.username,
+            password: old.password,
+            url: old.url,
+            notes: old.notes,
+            tags: old.tags,
+            password_history: old.password_history,
+            created_at: old.created_at,
+            modified_at: old.modified_at,
+            attachments: old.attachments,
+            embedded: old.embedded,
+            otp_secret: old.otp_secret,
+            urls: old.urls,
+            no_clipboard_clear: old.no_clipboard_clear,
+            notes_secret: old.notes_secret,
+            kind: old.kind,
+            launch_command: old.launch_command,
+            ..Item::new(old.title, old.group_id)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::format::VERSION_PADDED;
+
+    fn old_payload() -> PayloadV1 {
+        let now = Utc::now();
+        let group = GroupV1 {
+            id: Uuid::new_v4(),
+            name: "Work".to_string(),
+            parent_id: None,
+            created_at: now,
+        };
+        let item = ItemV1 {
+            id: Uuid::new_v4(),
+            group_id: Some(group.id),
+            title: "Mail".to_string(),
+            username: "me".to_string(),
+            password: "hunter2".to_string(),
+            url: "mail.example.com".to_string(),
+            notes: "old notes".to_string(),
+            tags: vec!["email".to_string()],
+            password_history: vec![HistoryEntryV1 {
+                password: "hunter1".to_string(),
+                changed_at: now,
+            }],
+            created_at: now,
+            modified_at: now,
+        };
+        PayloadV1 {
+            meta: MetaV1 {
+                version: 1,
+                created_at: now,
+                modified_at: now,
+            },
+            groups: vec![group],
+            items: vec![item],
+        }
+    }
+
+    #[test]
+    fn test_old_shape_decodes_through_fallback() {
+        let old = old_payload();
+        let bytes = bincode::serialize(&old).unwrap();
+        assert!(bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .reject_trailing_bytes()
+            .deserialize::<VaultPayload>(&bytes)
+            .is_err());
+
+        let payload = decode_payload(&bytes, VERSION).unwrap();
+        assert_eq!(payload.meta.created_at, old.meta.created_at);
+        assert_eq!(payload.groups[0].name, "Work");
+        let item = &payload.items[0];
+        assert_eq!(item.id, old.items[0].id);
+        assert_eq!(item.group_id, Some(payload.groups[0].id));
+        assert_eq!(item.title, "Mail");
+        assert_eq!(item.password, "hunter2");
+        assert_eq!(item.password_history[0].password, "hunter1");
+        assert!(item.attachments.is_empty());
+        assert_eq!(item.launch_command, None);
+
+        // Padded files were never written in the old shape.
+        assert!(decode_payload(&bytes, VERSION_PADDED).is_err());
+    }
+
+    #[test]
+    fn test_missing_urls_default_to_empty() {
+        // The first-release shape predates extra URLs.
+        let bytes = bincode::serialize(&old_payload()).unwrap();
+        let payload = decode_payload(&bytes, VERSION).unwrap();
+        let item = &payload.items[0];
+        assert!(item.urls.is_empty());
+        assert_eq!(item.all_urls(), ["mail.example.com"]);
+    }
+
+    #[test]
+    fn test_v2_shape_decodes_padded_or_not() {
+        let mut item = Item::new("Box".to_string(), None);
+        item.launch_command = Some("ssh box".to_string());
+        item.no_clipboard_clear = true;
+        let now = Utc::now();
+        let old = PayloadV2 {
+            meta: MetaV2 {
+                version: 1,
+                created_at: now,
+                modified_at: now,
+                auto_lock_secs_override: Some(60),
+            },
+            groups: Vec::new(),
+            items: vec![ItemV2 {
+                id: item.id,
+                group_id: None,
+                title: item.title.clone(),
+                username: String::new(),
+                password: "pw".to_string(),
+                url: String::new(),
+                notes: String::new(),
+                tags: Vec::new(),
+                password_history: Vec::new(),
+                created_at: item.created_at,
+                modified_at: item.modified_at,
+                attachments: Vec::new(),
+                embedded: Vec::new(),
+                otp_secret: None,
+                urls: Vec::new(),
+                no_clipboard_clear: true,
+                notes_secret: false,
+                kind: ItemKind::Login,
+                launch_command: item.launch_command.clone(),
+            }],
+        };
+        let bytes = bincode::serialize(&old).unwrap();
+        for version in [VERSION, VERSION_PADDED] {
+            let payload = decode_payload(&bytes, version).unwrap();
+            let decoded = &payload.items[0];
+            assert_eq!(decoded.launch_command, item.launch_command);
+            assert!(decoded.no_clipboard_clear);
+            assert!(!decoded.sensitive);
+            assert_eq!(payload.meta.auto_lock_secs_override, Some(60));
+            assert!(payload.meta.record_password_history);
+        }
+    }
+
+    #[test]
+    fn test_v4_shape_decodes_without_group_descriptions() {
+        let now = Utc::now();
+        let group = GroupV1 {
+            id: Uuid::new_v4(),
+            name: "Work".to_string(),
+            parent_id: None,
+            created_at: now,
+        };
+        let mut item = Item::new("Mail".to_string(), Some(group.id));
+        item.sensitive = true;
+        let old = PayloadV4 {
+            meta: VaultMeta {
+                record_password_history: false,
+                ..VaultMeta::default()
+            },
+            groups: vec![group.clone()],
+            items: vec![item.clone()],
+        };
+        let bytes = bincode::serialize(&old).unwrap();
+        let payload = decode_payload(&bytes, VERSION_PADDED).unwrap();
+        assert_eq!(payload.groups[0].id, group.id);
+        assert_eq!(payload.groups[0].name, "Work");
+        assert_eq!(payload.groups[0].description, "");
+        assert_eq!(payload.items, [item]);
+        assert!(!payload.meta.record_password_history);
+    }
+
+    #[test]
+    fn test_current_shape_decodes_directly() {
+        let mut payload = VaultPayload::default();
+        let mut group = Group::new("Work".to_string(), None);
+        group.description = "Office accounts".to_string();
+        payload.groups.push(group);
+        let mut item = Item::new("Mail".to_string(), None);
+        item.launch_command = Some("mutt".to_string());
+        payload.items.push(item);
+        let bytes = bincode::serialize(&payload).unwrap();
+        assert_eq!(decode_payload(&bytes, VERSION).unwrap(), payload);
+    }
+}
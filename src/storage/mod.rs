@@ -1,2 +1,9 @@
+pub mod archive;
+pub mod atomic;
 pub mod format;
+#[cfg(feature = "kdbx")]
+pub mod kdbx;
+pub mod legacy;
+pub mod mount;
+pub mod sealed;
 pub mod vault_file;
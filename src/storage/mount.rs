@@ -0,0 +1,155 @@
+//! Best-effort detection of vaults stored on network or removable filesystems.
+
+use std::path::{Path, PathBuf};
+
+/// Filesystem types (from the mount table) that live on another machine.
+const NETWORK_FS_TYPES: [&str; 12] = [
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smbfs",
+    "smb3",
+    "9p",
+    "afs",
+    "ncpfs",
+    "davfs",
+    "fuse.sshfs",
+    "fuse.rclone",
+    "fuse.s3fs",
+];
+
+/// Path prefixes under which desktops mount removable media.
+const REMOVABLE_PREFIXES: [&str; 3] = ["/media/", "/run/media/", "/Volumes/"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MountKind {
+    /// A network filesystem of the given type, or a UNC share on Windows.
+    Network(String),
+    Removable,
+}
+
+impl MountKind {
+    pub fn warning(&self) -> String {
+        match self {
+            MountKind::Network(fs_type) => format!(
+                "Vault is on a network filesystem ({fs_type}); keep it on local storage to avoid corruption"
+            ),
+            MountKind::Removable => {
+                "Vault is on removable media; keep it on local storage to avoid corruption"
+                    .to_string()
+            }
+        }
+    }
+}
+
+/// Classify the filesystem holding `path`, or `None` if it looks local.
+pub fn detect(path: &Path) -> Option<MountKind> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let mounts = std::fs::read_to_string("/proc/mounts").unwrap_or_default();
+    classify(&path, &mounts)
+}
+
+/// Classify `path` against a mount table in `/proc/mounts` format.
+pub fn classify(path: &Path, mounts: &str) -> Option<MountKind> {
+    let text = path.to_string_lossy();
+    if text.starts_with(r"\\") || text.starts_with("//") {
+        return Some(MountKind::Network("share".to_string()));
+    }
+
+    let fs_type = mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            let mount_point = PathBuf::from(unescape(fields.next()?));
+            let fs_type = fields.next()?;
+            path.starts_with(&mount_point)
+                .then_some((mount_point.components().count(), fs_type))
+        })
+        .max_by_key(|(depth, _)| *depth)
+        .map(|(_, fs_type)| fs_type);
+    if let Some(fs_type) = fs_type.filter(|t| NETWORK_FS_TYPES.contains(t)) {
+        return Some(MountKind::Network(fs_type.to_string()));
+    }
+
+    REMOVABLE_PREFIXES
+        .iter()
+        .any(|prefix| text.starts_with(prefix))
+        .then_some(MountKind::Removable)
+}
+
+/// Mount points in the table escape spaces and similar characters as octal (`\040`).
+fn unescape(field: &str) -> String {
+    let mut out = String::with_capacity(field.len());
+    let mut rest = field;
+    while let Some(idx) = rest.find('\\') {
+        out.push_str(&rest[..idx]);
+        let code = rest.get(idx + 1..idx + 4);
+        match code.and_then(|c| u8::from_str_radix(c, 8).ok()) {
+            Some(byte) => {
+                out.push(byte as char);
+                rest = &rest[idx + 4..];
+            }
+            None => {
+                out.push('\\');
+                rest = &rest[idx + 1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOUNTS: &str = "\
+/dev/sda1 / ext4 rw,relatime 0 0
+server:/export/home /home/alice/nfs nfs4 rw 0 0
+//nas/share /mnt/nas cifs rw 0 0
+/dev/sdb1 /home/alice/local\\040disk ext4 rw 0 0
+alice@host:/ /home/alice/remote fuse.sshfs rw 0 0
+";
+
+    fn classify_str(path: &str) -> Option<MountKind> {
+        classify(Path::new(path), MOUNTS)
+    }
+
+    #[test]
+    fn test_network_mounts_detected() {
+        assert_eq!(
+            classify_str("/home/alice/nfs/vault.vltr"),
+            Some(MountKind::Network("nfs4".to_string()))
+        );
+        assert_eq!(
+            classify_str("/mnt/nas/vault.vltr"),
+            Some(MountKind::Network("cifs".to_string()))
+        );
+        assert_eq!(
+            classify_str("/home/alice/remote/v.vltr"),
+            Some(MountKind::Network("fuse.sshfs".to_string()))
+        );
+        assert_eq!(
+            classify(Path::new(r"\\server\share\vault.vltr"), ""),
+            Some(MountKind::Network("share".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_local_and_removable_paths() {
+        assert_eq!(classify_str("/home/alice/.local/share/vault.vltr"), None);
+        // The deepest mount point wins, and escaped spaces are decoded.
+        assert_eq!(classify_str("/home/alice/local disk/vault.vltr"), None);
+        // Sibling directories sharing a name prefix are not inside the mount.
+        assert_eq!(classify_str("/home/alice/nfsbackup/vault.vltr"), None);
+        assert_eq!(
+            classify_str("/media/alice/USB/vault.vltr"),
+            Some(MountKind::Removable)
+        );
+        assert_eq!(
+            classify(Path::new("/Volumes/Stick/vault.vltr"), ""),
+            Some(MountKind::Removable)
+        );
+    }
+}
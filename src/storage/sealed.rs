@@ -0,0 +1,75 @@
+use std::fs;
+use std::path::Path;
+
+use secrecy::SecretBox;
+
+use crate::crypto::aead;
+use crate::error::{Result, VaulturaError};
+use crate::storage::atomic;
+use crate::storage::format::{NONCE_LENGTH, SEALED_MAGIC, SEALED_VERSION, VERSION};
+
+/// Header: magic(4) + version(4) + payload version(4) + threshold(1).
+const HEADER_LENGTH: usize = 4 + 4 + 4 + 1;
+
+/// Encrypt a vault payload under a data key held only as shares:
+/// `[VLTS magic 4B][version u32 LE][payload version u32 LE][threshold u8][nonce 24B][ciphertext]`.
+///
+/// The payload version is the vault file format the plaintext was encoded for, so an
+/// import decodes it with the same fallbacks as a vault file of that version.
+pub fn write_sealed(
+    path: &Path,
+    key: &SecretBox<Vec<u8>>,
+    threshold: u8,
+    plaintext: &[u8],
+) -> Result<()> {
+    let (nonce, ciphertext) = aead::encrypt(key, plaintext)?;
+
+    let mut data = Vec::with_capacity(HEADER_LENGTH + nonce.len() + ciphertext.len());
+    data.extend_from_slice(SEALED_MAGIC);
+    data.extend_from_slice(&SEALED_VERSION.to_le_bytes());
+    data.extend_from_slice(&VERSION.to_le_bytes());
+    data.push(threshold);
+    data.extend_from_slice(&nonce);
+    data.extend_from_slice(&ciphertext);
+    atomic::write(path, &data)
+}
+
+/// The number of shares needed to open a sealed export.
+pub fn read_threshold(path: &Path) -> Result<u8> {
+    let data = fs::read(path)?;
+    check_header(&data)
+}
+
+/// Decrypt a sealed export with the data key recombined from its shares, returning
+/// the plaintext and the payload version it was written with.
+pub fn read_sealed(path: &Path, key: &SecretBox<Vec<u8>>) -> Result<(Vec<u8>, u32)> {
+    let data = fs::read(path)?;
+    check_header(&data)?;
+    let payload_version = u32::from_le_bytes(data[8..12].try_into().unwrap());
+    let nonce = &data[HEADER_LENGTH..HEADER_LENGTH + NONCE_LENGTH];
+    let ciphertext = &data[HEADER_LENGTH + NONCE_LENGTH..];
+    let plaintext = aead::decrypt(key, nonce, ciphertext)
+        .map_err(|_| VaulturaError::Decryption("the shares do not open this export".to_string()))?;
+    Ok((plaintext, payload_version))
+}
+
+/// Validate the header, returning the threshold it records.
+fn check_header(data: &[u8]) -> Result<u8> {
+    if data.len() < HEADER_LENGTH + NONCE_LENGTH + 1 {
+        return Err(VaulturaError::InvalidVaultFile {
+            reason: "File too small".to_string(),
+        });
+    }
+    if &data[0..4] != SEALED_MAGIC {
+        return Err(VaulturaError::InvalidVaultFile {
+            reason: "Invalid sealed export magic bytes".to_string(),
+        });
+    }
+    let version = u32::from_le_bytes(data[4..8].try_into().unwrap());
+    if version != SEALED_VERSION {
+        return Err(VaulturaError::InvalidVaultFile {
+            reason: format!("Unsupported sealed export version: {version}"),
+        });
+    }
+    Ok(data[12])
+}
@@ -1,13 +1,14 @@
 use std::fs;
-use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::core::models::{KdfParams, VaultPayload};
 use crate::crypto::{aead, kdf};
 use crate::error::{Result, VaulturaError};
 use crate::storage::format::{
-    KDF_PARAMS_LENGTH, MAGIC, MIN_FILE_SIZE, NONCE_LENGTH, SALT_LENGTH, VERSION,
+    KDF_PARAMS_LENGTH, MAGIC, MIN_FILE_SIZE, NONCE_LENGTH, PADDING_LENGTH_PREFIX, SALT_LENGTH,
+    VERSION, VERSION_PADDED,
 };
+use crate::storage::{atomic, legacy};
 
 /// Create a new vault file at `path` with the given master password.
 pub fn create_vault(path: &Path, password: &str, kdf_params: &KdfParams) -> Result<()> {
@@ -22,21 +23,162 @@ pub fn write_vault(
     kdf_params: &KdfParams,
     payload: &VaultPayload,
 ) -> Result<()> {
+    write_vault_padded(path, password, kdf_params, payload, 0)
+}
+
+/// Write like `write_vault`, padding the plaintext up to a multiple of `pad_to_block`
+/// bytes so the file size only reveals the vault's rough magnitude (0 = no padding).
+pub fn write_vault_padded(
+    path: &Path,
+    password: &str,
+    kdf_params: &KdfParams,
+    payload: &VaultPayload,
+    pad_to_block: usize,
+) -> Result<()> {
+    let mut plaintext = bincode::serialize(payload)?;
+    let version = if pad_to_block > 0 {
+        plaintext = pad(plaintext, pad_to_block);
+        VERSION_PADDED
+    } else {
+        VERSION
+    };
+
+    let mut header = Vec::new();
+    header.extend_from_slice(MAGIC);
+    header.extend_from_slice(&version.to_le_bytes());
+
+    let data = seal(header, password, kdf_params, &plaintext)?;
+    atomic::write(path, &data)
+}
+
+/// Prefix `plaintext` with its length and zero-fill it to the next multiple of `block`.
+fn pad(plaintext: Vec<u8>, block: usize) -> Vec<u8> {
+    let used = PADDING_LENGTH_PREFIX + plaintext.len();
+    let padded_len = used.div_ceil(block) * block;
+    let mut padded = Vec::with_capacity(padded_len);
+    padded.extend_from_slice(&(plaintext.len() as u64).to_le_bytes());
+    padded.extend_from_slice(&plaintext);
+    padded.resize(padded_len, 0);
+    padded
+}
+
+/// Strip the length prefix and padding added by `pad`.
+fn unpad(padded: &[u8]) -> Result<&[u8]> {
+    let invalid = || VaulturaError::InvalidVaultFile {
+        reason: "Invalid padding".to_string(),
+    };
+    let prefix = padded.get(..PADDING_LENGTH_PREFIX).ok_or_else(invalid)?;
+    let len = u64::from_le_bytes(prefix.try_into().unwrap());
+    let end = usize::try_from(len)
+        .ok()
+        .and_then(|len| len.checked_add(PADDING_LENGTH_PREFIX))
+        .ok_or_else(invalid)?;
+    padded.get(PADDING_LENGTH_PREFIX..end).ok_or_else(invalid)
+}
+
+/// Write like `write_vault`, then re-read and decrypt the new file to check it is intact.
+///
+/// The previous file is copied to `backup_path(path)` first and removed once the new one
+/// verifies. If verification fails the backup is kept and an integrity error is returned.
+pub fn write_vault_verified(
+    path: &Path,
+    password: &str,
+    kdf_params: &KdfParams,
+    payload: &VaultPayload,
+    pad_to_block: usize,
+) -> Result<()> {
+    let backup = backup_path(path);
+    let had_previous = path.exists();
+    if had_previous {
+        fs::copy(path, &backup)?;
+    }
+
+    write_vault_padded(path, password, kdf_params, payload, pad_to_block)?;
+
+    match verify_vault(path, password, payload) {
+        Ok(()) => {
+            if had_previous {
+                fs::remove_file(&backup)?;
+            }
+            Ok(())
+        }
+        Err(e) if had_previous => Err(VaulturaError::Integrity(format!(
+            "{} did not verify after writing ({e}); previous version kept at {}",
+            path.display(),
+            backup.display()
+        ))),
+        Err(e) => Err(VaulturaError::Integrity(format!(
+            "{} did not verify after writing ({e})",
+            path.display()
+        ))),
+    }
+}
+
+/// Check that the vault at `path` decrypts to exactly `expected`.
+pub fn verify_vault(path: &Path, password: &str, expected: &VaultPayload) -> Result<()> {
+    let (payload, _) = read_vault(path, password)?;
+    if payload != *expected {
+        return Err(VaulturaError::Integrity(
+            "contents differ from what was written".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Where `write_vault_verified` keeps the previous vault: `<vault>.bak`.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+/// Append `[salt][kdf_params][nonce][ciphertext]` for `plaintext` to a caller-written header.
+pub(crate) fn seal(
+    mut data: Vec<u8>,
+    password: &str,
+    kdf_params: &KdfParams,
+    plaintext: &[u8],
+) -> Result<Vec<u8>> {
     let salt = kdf::generate_salt(SALT_LENGTH);
     let key = kdf::derive_key(password, &salt, kdf_params)?;
+    let (nonce, ciphertext) = aead::encrypt(&key, plaintext)?;
 
-    let plaintext = bincode::serialize(payload)?;
-    let (nonce, ciphertext) = aead::encrypt(&key, &plaintext)?;
-
-    let mut data = Vec::new();
-    data.extend_from_slice(MAGIC);
-    data.extend_from_slice(&VERSION.to_le_bytes());
     data.extend_from_slice(&salt);
     write_kdf_params(&mut data, kdf_params);
     data.extend_from_slice(&nonce);
     data.extend_from_slice(&ciphertext);
+    Ok(data)
+}
+
+/// Decrypt the `[salt][kdf_params][nonce][ciphertext]` body that follows a header.
+pub(crate) fn unseal(body: &[u8], password: &str) -> Result<(Vec<u8>, KdfParams)> {
+    if body.len() < SALT_LENGTH + KDF_PARAMS_LENGTH + NONCE_LENGTH + 1 {
+        return Err(VaulturaError::InvalidVaultFile {
+            reason: "File too small".to_string(),
+        });
+    }
+
+    let mut offset = 0;
+
+    // Salt
+    let salt = &body[offset..offset + SALT_LENGTH];
+    offset += SALT_LENGTH;
+
+    // KDF params
+    let kdf_params = read_kdf_params(&body[offset..offset + KDF_PARAMS_LENGTH]);
+    offset += KDF_PARAMS_LENGTH;
+
+    // Nonce
+    let nonce = &body[offset..offset + NONCE_LENGTH];
+    offset += NONCE_LENGTH;
+
+    // Ciphertext
+    let ciphertext = &body[offset..];
 
-    atomic_write(path, &data)
+    let key = kdf::derive_key(password, salt, &kdf_params)?;
+    let plaintext =
+        aead::decrypt(&key, nonce, ciphertext).map_err(|_| VaulturaError::WrongPassword)?;
+    Ok((plaintext, kdf_params))
 }
 
 /// Read and decrypt a vault file, returning the payload.
@@ -61,33 +203,21 @@ pub fn read_vault(path: &Path, password: &str) -> Result<(VaultPayload, KdfParam
 
     // Version
     let version = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
-    if version != VERSION {
+    if version != VERSION && version != VERSION_PADDED {
         return Err(VaulturaError::InvalidVaultFile {
             reason: format!("Unsupported version: {version}"),
         });
     }
     offset += 4;
 
-    // Salt
-    let salt = &data[offset..offset + SALT_LENGTH];
-    offset += SALT_LENGTH;
-
-    // KDF params
-    let kdf_params = read_kdf_params(&data[offset..offset + KDF_PARAMS_LENGTH]);
-    offset += KDF_PARAMS_LENGTH;
-
-    // Nonce
-    let nonce = &data[offset..offset + NONCE_LENGTH];
-    offset += NONCE_LENGTH;
-
-    // Ciphertext
-    let ciphertext = &data[offset..];
-
-    let key = kdf::derive_key(password, salt, &kdf_params)?;
-    let plaintext =
-        aead::decrypt(&key, nonce, ciphertext).map_err(|_| VaulturaError::WrongPassword)?;
+    let (plaintext, kdf_params) = unseal(&data[offset..], password)?;
+    let plaintext = if version == VERSION_PADDED {
+        unpad(&plaintext)?
+    } else {
+        &plaintext
+    };
 
-    let payload: VaultPayload = bincode::deserialize(&plaintext)?;
+    let payload = legacy::decode_payload(plaintext, version)?;
     Ok((payload, kdf_params))
 }
 
@@ -108,7 +238,7 @@ pub fn read_vault_header(path: &Path) -> Result<(Vec<u8>, KdfParams)> {
     }
 
     let version = u32::from_le_bytes(data[4..8].try_into().unwrap());
-    if version != VERSION {
+    if version != VERSION && version != VERSION_PADDED {
         return Err(VaulturaError::InvalidVaultFile {
             reason: format!("Unsupported version: {version}"),
         });
@@ -133,19 +263,6 @@ fn read_kdf_params(data: &[u8]) -> KdfParams {
     }
 }
 
-fn atomic_write(path: &Path, data: &[u8]) -> Result<()> {
-    let parent = path.parent().unwrap_or(Path::new("."));
-    let temp_path = parent.join(format!(".vaultura_tmp_{}", std::process::id()));
-
-    let mut file = fs::File::create(&temp_path)?;
-    file.write_all(data)?;
-    file.sync_all()?;
-    drop(file);
-
-    fs::rename(&temp_path, path)?;
-    Ok(())
-}
-
 /// Export vault: re-encrypts current payload with a different password.
 pub fn export_vault(
     path: &Path,
@@ -272,4 +389,144 @@ mod tests {
         let imported = import_vault(&export_path, "pass2").unwrap();
         assert_eq!(imported, payload);
     }
+
+    #[test]
+    fn test_verified_write_succeeds_and_removes_backup() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("test.vault");
+        let params = test_params();
+        create_vault(&path, "password", &params).unwrap();
+
+        let mut payload = VaultPayload::default();
+        payload
+            .items
+            .push(crate::core::models::Item::new("Login".to_string(), None));
+        write_vault_verified(&path, "password", &params, &payload, 0).unwrap();
+
+        assert!(!backup_path(&path).exists());
+        verify_vault(&path, "password", &payload).unwrap();
+    }
+
+    #[test]
+    fn test_corruption_after_write_is_detected() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("test.vault");
+        let params = test_params();
+        let payload = VaultPayload::default();
+        write_vault_verified(&path, "password", &params, &payload, 0).unwrap();
+
+        let mut data = fs::read(&path).unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 0xff;
+        fs::write(&path, &data).unwrap();
+        assert!(verify_vault(&path, "password", &payload).is_err());
+
+        let mut other = VaultPayload::default();
+        other
+            .groups
+            .push(crate::core::models::Group::new("G".to_string(), None));
+        write_vault(&path, "password", &params, &other).unwrap();
+        assert!(matches!(
+            verify_vault(&path, "password", &payload),
+            Err(VaulturaError::Integrity(_))
+        ));
+    }
+
+    fn payload_with(titles: &[&str]) -> VaultPayload {
+        let mut payload = VaultPayload::default();
+        for title in titles {
+            payload
+                .items
+                .push(crate::core::models::Item::new(title.to_string(), None));
+        }
+        payload
+    }
+
+    #[test]
+    fn test_padded_vault_round_trips() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("test.vault");
+        let params = test_params();
+        let payload = payload_with(&["Login", "Bank"]);
+
+        write_vault_padded(&path, "password", &params, &payload, 4096).unwrap();
+        let (read, _) = read_vault(&path, "password").unwrap();
+        assert_eq!(read, payload);
+        assert!(read_vault_header(&path).is_ok());
+    }
+
+    #[test]
+    fn test_padding_hides_small_size_differences() {
+        let dir = TempDir::new().unwrap();
+        let params = test_params();
+        let small = dir.path().join("small.vault");
+        let larger = dir.path().join("larger.vault");
+
+        write_vault_padded(&small, "password", &params, &payload_with(&["a"]), 4096).unwrap();
+        write_vault_padded(
+            &larger,
+            "password",
+            &params,
+            &payload_with(&["a", "b"]),
+            4096,
+        )
+        .unwrap();
+        let size = |path: &Path| fs::metadata(path).unwrap().len();
+        assert_eq!(size(&small), size(&larger));
+
+        // Without padding the extra item shows in the file size.
+        write_vault(&small, "password", &params, &payload_with(&["a"])).unwrap();
+        write_vault(&larger, "password", &params, &payload_with(&["a", "b"])).unwrap();
+        assert!(size(&small) < size(&larger));
+    }
+
+    #[test]
+    fn test_unpad_rejects_bad_length() {
+        let padded = pad(b"hello".to_vec(), 64);
+        assert_eq!(padded.len(), 64);
+        assert_eq!(unpad(&padded).unwrap(), b"hello");
+
+        let mut bad = padded.clone();
+        bad[..8].copy_from_slice(&1000u64.to_le_bytes());
+        assert!(unpad(&bad).is_err());
+        assert!(unpad(&padded[..4]).is_err());
+    }
+
+    #[test]
+    fn test_backup_path_appends_suffix() {
+        assert_eq!(
+            backup_path(Path::new("/tmp/vault.vltr")),
+            PathBuf::from("/tmp/vault.vltr.bak")
+        );
+    }
+
+    #[test]
+    fn test_old_payload_shape_still_opens() {
+        use crate::storage::legacy::{MetaV1, PayloadV1};
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("old.vault");
+        let now = chrono::Utc::now();
+        let old = PayloadV1 {
+            meta: MetaV1 {
+                version: 1,
+                created_at: now,
+                modified_at: now,
+            },
+            groups: Vec::new(),
+            items: Vec::new(),
+        };
+        let mut header = MAGIC.to_vec();
+        header.extend_from_slice(&VERSION.to_le_bytes());
+        let plaintext = bincode::serialize(&old).unwrap();
+        let data = seal(header, "password", &test_params(), &plaintext).unwrap();
+        fs::write(&path, data).unwrap();
+
+        let (payload, _) = read_vault(&path, "password").unwrap();
+        assert_eq!(payload.meta.created_at, now);
+
+        // Saving writes the current shape, which then decodes directly.
+        write_vault(&path, "password", &test_params(), &payload).unwrap();
+        assert_eq!(read_vault(&path, "password").unwrap().0, payload);
+    }
 }
@@ -1,20 +1,49 @@
+use std::collections::HashMap;
 use std::io;
-use std::time::{Duration, Instant};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, TryRecvError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crossterm::event::{self, Event};
+use crossterm::event::{self, Event, KeyCode};
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Paragraph, Wrap};
 use ratatui::Frame;
 use uuid::Uuid;
+use zeroize::Zeroizing;
 
-use crate::clipboard::ClipboardManager;
-use crate::config::AppConfig;
-use crate::core::vault_service::VaultService;
+use crate::clipboard::{ClipboardBackend, ClipboardManager, ClipboardStatus, SystemClipboard};
+use crate::config::{AppConfig, FilterScope, SavedFilter};
+use crate::core::launch::LaunchCommand;
+use crate::core::models::{Group, Item, ItemKind};
+use crate::core::qr::{self, QrField};
+use crate::core::vault_service::{
+    self, CompactOptions, ImportData, ImportJob, ImportMode, ItemDraft, VaultService,
+};
+use crate::core::{opener, password_command, template, totp};
+use crate::error::VaulturaError;
+use crate::storage::mount;
+use crate::ui::copy_log::{CopyLog, COPY_LOG_CAPACITY};
+use crate::ui::event_log::{EventLog, EVENT_LOG_CAPACITY};
+use crate::ui::modals::changes_view::ChangesView;
 use crate::ui::modals::confirm_dialog::ConfirmDialog;
+use crate::ui::modals::copy_log_view::CopyLogView;
+use crate::ui::modals::event_log_view::EventLogView;
+use crate::ui::modals::filter_picker::FilterPicker;
 use crate::ui::modals::group_form::GroupForm;
+use crate::ui::modals::import_form::ImportForm;
 use crate::ui::modals::item_form::ItemForm;
 use crate::ui::modals::password_generator_modal::PasswordGeneratorModal;
+use crate::ui::modals::password_prompt::PasswordPrompt;
+#[cfg(feature = "qr")]
+use crate::ui::modals::qr_modal::QrModal;
+use crate::ui::modals::vault_info_view::VaultInfoView;
+use crate::ui::modals::vault_settings::VaultSettings;
+use crate::ui::panels::items_panel::ItemsPanel;
 use crate::ui::screens::lock_screen::LockScreen;
-use crate::ui::screens::main_screen::MainScreen;
-use crate::ui::{Action, Component};
+use crate::ui::screens::main_screen::{MainScreen, StatusLevel};
+use crate::ui::text::has_edge_whitespace;
+use crate::ui::{theme, Action, Component};
 
 const TICK_RATE: Duration = Duration::from_millis(250);
 
@@ -30,61 +59,287 @@ enum Modal {
     GroupForm(GroupForm),
     Confirm(ConfirmDialog),
     PasswordGenerator(PasswordGeneratorModal),
+    PasswordPrompt(PasswordPrompt),
+    FilterPicker(FilterPicker),
+    VaultSettings(VaultSettings),
+    EventLog(EventLogView),
+    CopyLog(CopyLogView),
+    Changes(ChangesView),
+    VaultInfo(VaultInfoView),
+    Import(ImportForm),
+    #[cfg(feature = "qr")]
+    Qr(QrModal),
 }
 
 pub struct App {
     vault_service: VaultService,
-    clipboard: ClipboardManager,
+    clipboard: ClipboardManager<Box<dyn ClipboardBackend>>,
+    /// Probed once at startup; any warning is shown when the vault opens.
+    clipboard_status: ClipboardStatus,
     config: AppConfig,
+    /// Where config changes made from the UI are persisted; `None` keeps them in memory.
+    config_path: Option<PathBuf>,
+    /// Why the config could not be saved at startup, shown when the vault opens.
+    config_warning: Option<String>,
     lock_screen: LockScreen,
     main_screen: MainScreen,
     current_screen: Screen,
     modal: Modal,
     /// Stashed item form while the password generator is open on top of it.
     stashed_item_form: Option<ItemForm>,
+    /// Stashed group form while "Discard changes?" is asked over it.
+    stashed_group_form: Option<GroupForm>,
     running: bool,
     last_activity: Instant,
+    /// When auto-lock asked whether to save; cleared by any key press.
+    autolock_prompt_at: Option<Instant>,
+    /// Username already copied; the password follows on the next press or after the delay.
+    credential_sequence: Option<CredentialSequence>,
+    /// When the master password was last re-entered for a protected action.
+    reauth_at: Option<Instant>,
+    /// Consecutive failed re-authentication attempts since the last success or unlock.
+    reauth_failures: u32,
+    /// Last deleted item, restorable with `u` until the undo window closes.
+    deleted_item: Option<DeletedItem>,
+    /// Recent status messages, kept after they leave the status bar.
+    event_log: EventLog,
+    /// What was copied this session, without the values; emptied on lock.
+    copy_log: CopyLog,
+    /// Passwords yanked into named registers with `"<letter>y`, with the title of the
+    /// item each came from; emptied on lock.
+    registers: HashMap<char, (String, Zeroizing<String>)>,
+    /// Set while a `ConfirmedSensitive` action runs, so it is not asked about again.
+    sensitive_confirmed: bool,
+    /// Set while a `ConfirmedCopy` action runs, so its copy is not asked about again.
+    copy_confirmed: bool,
+    /// The import in progress; only Esc reaches the app until it ends.
+    import: Option<ImportTask>,
+}
+
+/// Items merged per tick while an import runs, between which keys are handled.
+const IMPORT_BATCH: usize = 200;
+
+/// An import under way: the file is decrypted and parsed on a worker thread, then
+/// merged a batch per tick so the UI keeps drawing and Esc can cancel it.
+enum ImportTask {
+    Reading(mpsc::Receiver<crate::error::Result<ImportData>>),
+    Merging(Box<ImportJob>),
+}
+
+/// Seconds after a delete during which `u` restores the item.
+const UNDO_DELETE_SECS: u64 = 5;
+
+struct DeletedItem {
+    item: Item,
+    deleted_at: Instant,
+}
+
+impl DeletedItem {
+    fn undoable(&self, now: Instant) -> bool {
+        now.duration_since(self.deleted_at).as_secs() < UNDO_DELETE_SECS
+    }
+}
+
+/// Seconds the auto-lock save prompt waits for an answer before saving and locking.
+const AUTOLOCK_PROMPT_SECS: u64 = 30;
+
+/// What the auto-lock check does on a tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AutoLockStep {
+    Wait,
+    /// Ask whether to save before locking.
+    Prompt,
+    Lock,
+}
+
+/// Decide the auto-lock step after `idle` without input, given how long the save prompt
+/// has been open (`None` if it has not been shown).
+fn auto_lock_step(
+    idle: Duration,
+    lock_after_secs: u64,
+    confirm_save: bool,
+    dirty: bool,
+    prompt_open_for: Option<Duration>,
+) -> AutoLockStep {
+    if lock_after_secs == 0 || idle <= Duration::from_secs(lock_after_secs) {
+        return AutoLockStep::Wait;
+    }
+    if !(confirm_save && dirty) {
+        return AutoLockStep::Lock;
+    }
+    match prompt_open_for {
+        None => AutoLockStep::Prompt,
+        Some(open) if open >= Duration::from_secs(AUTOLOCK_PROMPT_SECS) => AutoLockStep::Lock,
+        Some(_) => AutoLockStep::Wait,
+    }
+}
+
+struct CredentialSequence {
+    item_id: Uuid,
+    username_copied_at: Instant,
+}
+
+impl CredentialSequence {
+    /// Whether the configured delay has elapsed and the password should be copied.
+    fn password_due(&self, now: Instant, delay_secs: u64) -> bool {
+        delay_secs > 0 && now.duration_since(self.username_copied_at).as_secs() >= delay_secs
+    }
 }
 
 impl App {
     pub fn new(config: AppConfig) -> Self {
+        Self::with_clipboard(config, Box::new(SystemClipboard))
+    }
+
+    /// `new`, copying through `backend` instead of the system clipboard.
+    fn with_clipboard(config: AppConfig, backend: Box<dyn ClipboardBackend>) -> Self {
         let kdf_params = config.kdf_params();
         let vault_path = config.vault_path.clone();
         let vault_exists = vault_path.exists();
-        let clipboard_secs = config.clipboard_clear_secs;
+        let mut clipboard = ClipboardManager::with_backend(
+            backend,
+            Duration::from_secs(config.clipboard_clear_secs),
+        );
+        clipboard.set_osc52_fallback(config.clipboard_osc52_fallback);
+        clipboard.set_clear_value(config.clipboard_clear_value.clone());
+        clipboard.set_clear_only_if_ours(config.clipboard_clear_only_if_ours);
+        let clipboard_status = clipboard.probe();
+
+        let mut vault_service = VaultService::new(vault_path, kdf_params);
+        vault_service.set_decoy_path(config.decoy_vault_path.clone());
+        vault_service.set_verify_after_write(config.verify_after_write);
+        vault_service.set_pad_to_block(config.pad_to_block);
+        vault_service.set_sanitize_imports(config.sanitize_imports);
+        vault_service.set_backup_before_import(config.backup_before_import);
+        vault_service.set_quotas(config.max_items, config.max_groups);
+        // Nothing is unlocked yet, so there is nothing to seal and this cannot fail.
+        let _ = vault_service.set_seal_passwords(config.seal_passwords_in_memory);
+        let mut lock_screen = LockScreen::new(vault_exists);
+        lock_screen.set_passphrase_words(config.passphrase_words);
+        lock_screen.set_allow_show_password(config.allow_show_master);
 
         Self {
-            vault_service: VaultService::new(vault_path, kdf_params),
-            clipboard: ClipboardManager::new(clipboard_secs),
+            vault_service,
+            clipboard,
+            clipboard_status,
+            main_screen: build_main_screen(&config),
             config,
-            lock_screen: LockScreen::new(vault_exists),
-            main_screen: MainScreen::new(),
+            config_path: None,
+            config_warning: None,
+            lock_screen,
             current_screen: Screen::Lock,
             modal: Modal::None,
             stashed_item_form: None,
+            stashed_group_form: None,
             running: true,
             last_activity: Instant::now(),
+            autolock_prompt_at: None,
+            credential_sequence: None,
+            reauth_at: None,
+            reauth_failures: 0,
+            deleted_item: None,
+            event_log: EventLog::new(EVENT_LOG_CAPACITY),
+            copy_log: CopyLog::new(COPY_LOG_CAPACITY),
+            registers: HashMap::new(),
+            sensitive_confirmed: false,
+            copy_confirmed: false,
+            import: None,
+        }
+    }
+
+    /// Unlock with `master_password_command`, if set, so the vault opens without the
+    /// password prompt. On failure the lock screen shows the error and prompts as usual.
+    pub fn unlock_with_password_command(&mut self) {
+        let Some(command) = self.config.master_password_command.clone() else {
+            return;
+        };
+        if self.current_screen != Screen::Lock || !self.vault_service.vault_path().exists() {
+            return;
+        }
+        match password_command::read_password(&command) {
+            Ok(password) => self.handle_action(Action::UnlockVault(password.to_string())),
+            Err(e) => self
+                .lock_screen
+                .set_error(format!("Password command failed: {e}")),
         }
     }
 
+    /// Record a status event in the log shown by Ctrl+G. Never pass secret values.
+    pub fn log_event(&mut self, level: StatusLevel, text: String) {
+        self.event_log.push(level, text);
+    }
+
+    /// Copy status messages raised since the last call into the event log.
+    fn sync_event_log(&mut self) {
+        for (level, text) in self.main_screen.take_unlogged() {
+            self.log_event(level, text);
+        }
+    }
+
+    pub fn with_config_path(mut self, path: PathBuf) -> Self {
+        self.config_path = Some(path);
+        self
+    }
+
+    pub fn with_config_warning(mut self, warning: String) -> Self {
+        self.config_warning = Some(warning);
+        self
+    }
+
     pub fn run(&mut self, terminal: &mut ratatui::DefaultTerminal) -> io::Result<()> {
         while self.running {
+            self.main_screen.set_dirty(self.vault_service.is_dirty());
+            self.sync_event_log();
             terminal.draw(|frame| self.render(frame))?;
 
             // Expire status messages
             self.main_screen.tick();
 
-            // Auto-lock check
-            if self.current_screen == Screen::Main
-                && self.config.auto_lock_secs > 0
-                && self.last_activity.elapsed() > Duration::from_secs(self.config.auto_lock_secs)
+            let delay = self.config.credential_sequence_delay_secs;
+            if let Some(seq) = &self.credential_sequence {
+                if seq.password_due(Instant::now(), delay) {
+                    let id = seq.item_id;
+                    self.credential_sequence = None;
+                    self.copy_sequence_password(id);
+                }
+            }
+
+            if self
+                .deleted_item
+                .as_ref()
+                .is_some_and(|deleted| !deleted.undoable(Instant::now()))
             {
-                self.handle_action(Action::Lock);
+                self.deleted_item = None;
+            }
+
+            // Auto-lock check
+            if self.current_screen == Screen::Main {
+                let step = auto_lock_step(
+                    self.last_activity.elapsed(),
+                    self.auto_lock_secs(),
+                    self.config.confirm_autolock_save,
+                    self.vault_service.is_dirty(),
+                    self.autolock_prompt_at.map(|at| at.elapsed()),
+                );
+                match step {
+                    AutoLockStep::Wait => {}
+                    AutoLockStep::Prompt => self.open_autolock_prompt(),
+                    AutoLockStep::Lock => self.handle_action(Action::Lock),
+                }
             }
 
-            if event::poll(TICK_RATE)? {
+            self.poll_import();
+
+            // While an import runs, merge the next batch as soon as keys are handled.
+            let timeout = if self.import.is_some() {
+                Duration::ZERO
+            } else {
+                TICK_RATE
+            };
+            if event::poll(timeout)? {
                 if let Event::Key(key) = event::read()? {
                     self.last_activity = Instant::now();
+                    self.autolock_prompt_at = None;
                     let action = self.handle_input(key);
                     self.handle_action(action);
                 }
@@ -96,6 +351,16 @@ impl App {
     fn render(&self, frame: &mut Frame) {
         let area = frame.area();
 
+        // Below this size the panes and centered modals collapse into zero-sized rects.
+        let min_size = (
+            self.config.min_terminal_width,
+            self.config.min_terminal_height,
+        );
+        if !fits_min_size(area, min_size) {
+            render_too_small(frame, area, min_size);
+            return;
+        }
+
         match self.current_screen {
             Screen::Lock => self.lock_screen.render(frame, area),
             Screen::Main => {
@@ -108,12 +373,31 @@ impl App {
                     Modal::GroupForm(form) => form.render(frame, area),
                     Modal::Confirm(dialog) => dialog.render(frame, area),
                     Modal::PasswordGenerator(gen) => gen.render(frame, area),
+                    Modal::PasswordPrompt(prompt) => prompt.render(frame, area),
+                    Modal::FilterPicker(picker) => picker.render(frame, area),
+                    Modal::VaultSettings(settings) => settings.render(frame, area),
+                    Modal::EventLog(view) => view.render(frame, area),
+                    Modal::CopyLog(view) => view.render(frame, area),
+                    #[cfg(feature = "qr")]
+                    Modal::Qr(view) => view.render(frame, area),
+                    Modal::Changes(view) => view.render(frame, area),
+                    Modal::VaultInfo(view) => view.render(frame, area),
+                    Modal::Import(form) => form.render(frame, area),
                 }
             }
         }
     }
 
     fn handle_input(&mut self, key: crossterm::event::KeyEvent) -> Action {
+        // A running import would be undone by cancelling it, so nothing else may change
+        // the vault meanwhile.
+        if self.import.is_some() {
+            return match key.code {
+                KeyCode::Esc => Action::CancelImport,
+                _ => Action::None,
+            };
+        }
+
         // Modal gets input first
         match &mut self.modal {
             Modal::None => {}
@@ -121,113 +405,412 @@ impl App {
             Modal::GroupForm(form) => return form.handle_key(key),
             Modal::Confirm(dialog) => return dialog.handle_key(key),
             Modal::PasswordGenerator(gen) => return gen.handle_key(key),
+            Modal::PasswordPrompt(prompt) => return prompt.handle_key(key),
+            Modal::FilterPicker(picker) => return picker.handle_key(key),
+            Modal::VaultSettings(settings) => return settings.handle_key(key),
+            Modal::EventLog(view) => return view.handle_key(key),
+            Modal::CopyLog(view) => return view.handle_key(key),
+            #[cfg(feature = "qr")]
+            Modal::Qr(view) => return view.handle_key(key),
+            Modal::Changes(view) => return view.handle_key(key),
+            Modal::VaultInfo(view) => return view.handle_key(key),
+            Modal::Import(form) => return form.handle_key(key),
         }
 
         match self.current_screen {
             Screen::Lock => self.lock_screen.handle_key(key),
+            Screen::Main
+                if key.code == KeyCode::Char('u') && self.undo_available(Instant::now()) =>
+            {
+                Action::UndoDelete
+            }
             Screen::Main => self.main_screen.handle_key(key),
         }
     }
 
+    /// Apply the config-driven settings shared by the new and edit item forms.
+    fn configure_item_form(&mut self, form: &mut ItemForm) {
+        form.set_confirm_discard(self.config.confirm_discard_form);
+        form.set_known_tags(
+            self.vault_service.all_tags().unwrap_or_default(),
+            self.config.normalize_tag_case,
+        );
+        form.set_username_generator(
+            self.config.username_alias_base.clone(),
+            self.config.username_handle_length,
+        );
+        if let Err(e) = form.set_field_order(&self.config.item_form_field_order) {
+            self.main_screen.set_warning(format!(
+                "Ignoring item_form_field_order ({e}); using the default order"
+            ));
+        }
+    }
+
+    /// Ask whether to save and lock, replacing any open modal as the lock itself would.
+    fn open_autolock_prompt(&mut self) {
+        self.autolock_prompt_at = Some(Instant::now());
+        self.stashed_item_form = None;
+        self.stashed_group_form = None;
+        self.modal = Modal::Confirm(ConfirmDialog::new(
+            format!("Unsaved changes. Save and lock? (auto in {AUTOLOCK_PROMPT_SECS}s)"),
+            Action::Lock,
+        ));
+    }
+
+    /// Idle timeout in effect: the open vault's override, else the config value.
+    fn auto_lock_secs(&self) -> u64 {
+        let vault_override = self
+            .vault_service
+            .meta()
+            .ok()
+            .and_then(|meta| meta.auto_lock_secs_override);
+        effective_auto_lock_secs(self.config.auto_lock_secs, vault_override)
+    }
+
+    /// Advance the running import: pick up the worker's result, or merge the next batch.
+    fn poll_import(&mut self) {
+        let Some(task) = self.import.take() else {
+            return;
+        };
+        // Waiting on an import the user started is not idling.
+        self.last_activity = Instant::now();
+        let mut job = match task {
+            ImportTask::Reading(receiver) => match receiver.try_recv() {
+                Ok(Ok(data)) => match self
+                    .vault_service
+                    .begin_import_data(data, ImportMode::SkipExisting)
+                {
+                    Ok(job) => Box::new(job),
+                    Err(e) => return self.end_import(Err(e.to_string())),
+                },
+                Ok(Err(e)) => return self.end_import(Err(e.to_string())),
+                Err(TryRecvError::Empty) => {
+                    self.import = Some(ImportTask::Reading(receiver));
+                    return;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    return self.end_import(Err("the import stopped unexpectedly".to_string()))
+                }
+            },
+            ImportTask::Merging(job) => job,
+        };
+        match self.vault_service.import_step(&mut job, IMPORT_BATCH) {
+            Ok(progress) if progress.is_done() => {
+                let count = self.vault_service.finish_import(*job);
+                self.end_import(Ok(count));
+            }
+            Ok(progress) => {
+                self.main_screen.set_progress(Some(progress.status()));
+                self.import = Some(ImportTask::Merging(job));
+            }
+            Err(e) => {
+                let _ = self.vault_service.cancel_import(*job);
+                self.end_import(Err(e.to_string()));
+            }
+        }
+    }
+
+    /// Report how an import ended and show the vault as it now is.
+    fn end_import(&mut self, result: std::result::Result<usize, String>) {
+        self.main_screen.set_progress(None);
+        match result {
+            Ok(count) => {
+                self.refresh_ui();
+                self.auto_save();
+                let cleaned = self.vault_service.sanitized_fields();
+                let mut message = format!("Imported {count} groups and items");
+                if cleaned > 0 {
+                    message.push_str(&format!(
+                        "; removed invisible characters from {cleaned} usernames and passwords"
+                    ));
+                }
+                self.main_screen.set_success(message);
+                for warning in self.vault_service.import_warnings().to_vec() {
+                    self.main_screen.set_warning(warning);
+                }
+            }
+            Err(e) => {
+                self.refresh_ui();
+                self.main_screen.set_error(format!("Import failed: {e}"));
+            }
+        }
+    }
+
+    /// Stop the running import, if any, putting the vault back as it was before it.
+    /// Returns whether there was one.
+    fn cancel_import(&mut self) -> bool {
+        let Some(task) = self.import.take() else {
+            return false;
+        };
+        self.main_screen.set_progress(None);
+        if let ImportTask::Merging(job) = task {
+            if let Err(e) = self.vault_service.cancel_import(*job) {
+                self.main_screen
+                    .set_error(format!("Could not undo the partial import: {e}"));
+            }
+            self.refresh_ui();
+        }
+        true
+    }
+
+    /// Whether `u` should restore the last deleted item instead of its usual binding.
+    fn undo_available(&self, now: Instant) -> bool {
+        self.deleted_item
+            .as_ref()
+            .is_some_and(|deleted| deleted.undoable(now))
+            && !self.main_screen.items_panel.is_search_active()
+    }
+
     fn handle_action(&mut self, action: Action) {
         match action {
             Action::None => {}
             Action::Quit => {
+                self.cancel_import();
+                self.remember_selection();
                 if self.vault_service.is_dirty() {
                     let _ = self.vault_service.save();
                 }
                 self.running = false;
             }
             Action::Lock => {
+                // A half-merged import is dropped rather than saved.
+                self.cancel_import();
+                self.remember_selection();
                 if self.vault_service.is_dirty() {
                     let _ = self.vault_service.save();
                 }
                 self.vault_service.lock();
+                // A secret still waiting for its auto-clear goes with the vault.
+                let _ = self.clipboard.clear_pending();
                 self.current_screen = Screen::Lock;
                 self.lock_screen.clear();
                 self.lock_screen.set_vault_exists(true);
                 self.modal = Modal::None;
                 self.stashed_item_form = None;
-                self.main_screen = MainScreen::new();
+                self.stashed_group_form = None;
+                self.credential_sequence = None;
+                self.reauth_at = None;
+                self.autolock_prompt_at = None;
+                self.deleted_item = None;
+                self.registers.clear();
+                self.copy_log.clear();
+                self.main_screen = build_main_screen(&self.config);
             }
             Action::Save => match self.vault_service.save() {
-                Ok(()) => self.main_screen.set_status("Saved".to_string()),
-                Err(e) => self.main_screen.set_status(format!("Save failed: {e}")),
+                Ok(()) => self.main_screen.set_success("Saved".to_string()),
+                Err(e) => self.main_screen.set_error(format!("Save failed: {e}")),
             },
+            Action::DiscardChanges => {
+                if self.vault_service.is_dirty() {
+                    let dialog = ConfirmDialog::new(
+                        "Discard unsaved changes?".to_string(),
+                        Action::ReloadVault,
+                    );
+                    self.modal = Modal::Confirm(dialog);
+                } else {
+                    self.handle_action(Action::ReloadVault);
+                }
+            }
+            Action::ReloadVault => {
+                self.modal = Modal::None;
+                match self.vault_service.reload() {
+                    Ok(()) => {
+                        // The deleted item would come back as an unsaved change.
+                        self.deleted_item = None;
+                        self.refresh_ui();
+                        self.main_screen
+                            .set_success("Reloaded from disk".to_string());
+                    }
+                    Err(e) => self.main_screen.set_error(format!("Reload failed: {e}")),
+                }
+            }
+            Action::OpenCompactConfirm => {
+                if self.vault_service.is_dirty() {
+                    self.main_screen.set_warning(
+                        "Save or discard unsaved changes before compacting".to_string(),
+                    );
+                    return;
+                }
+                let preview = self.vault_service.compact(CompactOptions {
+                    clear_history: true,
+                    dry_run: true,
+                });
+                let message = match preview {
+                    Ok(report) => format!(
+                        "Compact vault? {} history entries from {} items and {} orphaned references will be removed.",
+                        report.history_entries_removed,
+                        report.affected_items.len(),
+                        report.orphaned_references_removed
+                    ),
+                    Err(_) => "Compact vault? All password history will be deleted.".to_string(),
+                };
+                self.modal = Modal::Confirm(ConfirmDialog::new(message, Action::CompactVault));
+            }
+            Action::CompactVault => {
+                self.modal = Modal::None;
+                let options = CompactOptions {
+                    clear_history: true,
+                    dry_run: false,
+                };
+                match self.vault_service.compact(options) {
+                    Ok(report) => {
+                        self.refresh_ui();
+                        self.main_screen.set_success(format!(
+                            "Compacted: {} history entries, {} orphaned references, {} bytes removed",
+                            report.history_entries_removed,
+                            report.orphaned_references_removed,
+                            report.bytes_removed()
+                        ));
+                    }
+                    Err(e) => self.main_screen.set_error(format!("Compact failed: {e}")),
+                }
+            }
+            Action::CopyPassphraseSuggestion(passphrase) => {
+                match self.clipboard.copy_and_clear(&passphrase) {
+                    Ok(()) => self.lock_screen.set_notice(format!(
+                        "Copied (clears in {}s); type it in to use it",
+                        self.config.clipboard_clear_secs
+                    )),
+                    Err(e) => self.lock_screen.set_error(format!("Copy failed: {e}")),
+                }
+            }
             Action::CreateVault(password) => {
                 // Ensure parent directory exists
                 if let Some(parent) = self.vault_service.vault_path().parent() {
                     let _ = std::fs::create_dir_all(parent);
                 }
                 match self.vault_service.create(&password) {
-                    Ok(()) => {
-                        self.current_screen = Screen::Main;
-                        self.refresh_ui();
-                    }
+                    Ok(()) => self.enter_main_screen(),
                     Err(e) => self.lock_screen.set_error(format!("{e}")),
                 }
             }
             Action::UnlockVault(password) => match self.vault_service.unlock(&password) {
                 Ok(()) => {
-                    self.current_screen = Screen::Main;
-                    self.refresh_ui();
+                    self.enter_main_screen();
+                    self.warn_if_weak_kdf();
                 }
                 Err(e) => self.lock_screen.set_error(format!("{e}")),
             },
             Action::SelectGroup(group_id) => {
+                if self.config.clear_search_on_group_change {
+                    self.main_screen.items_panel.clear_search();
+                }
                 self.refresh_items(group_id);
+                let description = group_id.and_then(|id| {
+                    self.vault_service
+                        .groups()
+                        .ok()?
+                        .iter()
+                        .find(|g| g.id == id)
+                        .map(|g| g.description.clone())
+                });
+                if let Some(description) = description.filter(|d| !d.is_empty()) {
+                    self.main_screen.set_status(description);
+                }
             }
             Action::SelectItem(item_id) => {
                 self.refresh_details(item_id);
             }
-            Action::CreateItem(draft) => match self.vault_service.create_item(draft) {
-                Ok(_id) => {
-                    self.modal = Modal::None;
-                    self.auto_save();
-                    self.refresh_ui();
-                    self.main_screen.set_status("Item created".to_string());
+            Action::ShowDetailsFullScreen(id) => {
+                self.refresh_details(Some(id));
+                self.main_screen.show_details_full_screen();
+            }
+            Action::CreateItem(mut draft) => {
+                if self.config.trim_on_save {
+                    draft.trim_credentials();
                 }
-                Err(e) => self.main_screen.set_status(format!("Error: {e}")),
-            },
-            Action::UpdateItem(id, draft) => match self.vault_service.update_item(id, draft) {
-                Ok(()) => {
-                    self.modal = Modal::None;
-                    self.auto_save();
-                    self.refresh_ui();
-                    self.main_screen.set_status("Item updated".to_string());
+                self.create_item(draft);
+            }
+            Action::UpdateItem(id, mut draft) => {
+                if self.config.trim_on_save {
+                    draft.trim_credentials();
                 }
-                Err(e) => self.main_screen.set_status(format!("Error: {e}")),
-            },
-            Action::DeleteItem(id) => match self.vault_service.delete_item(id) {
-                Ok(()) => {
-                    self.modal = Modal::None;
-                    self.auto_save();
-                    self.main_screen.details_panel.clear();
-                    self.refresh_ui();
-                    self.main_screen.set_status("Item deleted".to_string());
+                self.update_item(id, draft);
+            }
+            Action::DeleteItem(id) => {
+                let item = self.vault_service.get_item(id).cloned();
+                match self.vault_service.delete_item(id) {
+                    Ok(()) => {
+                        self.modal = Modal::None;
+                        self.deleted_item = item.ok().map(|item| DeletedItem {
+                            item,
+                            deleted_at: Instant::now(),
+                        });
+                        self.auto_save();
+                        self.main_screen.details_panel.clear();
+                        self.refresh_ui();
+                        self.main_screen
+                            .set_warning("Item deleted — press u to undo".to_string());
+                    }
+                    Err(e) => self.main_screen.set_error(format!("Error: {e}")),
+                }
+            }
+            Action::MoveItemUp(id) | Action::MoveItemDown(id) => {
+                if self.config.sort_totp_first {
+                    self.main_screen.set_warning(
+                        "Items are sorted (sort_totp_first); manual order is not shown".to_string(),
+                    );
+                    return;
+                }
+                let moved = if matches!(action, Action::MoveItemUp(_)) {
+                    self.vault_service.move_item_up(id)
+                } else {
+                    self.vault_service.move_item_down(id)
+                };
+                match moved {
+                    Ok(true) => {
+                        self.auto_save();
+                        let group_id = self.main_screen.selected_group_id();
+                        self.refresh_items(group_id);
+                    }
+                    Ok(false) => {}
+                    Err(e) => self.main_screen.set_error(format!("Error: {e}")),
                 }
-                Err(e) => self.main_screen.set_status(format!("Error: {e}")),
+            }
+            Action::UndoDelete => match self.deleted_item.take() {
+                Some(deleted) if deleted.undoable(Instant::now()) => {
+                    let id = deleted.item.id;
+                    match self.vault_service.restore_item(deleted.item) {
+                        Ok(()) => {
+                            self.auto_save();
+                            self.refresh_ui();
+                            self.main_screen.items_panel.select_item(id);
+                            self.refresh_details(Some(id));
+                            self.main_screen.set_success("Item restored".to_string());
+                        }
+                        Err(e) => self.main_screen.set_error(format!("Undo failed: {e}")),
+                    }
+                }
+                _ => self.main_screen.set_status("Nothing to undo".to_string()),
             },
-            Action::CreateGroup(name, parent_id) => {
-                match self.vault_service.create_group(name, parent_id) {
-                    Ok(_id) => {
+            Action::CreateGroup(name, parent_id, description) => {
+                match self
+                    .vault_service
+                    .create_group(name, parent_id)
+                    .and_then(|id| self.vault_service.set_group_description(id, description))
+                {
+                    Ok(()) => {
                         self.modal = Modal::None;
                         self.auto_save();
                         self.refresh_ui();
-                        self.main_screen.set_status("Group created".to_string());
+                        self.main_screen.set_success("Group created".to_string());
                     }
-                    Err(e) => self.main_screen.set_status(format!("Error: {e}")),
+                    Err(e) => self.main_screen.set_error(format!("Error: {e}")),
                 }
             }
-            Action::UpdateGroup(id, name, parent_id) => {
-                match self.vault_service.update_group(id, name, parent_id) {
+            Action::UpdateGroup(id, name, parent_id, description) => {
+                match self
+                    .vault_service
+                    .update_group(id, name, parent_id)
+                    .and_then(|()| self.vault_service.set_group_description(id, description))
+                {
                     Ok(()) => {
                         self.modal = Modal::None;
                         self.auto_save();
                         self.refresh_ui();
-                        self.main_screen.set_status("Group updated".to_string());
+                        self.main_screen.set_success("Group updated".to_string());
                     }
-                    Err(e) => self.main_screen.set_status(format!("Error: {e}")),
+                    Err(e) => self.main_screen.set_error(format!("Error: {e}")),
                 }
             }
             Action::DeleteGroup(id) => match self.vault_service.delete_group(id) {
@@ -235,147 +818,689 @@ impl App {
                     self.modal = Modal::None;
                     self.auto_save();
                     self.refresh_ui();
-                    self.main_screen.set_status("Group deleted".to_string());
+                    self.main_screen.set_success("Group deleted".to_string());
                 }
-                Err(e) => self.main_screen.set_status(format!("Error: {e}")),
+                Err(e) => self.main_screen.set_error(format!("Error: {e}")),
             },
-            Action::CopyPassword(id) => {
-                if let Ok(item) = self.vault_service.get_item(id) {
-                    let pw = item.password.clone();
-                    match self.clipboard.copy_and_clear(&pw) {
-                        Ok(()) => self.main_screen.set_status(format!(
-                            "Password copied (clears in {}s)",
-                            self.config.clipboard_clear_secs
-                        )),
-                        Err(e) => self.main_screen.set_status(format!("Clipboard error: {e}")),
-                    }
+            // Sealed, a secret copied out of the item form needs the master password too.
+            Action::CopyText { secret: true, .. }
+                if matches!(self.modal, Modal::ItemForm(_)) && self.require_seal(&action) => {}
+            Action::CopyText {
+                ref label,
+                secret: true,
+                ..
+            } if self.copying_from_sensitive_form() => {
+                let message = format!("Copy {} of this sensitive item?", label.to_lowercase());
+                self.confirm_copy(message, action);
+            }
+            Action::CopyText {
+                ref label, secret, ..
+            } if self.copy_needs_confirm(secret) => {
+                let message = format!("Copy {} to clipboard?", label.to_lowercase());
+                self.confirm_copy(message, action);
+            }
+            Action::CopyText {
+                source,
+                text,
+                label,
+                sensitive,
+                ..
+            } => {
+                self.copy_text(&source, &text, &label, sensitive);
+            }
+            Action::ConfirmedCopy(copy) => {
+                self.modal = match self.stashed_item_form.take() {
+                    Some(form) => Modal::ItemForm(form),
+                    None => Modal::None,
+                };
+                self.copy_confirmed = true;
+                self.handle_action(*copy);
+                self.copy_confirmed = false;
+            }
+            Action::OpenUrl(url) => {
+                self.modal = Modal::None;
+                match opener::open_url(&url) {
+                    Ok(()) => self.main_screen.set_success(format!("Opened {url}")),
+                    Err(e) => self.main_screen.set_error(format!("Could not open: {e}")),
                 }
             }
-            Action::CopyUsername(id) => {
-                if let Ok(item) = self.vault_service.get_item(id) {
-                    let un = item.username.clone();
-                    match self.clipboard.copy_and_clear(&un) {
-                        Ok(()) => self.main_screen.set_status(format!(
-                            "Username copied (clears in {}s)",
-                            self.config.clipboard_clear_secs
-                        )),
-                        Err(e) => self.main_screen.set_status(format!("Clipboard error: {e}")),
+            Action::ChooseUrl(urls) => {
+                let names = urls.clone();
+                self.modal = Modal::FilterPicker(FilterPicker::with_action(
+                    " Open URL ",
+                    names,
+                    move |index| Action::OpenUrl(urls[index].clone()),
+                ));
+            }
+            Action::OpenAttachment(path) => match opener::open_path(&path) {
+                Ok(()) => self
+                    .main_screen
+                    .set_success(format!("Opened {}", path.display())),
+                Err(e) => self.main_screen.set_error(format!("Could not open: {e}")),
+            },
+            Action::EmbedAttachment(id, path) => {
+                let max = self.config.max_embedded_file_bytes;
+                match self.vault_service.embed_file(id, &path, max) {
+                    Ok(()) => {
+                        self.auto_save();
+                        self.refresh_details(Some(id));
+                        let message = format!("Embedded {}", path.display());
+                        let total = self.vault_service.embedded_total_bytes().unwrap_or(0);
+                        if total > self.config.embedded_warn_total_bytes {
+                            self.main_screen.set_warning(format!(
+                                "{message}; embedded files now take {total} bytes of the vault"
+                            ));
+                        } else {
+                            self.main_screen.set_success(message);
+                        }
                     }
+                    Err(e) => self.main_screen.set_error(format!("Could not embed: {e}")),
                 }
             }
-            Action::SetSearchQuery(query) => {
-                let group_id = self.main_screen.selected_group_id();
-                if let Ok(items) = self.vault_service.search_in_group(&query, group_id) {
-                    self.main_screen.update_items(&items);
+            Action::ExportEmbedded(id, index) => {
+                let dir = std::env::current_dir().unwrap_or_default();
+                match self.vault_service.export_embedded(id, index, &dir) {
+                    Ok(dest) => self
+                        .main_screen
+                        .set_success(format!("Exported to {}", dest.display())),
+                    Err(e) => self.main_screen.set_error(format!("Export failed: {e}")),
                 }
             }
-            Action::ClearSearch => {
-                let group_id = self.main_screen.selected_group_id();
-                self.refresh_items(group_id);
+            Action::ConfirmedSensitive(inner) => {
+                self.modal = match self.stashed_item_form.take() {
+                    Some(form) => Modal::ItemForm(form),
+                    None => Modal::None,
+                };
+                self.sensitive_confirmed = true;
+                self.handle_action(*inner);
+                self.sensitive_confirmed = false;
             }
-            Action::OpenNewItemForm => {
-                if let Ok(groups) = self.vault_service.groups() {
-                    let default_group = self.main_screen.selected_group_id();
-                    let form = ItemForm::new_create(groups, default_group);
-                    self.modal = Modal::ItemForm(form);
+            Action::CopyPassword(id) if self.confirm_sensitive(id, "Copy", &action) => {}
+            Action::CopyPassword(id) | Action::CopyUsername(id) => {
+                let Ok(item) = self.vault_service.get_item(id) else {
+                    return;
+                };
+                let (text, label) = match action {
+                    Action::CopyPassword(_) => {
+                        match self
+                            .vault_service
+                            .with_password(id, |pw| Zeroizing::new(pw.to_owned()))
+                        {
+                            Ok(password) => (password, "Password"),
+                            Err(e) => {
+                                self.main_screen.set_error(format!("Error: {e}"));
+                                return;
+                            }
+                        }
+                    }
+                    _ => (Zeroizing::new(item.username.clone()), "Username"),
+                };
+                let secret = matches!(action, Action::CopyPassword(_));
+                let auto_clear = !item.no_clipboard_clear;
+                let copy = if secret {
+                    Action::copy_secret(&item.title, text, label, auto_clear)
+                } else {
+                    Action::copy_text(&item.title, text, label, auto_clear)
+                };
+                if self.copy_needs_confirm(secret) {
+                    // Name the item so a copy from the wrong selection is caught.
+                    let message = format!("Copy {} for '{}'?", label.to_lowercase(), item.title);
+                    self.confirm_copy(message, copy);
+                } else {
+                    self.handle_action(copy);
                 }
             }
-            Action::OpenEditItemForm(id) => {
-                if let (Ok(item), Ok(groups)) =
-                    (self.vault_service.get_item(id), self.vault_service.groups())
-                {
-                    let item = item.clone();
-                    let groups = groups.to_vec();
-                    let form = ItemForm::new_edit(&item, &groups);
-                    self.modal = Modal::ItemForm(form);
+            Action::ChooseQrField(id) => {
+                let Ok(item) = self.vault_service.unsealed_item(id) else {
+                    return;
+                };
+                let fields: Vec<QrField> = QrField::ALL
+                    .into_iter()
+                    .filter(|&field| qr::field_data(&item, field).is_some())
+                    .collect();
+                match fields.as_slice() {
+                    [] => self
+                        .main_screen
+                        .set_warning("Nothing to show as a QR code".to_string()),
+                    &[field] => self.handle_action(Action::RequestQr(id, field)),
+                    _ => {
+                        let names = fields.iter().map(|f| f.label().to_string()).collect();
+                        self.modal = Modal::FilterPicker(FilterPicker::with_action(
+                            " Show as QR ",
+                            names,
+                            move |index| Action::RequestQr(id, fields[index]),
+                        ));
+                    }
                 }
             }
-            Action::OpenDeleteConfirm(id) => {
-                let name = self
+            // A QR code of a secret is a reveal, gated the same way.
+            Action::RequestQr(id, field)
+                if field.is_secret() && self.confirm_sensitive(id, "Reveal", &action) => {}
+            Action::RequestQr(id, field) => {
+                self.modal = Modal::None;
+                let sealed = self.config.seal_mode && self.sensitive_confirmed;
+                if field.is_secret() && !sealed && self.reauth_required(Instant::now()) {
+                    self.modal = Modal::PasswordPrompt(PasswordPrompt::new(
+                        "Re-enter master password to show the QR code:".to_string(),
+                        Action::ShowQr(id, field),
+                    ));
+                } else {
+                    self.handle_action(Action::ShowQr(id, field));
+                }
+            }
+            Action::ShowQr(id, field) => self.show_qr(id, field),
+            Action::CopyTotp(id)
+                if !self
                     .vault_service
                     .get_item(id)
-                    .map(|i| i.title.clone())
-                    .unwrap_or_default();
-                let dialog =
-                    ConfirmDialog::new(format!("Delete item \"{name}\"?"), Action::DeleteItem(id));
-                self.modal = Modal::Confirm(dialog);
+                    .is_ok_and(|item| item.has_totp()) =>
+            {
+                self.main_screen
+                    .set_warning("No 2FA secret on this item".to_string());
             }
-            Action::OpenNewGroupForm => {
-                if let Ok(groups) = self.vault_service.groups() {
-                    let groups = groups.to_vec();
-                    self.modal = Modal::GroupForm(GroupForm::new_create(&groups));
-                }
+            Action::CopyTotp(id) if self.confirm_sensitive(id, "Copy", &action) => {}
+            // The dialog carries the request, not a code, so the code is computed
+            // when the copy is confirmed rather than when it was asked for.
+            Action::CopyTotp(_) if self.copy_needs_confirm(true) => {
+                self.confirm_copy("Copy TOTP code to clipboard?".to_string(), action);
             }
-            Action::OpenEditGroupForm(id) => {
-                if let Ok(groups) = self.vault_service.groups() {
-                    let groups = groups.to_vec();
-                    if let Some(group) = groups.iter().find(|g| g.id == id) {
-                        self.modal = Modal::GroupForm(GroupForm::new_edit(group, &groups));
+            Action::CopyTotp(id) => {
+                let Ok(item) = self.vault_service.get_item(id) else {
+                    return;
+                };
+                let Some(secret) = item.otp_secret.as_deref() else {
+                    return;
+                };
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                match totp::code_for_copy(secret, now) {
+                    Ok(copied) => {
+                        let label = if copied.next_window {
+                            "Next TOTP code"
+                        } else {
+                            "TOTP code"
+                        };
+                        let clear = !item.no_clipboard_clear;
+                        let title = item.title.clone();
+                        self.copy_text(&title, &copied.code, label, clear);
                     }
+                    Err(e) => self.main_screen.set_error(e.to_string()),
                 }
             }
-            Action::OpenDeleteGroupConfirm(id) => {
-                if let Ok(groups) = self.vault_service.groups() {
-                    let name = groups
-                        .iter()
-                        .find(|g| g.id == id)
-                        .map(|g| g.name.clone())
-                        .unwrap_or_default();
-                    let dialog = ConfirmDialog::new(
-                        format!("Delete group \"{name}\"?"),
-                        Action::DeleteGroup(id),
+            Action::RunLaunchCommand(id) => {
+                if !self.config.enable_launch_commands {
+                    self.main_screen.set_warning(
+                        "Launch commands are disabled (set enable_launch_commands)".to_string(),
                     );
-                    self.modal = Modal::Confirm(dialog);
+                    return;
                 }
-            }
-            Action::OpenPasswordGenerator => {
-                let for_item_form = matches!(self.modal, Modal::ItemForm(_));
-                if for_item_form {
-                    // Stash the item form so we can restore it after the generator closes.
-                    let old_modal = std::mem::replace(
-                        &mut self.modal,
-                        Modal::PasswordGenerator(PasswordGeneratorModal::new()),
-                    );
-                    if let Modal::ItemForm(form) = old_modal {
-                        self.stashed_item_form = Some(form);
+                match self.launch_command(id) {
+                    Ok(launch) => {
+                        let message = format!("Run `{}`?", launch.display());
+                        let dialog = ConfirmDialog::new(message, Action::ConfirmedLaunch(id));
+                        self.modal = Modal::Confirm(dialog);
                     }
-                } else {
-                    self.modal = Modal::PasswordGenerator(PasswordGeneratorModal::new());
+                    Err(e) => self.main_screen.set_error(e.to_string()),
                 }
             }
-            Action::UseGeneratedPassword => {
-                // Extract generated password, restore stashed item form with it.
-                if let Modal::PasswordGenerator(ref gen) = self.modal {
-                    let pw = gen.generated_password().to_string();
-                    if let Some(mut form) = self.stashed_item_form.take() {
-                        form.set_password(pw);
-                        self.modal = Modal::ItemForm(form);
-                    } else {
-                        // No item form stashed — copy to clipboard instead.
-                        let _ = self.clipboard.copy_and_clear(&pw);
-                        self.main_screen.set_status(format!(
-                            "Password copied (clears in {}s)",
-                            self.config.clipboard_clear_secs
+            Action::ConfirmedLaunch(id) => {
+                self.modal = Modal::None;
+                match self.launch_command(id).and_then(|launch| {
+                    launch.spawn()?;
+                    Ok(launch)
+                }) {
+                    Ok(launch) => self
+                        .main_screen
+                        .set_success(format!("Started {}", launch.program())),
+                    Err(e) => self.main_screen.set_error(format!("Could not run: {e}")),
+                }
+            }
+            Action::YankToRegister(_, id) if self.confirm_sensitive(id, "Copy", &action) => {}
+            Action::YankToRegister(name, id) => {
+                let Ok(title) = self.vault_service.get_item(id).map(|i| i.title.clone()) else {
+                    return;
+                };
+                match self
+                    .vault_service
+                    .with_password(id, |pw| Zeroizing::new(pw.to_owned()))
+                {
+                    Ok(password) => {
+                        self.registers.insert(name, (title.clone(), password));
+                        self.main_screen.set_success(format!(
+                            "Password of '{title}' stored in register \"{name}"
                         ));
-                        self.modal = Modal::None;
                     }
+                    Err(e) => self.main_screen.set_error(format!("Error: {e}")),
                 }
             }
-            Action::CloseModal => {
-                // Esc / cancel: restore stashed form without applying password.
-                if let Some(form) = self.stashed_item_form.take() {
-                    self.modal = Modal::ItemForm(form);
-                } else {
-                    self.modal = Modal::None;
+            Action::CopyRegister(_) if self.require_seal(&action) => {}
+            Action::CopyRegister(name) => match self.registers.get(&name) {
+                Some((title, value)) => {
+                    let label = format!("Register \"{name}");
+                    let copy = Action::copy_secret(title, value.to_string(), &label, true);
+                    self.handle_action(copy);
                 }
-            }
-            Action::SetStatus(msg) => {
-                self.main_screen.set_status(msg);
-            }
-        }
-    }
-
-    fn refresh_ui(&mut self) {
+                None => self
+                    .main_screen
+                    .set_warning(format!("Register \"{name} is empty")),
+            },
+            Action::CopyPublicKey(id) => match self.vault_service.get_item(id) {
+                Ok(Item {
+                    title,
+                    kind: ItemKind::SshKey { public_key, .. },
+                    ..
+                }) if !public_key.is_empty() => {
+                    let copy = Action::copy_text(title, public_key.clone(), "Public key", false);
+                    self.handle_action(copy);
+                }
+                Ok(_) => self
+                    .main_screen
+                    .set_warning("No public key to copy".to_string()),
+                Err(e) => self.main_screen.set_error(format!("Error: {e}")),
+            },
+            Action::ToggleNoClipboardClear(id) => {
+                let keep = self
+                    .vault_service
+                    .get_item(id)
+                    .is_ok_and(|item| !item.no_clipboard_clear);
+                match self.vault_service.set_no_clipboard_clear(id, keep) {
+                    Ok(()) => {
+                        self.auto_save();
+                        self.refresh_details(Some(id));
+                        self.main_screen.set_success(if keep {
+                            "Copies from this item will not be auto-cleared".to_string()
+                        } else {
+                            "Copies from this item will be auto-cleared".to_string()
+                        });
+                    }
+                    Err(e) => self.main_screen.set_error(format!("Error: {e}")),
+                }
+            }
+            Action::CopyFormattedItem(id)
+                if template::includes_password(&self.config.item_copy_template)
+                    && self.confirm_sensitive(id, "Copy", &action) => {}
+            Action::CopyFormattedItem(id) => {
+                if let Ok(item) = self.vault_service.unsealed_item(id) {
+                    let template = &self.config.item_copy_template;
+                    let text = template::format_item(&item, template);
+                    // With a password in it the text is a secret, cleared unless the item
+                    // opts out like its password would.
+                    let copy = if template::includes_password(template) {
+                        let auto_clear = !item.no_clipboard_clear;
+                        Action::copy_secret(&item.title, text, "Item", auto_clear)
+                    } else {
+                        Action::copy_text(&item.title, text, "Item", false)
+                    };
+                    self.handle_action(copy);
+                }
+            }
+            // Confirmed once when the sequence starts; its password step does not ask again.
+            Action::CopyCredentialSequence(id)
+                if self
+                    .credential_sequence
+                    .as_ref()
+                    .is_none_or(|seq| seq.item_id != id)
+                    && self.confirm_sensitive(id, "Copy", &action) => {}
+            Action::CopyCredentialSequence(id) => match self.credential_sequence.take() {
+                Some(seq) if seq.item_id == id => self.copy_sequence_password(id),
+                _ => {
+                    let username = self
+                        .vault_service
+                        .get_item(id)
+                        .map(|i| (i.username.clone(), !i.no_clipboard_clear, i.title.clone()));
+                    if let Ok((un, clear, title)) = username {
+                        if self.copy_needs_confirm(false) {
+                            let message = format!("Copy username for '{title}'?");
+                            self.confirm_copy(message, action);
+                        } else if self.copy_text(&title, &un, "Username", clear) {
+                            self.credential_sequence = Some(CredentialSequence {
+                                item_id: id,
+                                username_copied_at: Instant::now(),
+                            });
+                            self.main_screen
+                                .set_status("Press c again to copy the password".to_string());
+                        }
+                    }
+                }
+            },
+            Action::RequestReveal(id) if self.confirm_sensitive(id, "Reveal", &action) => {}
+            Action::RequestReveal(id) => {
+                // In seal mode the password was entered for this very reveal.
+                let sealed = self.config.seal_mode && self.sensitive_confirmed;
+                if !sealed && self.reauth_required(Instant::now()) {
+                    self.modal = Modal::PasswordPrompt(PasswordPrompt::new(
+                        "Re-enter master password to reveal:".to_string(),
+                        Action::RevealPassword(id),
+                    ));
+                } else {
+                    self.handle_action(Action::RevealPassword(id));
+                }
+            }
+            Action::RevealPassword(id) => {
+                if self.main_screen.details_panel.selected_item_id() == Some(id) {
+                    if let Ok(password) = self
+                        .vault_service
+                        .with_password(id, |pw| Zeroizing::new(pw.to_owned()))
+                    {
+                        self.main_screen.details_panel.set_sealed_password(password);
+                    }
+                    self.main_screen.details_panel.reveal();
+                }
+            }
+            Action::SubmitReauth(password) => {
+                if let Modal::PasswordPrompt(ref mut prompt) = self.modal {
+                    if self
+                        .vault_service
+                        .verify_password(&password)
+                        .unwrap_or(false)
+                    {
+                        let pending = prompt.pending_action().clone();
+                        self.reauth_at = Some(Instant::now());
+                        self.reauth_failures = 0;
+                        self.modal = Modal::None;
+                        self.handle_action(pending);
+                    } else {
+                        prompt.set_error("Wrong master password".to_string());
+                        if let Action::Lock = self.record_reauth_failure() {
+                            let attempts = self.reauth_failures;
+                            self.handle_action(Action::Lock);
+                            self.lock_screen.set_error(format!(
+                                "Locked after {attempts} failed re-authentication attempts"
+                            ));
+                        }
+                    }
+                }
+            }
+            Action::SetSearchQuery(_) => {
+                let group_id = self.main_screen.selected_group_id();
+                let items = visible_items(
+                    &self.vault_service,
+                    &self.main_screen.items_panel,
+                    group_id,
+                    self.config.sort_totp_first,
+                );
+                self.main_screen.update_items(&items);
+            }
+            Action::ToggleTotpView => {
+                self.main_screen.items_panel.toggle_totp_only();
+                let group_id = self.main_screen.selected_group_id();
+                self.refresh_items(group_id);
+            }
+            Action::OpenCopyLog => {
+                let records = self.copy_log.records().cloned().collect();
+                let last_cleared_ago = self.clipboard.last_cleared().map(|at| at.elapsed());
+                self.modal = Modal::CopyLog(CopyLogView::new(records, last_cleared_ago));
+            }
+            Action::OpenEventLog => {
+                self.sync_event_log();
+                let events = self.event_log.events().cloned().collect();
+                self.modal = Modal::EventLog(EventLogView::new(events));
+            }
+            Action::OpenChanges => {
+                let changes = self.vault_service.diff_since_save();
+                self.modal = Modal::Changes(ChangesView::new(changes));
+            }
+            Action::OpenVaultInfo => {
+                let view = VaultInfoView::new(self.vault_service.vault_path());
+                self.modal = Modal::VaultInfo(view);
+            }
+            Action::OpenImport => self.modal = Modal::Import(ImportForm::new()),
+            Action::StartImport(path, password) => {
+                self.modal = Modal::None;
+                let (sender, receiver) = mpsc::channel();
+                std::thread::spawn(move || {
+                    // The receiver is gone if the import was cancelled meanwhile.
+                    let _ = sender.send(ImportData::read(&path, &password));
+                });
+                self.import = Some(ImportTask::Reading(receiver));
+                self.main_screen
+                    .set_progress(Some("Decrypting import…".to_string()));
+            }
+            Action::CancelImport => {
+                if self.cancel_import() {
+                    self.main_screen.set_status("Import cancelled".to_string());
+                }
+            }
+            Action::OpenUndoImportConfirm => {
+                if self.vault_service.can_undo_import() {
+                    self.modal = Modal::Confirm(ConfirmDialog::new(
+                        "Undo the last import? Changes made since then are undone too.".to_string(),
+                        Action::UndoImport,
+                    ));
+                } else {
+                    self.main_screen.set_status("No import to undo".to_string());
+                }
+            }
+            Action::UndoImport => {
+                self.modal = Modal::None;
+                match self.vault_service.undo_last_import() {
+                    Ok(true) => {
+                        self.refresh_ui();
+                        self.auto_save();
+                        self.main_screen.set_success("Import undone".to_string());
+                    }
+                    Ok(false) => self.main_screen.set_status("No import to undo".to_string()),
+                    Err(e) => self.main_screen.set_error(format!("Undo failed: {e}")),
+                }
+            }
+            Action::OpenVaultSettings => {
+                if let Ok(meta) = self.vault_service.meta() {
+                    self.modal = Modal::VaultSettings(VaultSettings::new(
+                        meta.auto_lock_secs_override,
+                        self.config.auto_lock_secs,
+                        meta.record_password_history,
+                    ));
+                }
+            }
+            Action::SaveVaultSettings {
+                auto_lock_override,
+                record_password_history,
+            } => {
+                let recorded = self
+                    .vault_service
+                    .meta()
+                    .map(|meta| meta.record_password_history)
+                    .unwrap_or(record_password_history);
+                let result = self
+                    .vault_service
+                    .set_auto_lock_override(auto_lock_override)
+                    .and_then(|()| {
+                        self.vault_service
+                            .set_record_password_history(record_password_history)
+                    });
+                match result {
+                    Ok(()) => {
+                        self.modal = Modal::None;
+                        self.auto_save();
+                        let mut message = match auto_lock_override {
+                            Some(0) => "Auto-lock disabled for this vault".to_string(),
+                            Some(secs) => format!("This vault now locks after {secs}s idle"),
+                            None => "This vault uses the global auto-lock".to_string(),
+                        };
+                        if record_password_history != recorded {
+                            message.push_str(if record_password_history {
+                                "; previous passwords will be kept in item history"
+                            } else {
+                                "; previous passwords will no longer be kept"
+                            });
+                        }
+                        self.main_screen.set_success(message);
+                    }
+                    Err(e) => self.main_screen.set_error(format!("Error: {e}")),
+                }
+            }
+            Action::OpenSavedFilters => {
+                if self.config.saved_filters.is_empty() {
+                    self.main_screen.set_status(
+                        "No saved filters — press S in the items list to save one".to_string(),
+                    );
+                } else {
+                    let names = self
+                        .config
+                        .saved_filters
+                        .iter()
+                        .map(|f| f.name.clone())
+                        .collect();
+                    self.modal = Modal::FilterPicker(FilterPicker::new(names));
+                }
+            }
+            Action::ApplySavedFilter(index) => {
+                self.modal = Modal::None;
+                if let Some(filter) = self.config.saved_filters.get(index).cloned() {
+                    if filter.scope == FilterScope::All {
+                        self.main_screen.groups_panel.select_all_items();
+                    }
+                    self.main_screen
+                        .items_panel
+                        .set_filter(filter.query, filter.tag);
+                    let group_id = self.main_screen.selected_group_id();
+                    self.refresh_items(group_id);
+                    self.main_screen
+                        .set_status(format!("Filter \"{}\" applied", filter.name));
+                }
+            }
+            Action::SaveCurrentFilter => self.save_current_filter(),
+            Action::ClearSearch => {
+                let group_id = self.main_screen.selected_group_id();
+                self.refresh_items(group_id);
+            }
+            Action::OpenNewItemForm => {
+                if let Ok(groups) = self.vault_service.groups() {
+                    let default_group = new_item_group(
+                        groups,
+                        self.main_screen.selected_group_id(),
+                        self.config.default_group_name.as_deref(),
+                        self.config.prefer_selected_group,
+                    );
+                    let mut form = ItemForm::new_create(groups, default_group);
+                    self.configure_item_form(&mut form);
+                    self.modal = Modal::ItemForm(form);
+                }
+            }
+            // The form shows the password in plaintext, so opening it is a reveal.
+            Action::OpenEditItemForm(id) if self.confirm_sensitive(id, "Edit", &action) => {}
+            Action::OpenEditItemForm(id) => {
+                if let (Ok(item), Ok(groups)) = (
+                    self.vault_service.unsealed_item(id),
+                    self.vault_service.groups(),
+                ) {
+                    let groups = groups.to_vec();
+                    let mut form = ItemForm::new_edit(&item, &groups);
+                    self.configure_item_form(&mut form);
+                    self.modal = Modal::ItemForm(form);
+                }
+            }
+            Action::OpenDeleteConfirm(id) => {
+                let name = self
+                    .vault_service
+                    .get_item(id)
+                    .map(|i| i.title.clone())
+                    .unwrap_or_default();
+                let dialog =
+                    ConfirmDialog::new(format!("Delete item \"{name}\"?"), Action::DeleteItem(id));
+                self.modal = Modal::Confirm(dialog);
+            }
+            Action::OpenNewGroupForm => {
+                if let Ok(groups) = self.vault_service.groups() {
+                    let groups = groups.to_vec();
+                    let mut form = GroupForm::new_create(&groups);
+                    form.set_confirm_discard(self.config.confirm_discard_form);
+                    self.modal = Modal::GroupForm(form);
+                }
+            }
+            Action::OpenEditGroupForm(id) => {
+                if let Ok(groups) = self.vault_service.groups() {
+                    let groups = groups.to_vec();
+                    if let Some(group) = groups.iter().find(|g| g.id == id) {
+                        let mut form = GroupForm::new_edit(group, &groups);
+                        form.set_confirm_discard(self.config.confirm_discard_form);
+                        self.modal = Modal::GroupForm(form);
+                    }
+                }
+            }
+            Action::OpenDeleteGroupConfirm(id) => {
+                if let Ok(groups) = self.vault_service.groups() {
+                    let name = groups
+                        .iter()
+                        .find(|g| g.id == id)
+                        .map(|g| g.name.clone())
+                        .unwrap_or_default();
+                    let dialog = ConfirmDialog::new(
+                        format!("Delete group \"{name}\"?"),
+                        Action::DeleteGroup(id),
+                    );
+                    self.modal = Modal::Confirm(dialog);
+                }
+            }
+            Action::OpenPasswordGenerator => {
+                let for_item_form = matches!(self.modal, Modal::ItemForm(_));
+                let mut generator = PasswordGeneratorModal::new();
+                generator.set_length_step(self.config.generator_length_step);
+                generator.set_ambiguous_chars(&self.config.generator_ambiguous_chars);
+                if for_item_form {
+                    // Stash the item form so we can restore it after the generator closes.
+                    let old_modal =
+                        std::mem::replace(&mut self.modal, Modal::PasswordGenerator(generator));
+                    if let Modal::ItemForm(form) = old_modal {
+                        self.stashed_item_form = Some(form);
+                    }
+                } else {
+                    self.modal = Modal::PasswordGenerator(generator);
+                }
+            }
+            Action::UseGeneratedPassword => {
+                // Extract generated password, restore stashed item form with it.
+                if let Modal::PasswordGenerator(ref gen) = self.modal {
+                    let pw = gen.generated_password().to_string();
+                    if let Some(mut form) = self.stashed_item_form.take() {
+                        form.set_password(pw);
+                        self.modal = Modal::ItemForm(form);
+                    } else {
+                        // No item form stashed — copy to clipboard instead.
+                        self.modal = Modal::None;
+                        self.copy_text("Password generator", &pw, "Password", true);
+                    }
+                }
+            }
+            Action::ConfirmDiscardForm => {
+                match std::mem::replace(&mut self.modal, Modal::None) {
+                    Modal::ItemForm(form) => self.stashed_item_form = Some(form),
+                    Modal::GroupForm(form) => self.stashed_group_form = Some(form),
+                    other => {
+                        self.modal = other;
+                        return;
+                    }
+                }
+                let dialog =
+                    ConfirmDialog::new("Discard changes?".to_string(), Action::DiscardForm);
+                self.modal = Modal::Confirm(dialog);
+            }
+            Action::DiscardForm => {
+                self.stashed_item_form = None;
+                self.stashed_group_form = None;
+                self.modal = Modal::None;
+            }
+            Action::CloseModal => {
+                // Esc / cancel: restore stashed form without applying password.
+                if let Some(form) = self.stashed_item_form.take() {
+                    self.modal = Modal::ItemForm(form);
+                } else if let Some(form) = self.stashed_group_form.take() {
+                    self.modal = Modal::GroupForm(form);
+                } else {
+                    self.modal = Modal::None;
+                }
+            }
+            Action::SetStatus(msg) => {
+                self.main_screen.set_status(msg);
+            }
+        }
+    }
+
+    fn refresh_ui(&mut self) {
+        self.main_screen
+            .set_vault_empty(self.vault_service.is_empty());
         if let Ok(groups) = self.vault_service.groups() {
             let groups = groups.to_vec();
             self.main_screen.update_groups(&groups);
@@ -385,16 +1510,12 @@ impl App {
     }
 
     fn refresh_items(&mut self, group_id: Option<Uuid>) {
-        let query = self.main_screen.items_panel.search_query().to_string();
-        let items = if query.is_empty() {
-            self.vault_service
-                .items_in_group(group_id)
-                .unwrap_or_default()
-        } else {
-            self.vault_service
-                .search_in_group(&query, group_id)
-                .unwrap_or_default()
-        };
+        let items = visible_items(
+            &self.vault_service,
+            &self.main_screen.items_panel,
+            group_id,
+            self.config.sort_totp_first,
+        );
         self.main_screen.update_items(&items);
 
         // Auto-select first item
@@ -404,8 +1525,8 @@ impl App {
 
     fn refresh_details(&mut self, item_id: Option<Uuid>) {
         if let Some(id) = item_id {
-            if let Ok(item) = self.vault_service.get_item(id) {
-                let item = item.clone();
+            // A sealed password stays sealed until the panel reveals it.
+            if let Ok(item) = self.vault_service.get_item(id).cloned() {
                 let group_name = item
                     .group_id
                     .and_then(|gid| {
@@ -415,18 +1536,1980 @@ impl App {
                     })
                     .unwrap_or_else(|| "None".to_string());
                 self.main_screen.update_details(Some(&item), &group_name);
+                let summary = item.sealed_password.is_some().then(|| {
+                    self.vault_service
+                        .with_password(id, |pw| (pw.chars().count(), has_edge_whitespace(pw)))
+                });
+                if let Some(Ok((chars, edge))) = summary {
+                    self.main_screen
+                        .details_panel
+                        .set_sealed_password_summary(chars, edge);
+                }
             }
         } else {
             self.main_screen.update_details(None, "");
         }
     }
 
-    fn auto_save(&mut self) {
-        if self.vault_service.is_dirty() {
-            if let Err(e) = self.vault_service.save() {
-                self.main_screen
-                    .set_status(format!("Auto-save failed: {e}"));
+    /// Whether a protected action needs the master password re-entered right now.
+    fn reauth_required(&self, now: Instant) -> bool {
+        reauth_needed(
+            self.config.require_password_to_reveal,
+            self.reauth_at,
+            self.config.reveal_reauth_window_secs,
+            now,
+        )
+    }
+
+    /// Store the current search (and tag) as a saved filter, replacing one with the same name.
+    fn save_current_filter(&mut self) {
+        let panel = &self.main_screen.items_panel;
+        let query = panel.search_query().trim().to_string();
+        let tag = panel.tag_filter().map(str::to_string);
+        if query.is_empty() && tag.is_none() {
+            self.main_screen
+                .set_warning("Nothing to save — search first".to_string());
+            return;
+        }
+
+        let name = match tag {
+            Some(ref tag) => format!("tag:{tag} {query}").trim_end().to_string(),
+            None => query.clone(),
+        };
+        let scope = if self.main_screen.selected_group_id().is_some() {
+            FilterScope::CurrentGroup
+        } else {
+            FilterScope::All
+        };
+        let filter = SavedFilter {
+            name: name.clone(),
+            query,
+            tag,
+            scope,
+        };
+        let filters = &mut self.config.saved_filters;
+        match filters.iter_mut().find(|f| f.name == name) {
+            Some(existing) => *existing = filter,
+            None => filters.push(filter),
+        }
+
+        let filters = self.config.saved_filters.clone();
+        match self.persist_config(|on_disk| on_disk.saved_filters = filters) {
+            Ok(()) => self
+                .main_screen
+                .set_success(format!("Saved filter \"{name}\"")),
+            Err(e) => self
+                .main_screen
+                .set_error(format!("Could not save config: {e}")),
+        }
+    }
+
+    /// Apply `update` to the config file, leaving its other settings (which CLI flags
+    /// may have overridden in memory) as they are on disk.
+    fn persist_config(&self, update: impl FnOnce(&mut AppConfig)) -> crate::error::Result<()> {
+        let Some(ref path) = self.config_path else {
+            return Ok(());
+        };
+        let mut on_disk = if path.exists() {
+            AppConfig::load_from(path)?
+        } else {
+            AppConfig::default()
+        };
+        update(&mut on_disk);
+        on_disk.save_to(path)
+    }
+
+    /// Record the selected group and item so the next unlock can restore them.
+    fn remember_selection(&mut self) {
+        if !self.config.restore_last_selection || self.current_screen != Screen::Main {
+            return;
+        }
+        let group = self.main_screen.selected_group_id();
+        let item = self.main_screen.selected_item_id();
+        self.config.last_selected_group = group;
+        self.config.last_selected_item = item;
+        // Best effort: the app is locking or quitting, so there is nowhere to report a failure.
+        let _ = self.persist_config(|on_disk| {
+            on_disk.last_selected_group = group;
+            on_disk.last_selected_item = item;
+        });
+    }
+
+    /// Re-select the remembered group and item if they still exist.
+    fn restore_selection(&mut self) {
+        if let Some(group_id) = self.config.last_selected_group {
+            if self.main_screen.groups_panel.select_group(group_id) {
+                self.refresh_items(Some(group_id));
+            }
+        }
+        if let Some(item_id) = self.config.last_selected_item {
+            if self.main_screen.items_panel.select_item(item_id) {
+                self.refresh_details(Some(item_id));
+            }
+        }
+    }
+
+    /// Count a failed re-authentication; returns `Action::Lock` once the limit is exceeded.
+    fn record_reauth_failure(&mut self) -> Action {
+        self.reauth_failures += 1;
+        let max = self.config.max_reveal_reauth_failures;
+        if max > 0 && self.reauth_failures >= max {
+            Action::Lock
+        } else {
+            Action::None
+        }
+    }
+
+    fn enter_main_screen(&mut self) {
+        self.current_screen = Screen::Main;
+        self.reauth_failures = 0;
+        self.refresh_ui();
+        if self.config.restore_last_selection {
+            self.restore_selection();
+        }
+        if let Some(warning) = self.clipboard_status.warning() {
+            self.main_screen.set_warning(warning);
+        }
+        if let Some(ref warning) = self.config_warning {
+            self.main_screen.set_warning(warning.clone());
+        }
+        if let Some(kind) = mount::detect(self.vault_service.vault_path()) {
+            self.main_screen.set_warning(kind.warning());
+        }
+    }
+
+    /// Point out, without blocking, that the vault file was written with weak KDF settings.
+    fn warn_if_weak_kdf(&mut self) {
+        let params = self.vault_service.kdf_params();
+        if self.config.warn_weak_kdf && params.is_weak() {
+            let message = format!(
+                "This vault's key derivation is weaker than recommended ({} KiB, {} passes)",
+                params.memory_cost_kib, params.time_cost
+            );
+            self.main_screen.set_warning(message);
+        }
+    }
+
+    /// The item's launch command with its fields filled in.
+    fn launch_command(&self, id: Uuid) -> crate::error::Result<LaunchCommand> {
+        let item = self.vault_service.unsealed_item(id)?;
+        let template = item
+            .launch_command
+            .as_deref()
+            .ok_or_else(|| VaulturaError::Config("No launch command set".to_string()))?;
+        LaunchCommand::expand(template, &item)
+    }
+
+    /// For a sensitive item, ask before `action` reveals or copies its password and
+    /// return true; the confirmed action comes back as `ConfirmedSensitive`. In seal
+    /// mode every item asks, for the master password instead of a yes/no.
+    fn confirm_sensitive(&mut self, id: Uuid, verb: &str, action: &Action) -> bool {
+        if self.sensitive_confirmed {
+            return false;
+        }
+        if self.config.seal_mode {
+            return self.require_seal(action);
+        }
+        let Ok(item) = self.vault_service.get_item(id) else {
+            return false;
+        };
+        if !item.sensitive {
+            return false;
+        }
+        let message = format!("{verb} sensitive value for '{}'?", item.title);
+        let confirmed = Action::ConfirmedSensitive(Box::new(action.clone()));
+        self.modal = Modal::Confirm(ConfirmDialog::new(message, confirmed));
+        true
+    }
+
+    /// In seal mode, ask for the master password before `action` and return true;
+    /// the correct password brings the action back as `ConfirmedSensitive`.
+    fn require_seal(&mut self, action: &Action) -> bool {
+        if !self.config.seal_mode || self.sensitive_confirmed {
+            return false;
+        }
+        // Keep an open item form so it comes back after the prompt.
+        if let Modal::ItemForm(form) = std::mem::replace(&mut self.modal, Modal::None) {
+            self.stashed_item_form = Some(form);
+        }
+        self.modal = Modal::PasswordPrompt(PasswordPrompt::new(
+            "Vault is sealed; enter master password:".to_string(),
+            Action::ConfirmedSensitive(Box::new(action.clone())),
+        ));
+        true
+    }
+
+    /// Ask before performing `copy`; the confirmed copy skips the prompt.
+    fn confirm_copy(&mut self, message: String, copy: Action) {
+        // Keep an open item form so it comes back after the dialog.
+        if let Modal::ItemForm(form) = std::mem::replace(&mut self.modal, Modal::None) {
+            self.stashed_item_form = Some(form);
+        }
+        let dialog = ConfirmDialog::new(message, Action::ConfirmedCopy(Box::new(copy)));
+        self.modal = Modal::Confirm(dialog);
+    }
+
+    /// The single clipboard path: copy, schedule auto-clear for sensitive text,
+    /// and report the outcome in the status bar.
+    fn copy_text(&mut self, source: &str, text: &str, label: &str, sensitive: bool) -> bool {
+        let text = copy_payload(text, self.config.trim_on_copy);
+        let result = if sensitive {
+            self.clipboard.copy_and_clear(text)
+        } else {
+            self.clipboard.copy(text)
+        };
+        match result {
+            Ok(()) => {
+                self.copy_log.push(source.to_string(), label.to_string());
+                self.main_screen.set_success(copy_status(
+                    label,
+                    sensitive,
+                    self.config.clipboard_clear_secs,
+                ));
+                true
+            }
+            Err(e) => {
+                self.main_screen.set_error(e.to_string());
+                false
+            }
+        }
+    }
+
+    #[cfg(feature = "qr")]
+    fn show_qr(&mut self, id: Uuid, field: QrField) {
+        let Ok(item) = self.vault_service.unsealed_item(id) else {
+            return;
+        };
+        let Some(data) = qr::field_data(&item, field) else {
+            return;
+        };
+        match qr::render(&data) {
+            Ok(lines) => {
+                self.modal = Modal::Qr(QrModal::new(field.label(), lines, field.is_secret()));
+            }
+            Err(e) => self
+                .main_screen
+                .set_error(format!("Could not draw QR code: {e}")),
+        }
+    }
+
+    #[cfg(not(feature = "qr"))]
+    fn show_qr(&mut self, _id: Uuid, _field: QrField) {
+        self.main_screen.set_warning(
+            "QR codes are not available in this build (enable the `qr` feature)".to_string(),
+        );
+    }
+
+    fn create_item(&mut self, draft: ItemDraft) {
+        match self.vault_service.create_item(draft) {
+            Ok(_id) => {
+                self.modal = Modal::None;
+                self.auto_save();
+                self.refresh_ui();
+                self.main_screen.set_success("Item created".to_string());
+            }
+            Err(e) => self.main_screen.set_error(format!("Error: {e}")),
+        }
+    }
+
+    fn update_item(&mut self, id: Uuid, draft: ItemDraft) {
+        match self.vault_service.update_item(id, draft) {
+            Ok(()) => {
+                self.modal = Modal::None;
+                self.auto_save();
+                self.refresh_ui();
+                self.main_screen.set_success("Item updated".to_string());
             }
+            Err(e) => self.main_screen.set_error(format!("Error: {e}")),
         }
     }
+
+    fn copy_sequence_password(&mut self, id: Uuid) {
+        let Ok(item) = self.vault_service.get_item(id) else {
+            return;
+        };
+        let clear = !item.no_clipboard_clear;
+        let title = item.title.clone();
+        let Ok(pw) = self
+            .vault_service
+            .with_password(id, |pw| Zeroizing::new(pw.to_owned()))
+        else {
+            return;
+        };
+        if self.copy_needs_confirm(true) {
+            let message = format!("Copy password for '{title}'?");
+            let copy = Action::copy_secret(&title, pw, "Password", clear);
+            self.confirm_copy(message, copy);
+        } else {
+            self.copy_text(&title, &pw, "Password", clear);
+        }
+    }
+
+    /// Whether a secret copied out of the open item form belongs to a sensitive item
+    /// and has not been confirmed yet.
+    fn copying_from_sensitive_form(&self) -> bool {
+        !(self.sensitive_confirmed || self.copy_confirmed)
+            && matches!(&self.modal, Modal::ItemForm(form) if form.is_sensitive())
+    }
+
+    /// Whether a copy should wait for a yes/no; see `copy_needs_confirm`.
+    fn copy_needs_confirm(&self, secret: bool) -> bool {
+        copy_needs_confirm(
+            &self.config,
+            secret,
+            self.sensitive_confirmed || self.copy_confirmed,
+        )
+    }
+
+    fn auto_save(&mut self) {
+        if self.config.autosave && self.vault_service.is_dirty() {
+            if let Err(e) = self.vault_service.save() {
+                self.main_screen.set_error(format!("Auto-save failed: {e}"));
+            }
+        }
+    }
+}
+
+/// The group a new item starts in: the selected one, unless a default group is
+/// named (matched ignoring case) and the selection is not preferred.
+fn new_item_group(
+    groups: &[Group],
+    selected: Option<Uuid>,
+    default_name: Option<&str>,
+    prefer_selected: bool,
+) -> Option<Uuid> {
+    match default_name {
+        None => selected,
+        Some(_) if prefer_selected && selected.is_some() => selected,
+        Some(name) => groups
+            .iter()
+            .find(|g| g.name.eq_ignore_ascii_case(name))
+            .map(|g| g.id),
+    }
+}
+
+/// A vault's own auto-lock timeout wins over the global one. A stored 0 cannot be
+/// entered any more and is ignored, so it never switches auto-lock off by itself.
+fn effective_auto_lock_secs(global: u64, vault_override: Option<u64>) -> u64 {
+    vault_override.filter(|&secs| secs > 0).unwrap_or(global)
+}
+
+/// Whether `area` is at least `(width, height)`.
+fn fits_min_size(area: Rect, (min_width, min_height): (u16, u16)) -> bool {
+    area.width >= min_width && area.height >= min_height
+}
+
+fn render_too_small(frame: &mut Frame, area: Rect, (min_width, min_height): (u16, u16)) {
+    let message = Paragraph::new(vec![
+        Line::raw(format!(
+            "Terminal too small (need {min_width}x{min_height})"
+        )),
+        Line::raw(format!("Current: {}x{}", area.width, area.height)),
+    ])
+    .alignment(Alignment::Center)
+    .style(theme::style_warning())
+    .wrap(Wrap { trim: true });
+    let [middle] = Layout::vertical([Constraint::Length(2)])
+        .flex(Flex::Center)
+        .areas(area);
+    frame.render_widget(message, middle);
+}
+
+/// Items the list should show for the panel's search, tag and 2FA filters, in display order.
+fn visible_items<'a>(
+    service: &'a VaultService,
+    panel: &ItemsPanel,
+    group_id: Option<Uuid>,
+    totp_first: bool,
+) -> Vec<&'a Item> {
+    let mut items = service
+        .filter_items(panel.search_query(), panel.tag_filter(), group_id)
+        .unwrap_or_default();
+    if panel.totp_only() {
+        items.retain(|item| item.has_totp());
+    }
+    if totp_first {
+        vault_service::sort_totp_first(&mut items);
+    }
+    items
+}
+
+/// Whether a copy waits for a yes/no first. `secret` copies are always asked about
+/// when confirmation is on; others only without `confirm_copy_secrets_only`.
+fn copy_needs_confirm(config: &AppConfig, secret: bool, already_confirmed: bool) -> bool {
+    config.confirm_clipboard_copy
+        && !already_confirmed
+        && (secret || !config.confirm_copy_secrets_only)
+}
+
+/// The text actually placed on the clipboard.
+fn copy_payload(text: &str, trim: bool) -> &str {
+    if trim {
+        text.trim_end()
+    } else {
+        text
+    }
+}
+
+/// Status shown after a successful copy.
+fn copy_status(label: &str, sensitive: bool, clear_secs: u64) -> String {
+    if sensitive {
+        format!("{label} copied (clears in {clear_secs}s)")
+    } else {
+        format!("{label} copied")
+    }
+}
+
+/// Re-authentication is needed when gating is on and the last one is outside the window.
+fn reauth_needed(
+    required: bool,
+    last_reauth: Option<Instant>,
+    window_secs: u64,
+    now: Instant,
+) -> bool {
+    if !required {
+        return false;
+    }
+    match last_reauth {
+        Some(at) if window_secs > 0 => now.duration_since(at).as_secs() >= window_secs,
+        _ => true,
+    }
+}
+
+/// Build a fresh main screen with the config-driven display settings applied.
+fn build_main_screen(config: &AppConfig) -> MainScreen {
+    let mut screen = MainScreen::new();
+    screen
+        .details_panel
+        .set_mask_when_unfocused(config.mask_details_when_unfocused);
+    screen
+        .details_panel
+        .set_show_password_length(config.show_password_length);
+    screen
+        .details_panel
+        .set_reveal_flash_ms(config.reveal_flash_ms);
+    screen
+        .details_panel
+        .set_reveal_requires_auth(config.require_password_to_reveal || config.seal_mode);
+    screen.set_lock_key(config.lock_key);
+    screen.set_status_verbosity(config.status_verbosity);
+    screen.groups_panel.set_wraparound(config.list_wraparound);
+    screen
+        .groups_panel
+        .set_sort_alphabetically(config.sort_groups_alphabetically);
+    screen.items_panel.set_wraparound(config.list_wraparound);
+    screen
+        .items_panel
+        .set_enter_action(config.item_enter_action);
+    screen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::MemoryClipboard;
+    use crate::storage::archive::ArchiveFormat;
+    use crossterm::event::{KeyEvent, KeyModifiers};
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    /// Build an app with a freshly created, unlocked vault using fast KDF params.
+    fn test_app(config: AppConfig) -> (TempDir, App) {
+        let (dir, app, _) = test_app_with_clipboard(config);
+        (dir, app)
+    }
+
+    /// `test_app` with an in-memory clipboard the test can read back.
+    fn test_app_with_clipboard(config: AppConfig) -> (TempDir, App, MemoryClipboard) {
+        let dir = TempDir::new().unwrap();
+        let config = AppConfig {
+            vault_path: dir.path().join("test.vltr"),
+            kdf_memory_cost_kib: 1024,
+            kdf_time_cost: 1,
+            kdf_parallelism: 1,
+            ..config
+        };
+        let memory = MemoryClipboard::default();
+        let mut app = App::with_clipboard(config, Box::new(memory.clone()));
+        app.handle_action(Action::CreateVault("password".to_string()));
+        (dir, app, memory)
+    }
+
+    fn add_item(app: &mut App, title: &str) -> Uuid {
+        app.vault_service
+            .create_item(ItemDraft {
+                title: title.to_string(),
+                ..Default::default()
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_auto_lock_step_prompts_only_when_asked_and_dirty() {
+        let secs = Duration::from_secs;
+        // Not idle long enough, or auto-lock off.
+        assert_eq!(
+            auto_lock_step(secs(60), 300, true, true, None),
+            AutoLockStep::Wait
+        );
+        assert_eq!(
+            auto_lock_step(secs(900), 0, true, true, None),
+            AutoLockStep::Wait
+        );
+        // Without the option, or with nothing to save, it locks straight away.
+        assert_eq!(
+            auto_lock_step(secs(301), 300, false, true, None),
+            AutoLockStep::Lock
+        );
+        assert_eq!(
+            auto_lock_step(secs(301), 300, true, false, None),
+            AutoLockStep::Lock
+        );
+        // With it, it asks first and waits for an answer.
+        assert_eq!(
+            auto_lock_step(secs(301), 300, true, true, None),
+            AutoLockStep::Prompt
+        );
+        assert_eq!(
+            auto_lock_step(secs(320), 300, true, true, Some(secs(19))),
+            AutoLockStep::Wait
+        );
+        // Nobody answered: save and lock anyway.
+        assert_eq!(
+            auto_lock_step(secs(331), 300, true, true, Some(secs(AUTOLOCK_PROMPT_SECS))),
+            AutoLockStep::Lock
+        );
+    }
+
+    #[test]
+    fn test_autolock_prompt_confirms_into_save_and_lock() {
+        let (_dir, mut app) = test_app(AppConfig {
+            autosave: false,
+            confirm_autolock_save: true,
+            ..AppConfig::default()
+        });
+        add_item(&mut app, "Login");
+        assert!(app.vault_service.is_dirty());
+
+        app.open_autolock_prompt();
+        assert!(app.autolock_prompt_at.is_some());
+        let action = app.handle_input(KeyEvent::from(KeyCode::Char('y')));
+        app.handle_action(action);
+        assert_eq!(app.current_screen, Screen::Lock);
+        assert!(app.autolock_prompt_at.is_none());
+
+        app.handle_action(Action::UnlockVault("password".to_string()));
+        assert_eq!(app.vault_service.items().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_credential_sequence_waits_for_second_press_without_delay() {
+        let now = Instant::now();
+        let seq = CredentialSequence {
+            item_id: Uuid::new_v4(),
+            username_copied_at: now,
+        };
+        assert!(!seq.password_due(now + Duration::from_secs(3600), 0));
+    }
+
+    #[test]
+    fn test_credential_sequence_password_due_after_delay() {
+        let now = Instant::now();
+        let seq = CredentialSequence {
+            item_id: Uuid::new_v4(),
+            username_copied_at: now,
+        };
+        assert!(!seq.password_due(now + Duration::from_secs(4), 5));
+        assert!(seq.password_due(now + Duration::from_secs(5), 5));
+    }
+
+    #[test]
+    fn test_credential_sequence_second_press_copies_password() {
+        let (_dir, mut app, memory) = test_app_with_clipboard(AppConfig::default());
+        let id = app
+            .vault_service
+            .create_item(ItemDraft {
+                title: "Site".to_string(),
+                username: "alice".to_string(),
+                password: "hunter2".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        // Simulate the first stage having completed.
+        app.credential_sequence = Some(CredentialSequence {
+            item_id: id,
+            username_copied_at: Instant::now(),
+        });
+        app.handle_action(Action::CopyCredentialSequence(id));
+        assert!(app.credential_sequence.is_none());
+        assert_eq!(memory.contents(), "hunter2");
+
+        // A press for a different item restarts the sequence rather than copying the password.
+        app.credential_sequence = Some(CredentialSequence {
+            item_id: Uuid::new_v4(),
+            username_copied_at: Instant::now(),
+        });
+        app.handle_action(Action::CopyCredentialSequence(id));
+        assert_eq!(
+            app.credential_sequence.as_ref().map(|seq| seq.item_id),
+            Some(id)
+        );
+        assert_eq!(memory.contents(), "alice");
+    }
+
+    /// Answer yes to the open confirmation dialog and carry out its action.
+    fn answer_yes(app: &mut App) {
+        let action = app.handle_input(KeyEvent::from(KeyCode::Char('y')));
+        app.handle_action(action);
+    }
+
+    #[test]
+    fn test_credential_sequence_asks_before_each_copy() {
+        let (_dir, mut app, memory) = test_app_with_clipboard(AppConfig {
+            confirm_clipboard_copy: true,
+            ..AppConfig::default()
+        });
+        let id = app
+            .vault_service
+            .create_item(ItemDraft {
+                title: "Site".to_string(),
+                username: "alice".to_string(),
+                password: "hunter2".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        app.handle_action(Action::CopyCredentialSequence(id));
+        assert!(matches!(app.modal, Modal::Confirm(_)));
+        assert_eq!(memory.contents(), "");
+        answer_yes(&mut app);
+        assert_eq!(memory.contents(), "alice");
+        assert!(app.credential_sequence.is_some());
+
+        app.handle_action(Action::CopyCredentialSequence(id));
+        assert!(matches!(app.modal, Modal::Confirm(_)));
+        assert_eq!(memory.contents(), "alice");
+        answer_yes(&mut app);
+        assert_eq!(memory.contents(), "hunter2");
+    }
+
+    #[test]
+    fn test_new_item_group_precedence() {
+        let work = Group::new("Work".to_string(), None);
+        let home = Group::new("Home".to_string(), None);
+        let groups = [work.clone(), home.clone()];
+
+        assert_eq!(
+            new_item_group(&groups, Some(home.id), None, false),
+            Some(home.id)
+        );
+        assert_eq!(new_item_group(&groups, None, None, false), None);
+        assert_eq!(
+            new_item_group(&groups, Some(home.id), Some("work"), false),
+            Some(work.id)
+        );
+        assert_eq!(
+            new_item_group(&groups, Some(home.id), Some("Work"), true),
+            Some(home.id)
+        );
+        assert_eq!(
+            new_item_group(&groups, None, Some("Work"), true),
+            Some(work.id)
+        );
+        assert_eq!(
+            new_item_group(&groups, Some(home.id), Some("Gone"), false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_reauth_needed_gating() {
+        let now = Instant::now();
+        assert!(!reauth_needed(false, None, 300, now));
+        assert!(reauth_needed(true, None, 300, now));
+        assert!(!reauth_needed(
+            true,
+            Some(now),
+            300,
+            now + Duration::from_secs(299)
+        ));
+        assert!(reauth_needed(
+            true,
+            Some(now),
+            300,
+            now + Duration::from_secs(300)
+        ));
+        // A zero window asks on every reveal.
+        assert!(reauth_needed(true, Some(now), 0, now));
+    }
+
+    #[test]
+    fn test_reveal_prompts_and_requires_correct_password() {
+        let (_dir, mut app) = test_app(AppConfig {
+            require_password_to_reveal: true,
+            ..AppConfig::default()
+        });
+        let id = add_item(&mut app, "Site");
+        app.refresh_details(Some(id));
+
+        app.handle_action(Action::RequestReveal(id));
+        assert!(matches!(app.modal, Modal::PasswordPrompt(_)));
+
+        app.handle_action(Action::SubmitReauth("wrong".to_string()));
+        assert!(matches!(app.modal, Modal::PasswordPrompt(_)));
+        assert!(app.reauth_at.is_none());
+
+        app.handle_action(Action::SubmitReauth("password".to_string()));
+        assert!(matches!(app.modal, Modal::None));
+        assert!(app.reauth_at.is_some());
+
+        // Within the window, a further reveal skips the prompt.
+        app.handle_action(Action::RequestReveal(id));
+        assert!(matches!(app.modal, Modal::None));
+    }
+
+    #[test]
+    fn test_sensitive_item_confirms_each_reveal_and_copy() {
+        let (_dir, mut app) = test_app(AppConfig {
+            confirm_clipboard_copy: true,
+            ..AppConfig::default()
+        });
+        let id = app
+            .vault_service
+            .create_item(ItemDraft {
+                title: "Root".to_string(),
+                password: "toor".to_string(),
+                sensitive: true,
+                ..Default::default()
+            })
+            .unwrap();
+        app.refresh_details(Some(id));
+
+        for request in [Action::RequestReveal(id), Action::CopyPassword(id)] {
+            app.handle_action(request.clone());
+            let Modal::Confirm(ref dialog) = app.modal else {
+                panic!("expected a confirmation for {request:?}");
+            };
+            assert!(dialog.message().contains("sensitive value for 'Root'"));
+            let confirmed = app.handle_input(KeyEvent::from(KeyCode::Char('y')));
+            assert!(matches!(
+                confirmed,
+                Action::ConfirmedSensitive(ref inner)
+                    if std::mem::discriminant(&**inner) == std::mem::discriminant(&request)
+            ));
+            app.handle_action(confirmed);
+            // The sensitive prompt names the item, so no second copy prompt follows.
+            assert!(matches!(app.modal, Modal::None));
+        }
+        assert!(app.main_screen.details_panel.is_password_shown());
+
+        // Confirmation is asked again the next time.
+        app.handle_action(Action::CopyPassword(id));
+        assert!(matches!(app.modal, Modal::Confirm(_)));
+    }
+
+    #[test]
+    fn test_sensitive_item_confirms_edit_and_copies_from_the_form() {
+        let (_dir, mut app, memory) = test_app_with_clipboard(AppConfig::default());
+        let id = app
+            .vault_service
+            .create_item(ItemDraft {
+                title: "Root".to_string(),
+                password: "toor".to_string(),
+                sensitive: true,
+                ..Default::default()
+            })
+            .unwrap();
+
+        app.handle_action(Action::OpenEditItemForm(id));
+        let Modal::Confirm(ref dialog) = app.modal else {
+            panic!("expected a confirmation before the form opens");
+        };
+        assert!(dialog.message().contains("Edit sensitive value for 'Root'"));
+        answer_yes(&mut app);
+        assert!(matches!(app.modal, Modal::ItemForm(_)));
+
+        // Ctrl+Y on the password field, as the form emits it.
+        app.handle_action(Action::copy_secret(
+            "Root",
+            "toor".to_string(),
+            "Password",
+            true,
+        ));
+        assert!(matches!(app.modal, Modal::Confirm(_)));
+        assert_eq!(memory.contents(), "");
+        answer_yes(&mut app);
+        assert_eq!(memory.contents(), "toor");
+        assert!(matches!(app.modal, Modal::ItemForm(_)));
+    }
+
+    #[test]
+    fn test_copy_waits_for_confirmation_when_enabled() {
+        let (_dir, mut app) = test_app(AppConfig {
+            confirm_clipboard_copy: true,
+            ..AppConfig::default()
+        });
+        app.main_screen.clear_status();
+
+        app.handle_action(Action::copy_secret(
+            "Bank",
+            "secret".to_string(),
+            "Password",
+            true,
+        ));
+        assert!(matches!(app.modal, Modal::Confirm(_)));
+        // Nothing was copied yet, so no copy outcome is reported.
+        assert!(app.main_screen.status_message().is_none());
+
+        let confirmed = app.handle_input(KeyEvent::from(KeyCode::Char('y')));
+        app.handle_action(confirmed);
+        assert!(matches!(app.modal, Modal::None));
+        assert!(app.main_screen.status_message().is_some());
+    }
+
+    #[test]
+    fn test_copy_confirmation_can_be_limited_to_secrets() {
+        let mut config = AppConfig::default();
+        assert!(!copy_needs_confirm(&config, true, false));
+        config.confirm_clipboard_copy = true;
+        assert!(copy_needs_confirm(&config, true, false));
+        assert!(copy_needs_confirm(&config, false, false));
+        assert!(!copy_needs_confirm(&config, true, true));
+        config.confirm_copy_secrets_only = true;
+        assert!(copy_needs_confirm(&config, true, false));
+        assert!(!copy_needs_confirm(&config, false, false));
+
+        let (_dir, mut app) = test_app(config);
+        let id = app
+            .vault_service
+            .create_item(ItemDraft {
+                title: "Mail".to_string(),
+                username: "alice".to_string(),
+                password: "hunter2".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        app.handle_action(Action::CopyPassword(id));
+        let Modal::Confirm(ref dialog) = app.modal else {
+            panic!("expected a confirmation for the password");
+        };
+        assert_eq!(dialog.message(), "Copy password for 'Mail'?");
+
+        app.handle_action(Action::CloseModal);
+        app.handle_action(Action::CopyUsername(id));
+        assert!(matches!(app.modal, Modal::None));
+
+        // Whether a copy is a secret does not depend on whether it is auto-cleared.
+        app.handle_action(Action::copy_text(
+            "Mail",
+            "alice".to_string(),
+            "Username",
+            true,
+        ));
+        assert!(matches!(app.modal, Modal::None));
+        app.handle_action(Action::copy_secret(
+            "Mail",
+            "hunter2".to_string(),
+            "Password",
+            false,
+        ));
+        assert!(matches!(app.modal, Modal::Confirm(_)));
+        app.handle_action(Action::CloseModal);
+
+        // A formatted copy is a secret only when the template includes the password.
+        app.config.item_copy_template = "{title} {username}".to_string();
+        app.handle_action(Action::CopyFormattedItem(id));
+        assert!(matches!(app.modal, Modal::None));
+        app.config.item_copy_template = "{username} {password}".to_string();
+        app.handle_action(Action::CopyFormattedItem(id));
+        assert!(matches!(app.modal, Modal::Confirm(_)));
+    }
+
+    #[test]
+    fn test_copy_is_immediate_when_confirmation_disabled() {
+        let (_dir, mut app) = test_app(AppConfig::default());
+        app.main_screen.clear_status();
+
+        app.handle_action(Action::copy_text(
+            "Mail",
+            "alice".to_string(),
+            "Username",
+            true,
+        ));
+        assert!(matches!(app.modal, Modal::None));
+        assert!(app.main_screen.status_message().is_some());
+    }
+
+    #[test]
+    fn test_apply_saved_filter_sets_query_tag_and_scope() {
+        let (_dir, mut app) = test_app(AppConfig {
+            saved_filters: vec![SavedFilter {
+                name: "Work".to_string(),
+                query: "jen".to_string(),
+                tag: Some("work".to_string()),
+                scope: FilterScope::All,
+            }],
+            ..AppConfig::default()
+        });
+        for (title, tags) in [
+            ("Jenkins", vec!["work"]),
+            ("Jenny", vec![]),
+            ("Jira", vec!["work"]),
+        ] {
+            app.vault_service
+                .create_item(ItemDraft {
+                    title: title.to_string(),
+                    tags: tags.into_iter().map(String::from).collect(),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        app.refresh_ui();
+        assert_eq!(app.main_screen.items_panel.item_count(), 3);
+
+        app.handle_action(Action::OpenSavedFilters);
+        let action = app.handle_input(KeyEvent::from(KeyCode::Enter));
+        app.handle_action(action);
+
+        let panel = &app.main_screen.items_panel;
+        assert!(matches!(app.modal, Modal::None));
+        assert_eq!(panel.search_query(), "jen");
+        assert_eq!(panel.tag_filter(), Some("work"));
+        assert_eq!(panel.item_count(), 1);
+        let selected = app.main_screen.selected_item_id().unwrap();
+        assert_eq!(
+            app.vault_service.get_item(selected).unwrap().title,
+            "Jenkins"
+        );
+    }
+
+    #[test]
+    fn test_save_current_filter_persists_to_config_path() {
+        let (dir, app) = test_app(AppConfig::default());
+        let path = dir.path().join("config.toml");
+        let mut app = app.with_config_path(path.clone());
+
+        app.handle_action(Action::SaveCurrentFilter);
+        assert!(app.config.saved_filters.is_empty());
+
+        app.main_screen
+            .items_panel
+            .set_filter("admin".to_string(), None);
+        app.handle_action(Action::SaveCurrentFilter);
+        app.handle_action(Action::SaveCurrentFilter);
+
+        let saved = AppConfig::load_from(&path).unwrap().saved_filters;
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].name, "admin");
+        assert_eq!(saved[0].scope, FilterScope::All);
+    }
+
+    /// Two groups with one "mail" item each, plus a search for "mail" in the first group.
+    fn searched_app(clear_on_change: bool) -> (TempDir, App, Uuid) {
+        let (dir, mut app) = test_app(AppConfig {
+            clear_search_on_group_change: clear_on_change,
+            ..AppConfig::default()
+        });
+        let first = app
+            .vault_service
+            .create_group("A".to_string(), None)
+            .unwrap();
+        let second = app
+            .vault_service
+            .create_group("B".to_string(), None)
+            .unwrap();
+        for (title, group) in [("Mail A", first), ("Mail B", second), ("Bank B", second)] {
+            app.vault_service
+                .create_item(ItemDraft {
+                    title: title.to_string(),
+                    group_id: Some(group),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        app.refresh_ui();
+        app.handle_action(Action::SelectGroup(Some(first)));
+        app.main_screen
+            .items_panel
+            .set_filter("mail".to_string(), None);
+        app.refresh_items(Some(first));
+        assert_eq!(app.main_screen.items_panel.item_count(), 1);
+        (dir, app, second)
+    }
+
+    #[test]
+    fn test_group_change_clears_search_by_default() {
+        let (_dir, mut app, second) = searched_app(true);
+        app.handle_action(Action::SelectGroup(Some(second)));
+
+        assert_eq!(app.main_screen.items_panel.search_query(), "");
+        assert_eq!(app.main_screen.items_panel.item_count(), 2);
+    }
+
+    #[test]
+    fn test_group_change_keeps_search_when_disabled() {
+        let (_dir, mut app, second) = searched_app(false);
+        app.handle_action(Action::SelectGroup(Some(second)));
+
+        assert_eq!(app.main_screen.items_panel.search_query(), "mail");
+        assert_eq!(app.main_screen.items_panel.item_count(), 1);
+    }
+
+    #[test]
+    fn test_seal_mode_asks_for_password_before_each_copy() {
+        let (_dir, mut app) = test_app(AppConfig {
+            seal_mode: true,
+            ..AppConfig::default()
+        });
+        let id = add_item(&mut app, "Site");
+        app.main_screen.clear_status();
+
+        app.handle_action(Action::CopyPassword(id));
+        assert!(matches!(app.modal, Modal::PasswordPrompt(_)));
+        app.handle_action(Action::SubmitReauth("wrong".to_string()));
+        assert!(matches!(app.modal, Modal::PasswordPrompt(_)));
+        assert_eq!(app.main_screen.status_message(), None);
+        assert!(app.copy_log.is_empty());
+
+        app.handle_action(Action::SubmitReauth("password".to_string()));
+        assert!(matches!(app.modal, Modal::None));
+        // The copy ran: it either succeeded or reported the missing clipboard.
+        assert!(app.main_screen.status_message().is_some());
+        assert!(!app.sensitive_confirmed);
+
+        // No window: the next copy and reveal ask again.
+        app.handle_action(Action::CopyPassword(id));
+        assert!(matches!(app.modal, Modal::PasswordPrompt(_)));
+        app.handle_action(Action::CloseModal);
+        app.refresh_details(Some(id));
+        app.handle_action(Action::RequestReveal(id));
+        assert!(matches!(app.modal, Modal::PasswordPrompt(_)));
+        app.handle_action(Action::SubmitReauth("password".to_string()));
+        assert!(matches!(app.modal, Modal::None));
+        assert!(app.main_screen.details_panel.is_password_shown());
+    }
+
+    #[test]
+    fn test_seal_mode_asks_before_copying_a_secret_from_the_form() {
+        let (_dir, mut app, memory) = test_app_with_clipboard(AppConfig {
+            seal_mode: true,
+            ..AppConfig::default()
+        });
+        let id = app
+            .vault_service
+            .create_item(ItemDraft {
+                title: "Site".to_string(),
+                password: "hunter2".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        app.handle_action(Action::OpenEditItemForm(id));
+        app.handle_action(Action::SubmitReauth("password".to_string()));
+        assert!(matches!(app.modal, Modal::ItemForm(_)));
+
+        for _ in 0..2 {
+            app.handle_input(KeyEvent::from(KeyCode::Tab));
+        }
+        let copy = app.handle_input(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL));
+        app.handle_action(copy.clone());
+        assert!(matches!(app.modal, Modal::PasswordPrompt(_)));
+        assert_eq!(memory.contents(), "");
+
+        // Cancelling goes back to the form; the right password copies and returns there.
+        app.handle_action(Action::CloseModal);
+        assert!(matches!(app.modal, Modal::ItemForm(_)));
+        app.handle_action(copy);
+        app.handle_action(Action::SubmitReauth("password".to_string()));
+        assert!(matches!(app.modal, Modal::ItemForm(_)));
+        assert_eq!(memory.contents(), "hunter2");
+    }
+
+    #[test]
+    fn test_reauth_failures_escalate_to_lock() {
+        let (_dir, mut app) = test_app(AppConfig {
+            max_reveal_reauth_failures: 3,
+            ..AppConfig::default()
+        });
+        assert!(matches!(app.record_reauth_failure(), Action::None));
+        assert!(matches!(app.record_reauth_failure(), Action::None));
+        assert!(matches!(app.record_reauth_failure(), Action::Lock));
+
+        // Unlimited when set to zero.
+        app.config.max_reveal_reauth_failures = 0;
+        assert!(matches!(app.record_reauth_failure(), Action::None));
+    }
+
+    #[test]
+    fn test_wrong_reauth_passwords_lock_the_vault() {
+        let (_dir, mut app) = test_app(AppConfig {
+            require_password_to_reveal: true,
+            max_reveal_reauth_failures: 2,
+            ..AppConfig::default()
+        });
+        let id = add_item(&mut app, "Site");
+        app.refresh_details(Some(id));
+
+        app.handle_action(Action::RequestReveal(id));
+        app.handle_action(Action::SubmitReauth("wrong".to_string()));
+        assert!(app.vault_service.is_unlocked());
+        // A success in between resets the counter.
+        app.handle_action(Action::SubmitReauth("password".to_string()));
+        assert_eq!(app.reauth_failures, 0);
+
+        app.reauth_at = None;
+        app.handle_action(Action::RequestReveal(id));
+        app.handle_action(Action::SubmitReauth("wrong".to_string()));
+        app.handle_action(Action::SubmitReauth("wrong".to_string()));
+        assert!(!app.vault_service.is_unlocked());
+        assert!(matches!(app.current_screen, Screen::Lock));
+
+        app.handle_action(Action::UnlockVault("password".to_string()));
+        assert_eq!(app.reauth_failures, 0);
+    }
+
+    #[test]
+    fn test_copy_status_matches_label_and_sensitivity() {
+        assert_eq!(
+            copy_status("Password", true, 30),
+            "Password copied (clears in 30s)"
+        );
+        assert_eq!(copy_status("URL", false, 30), "URL copied");
+    }
+
+    #[test]
+    fn test_copy_text_reports_label_or_clipboard_error() {
+        let (_dir, mut app) = test_app(AppConfig::default());
+        app.main_screen.clear_status();
+
+        let copied = app.copy_text("Site", "https://example.com", "URL", false);
+        let status = app.main_screen.status_message().unwrap().to_string();
+        if copied {
+            assert_eq!(status, "URL copied");
+        } else {
+            // Headless environments have no clipboard; the failure is still reported.
+            assert!(status.contains("clipboard"), "{status}");
+        }
+    }
+
+    #[test]
+    fn test_qr_of_a_password_is_gated_like_a_reveal() {
+        let (_dir, mut app) = test_app(AppConfig {
+            require_password_to_reveal: true,
+            ..AppConfig::default()
+        });
+        let id = app
+            .vault_service
+            .create_item(ItemDraft {
+                title: "Bank".to_string(),
+                username: "alice".to_string(),
+                password: "hunter2".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        app.handle_action(Action::ChooseQrField(id));
+        assert!(matches!(app.modal, Modal::FilterPicker(_)));
+        app.handle_action(Action::RequestQr(id, QrField::Password));
+        assert!(matches!(app.modal, Modal::PasswordPrompt(_)));
+        app.handle_action(Action::SubmitReauth("password".to_string()));
+        #[cfg(feature = "qr")]
+        assert!(matches!(app.modal, Modal::Qr(_)));
+        #[cfg(not(feature = "qr"))]
+        assert!(matches!(app.modal, Modal::None));
+
+        // The username is not secret and needs no password.
+        app.reauth_at = None;
+        app.handle_action(Action::CloseModal);
+        app.handle_action(Action::RequestQr(id, QrField::Username));
+        assert!(!matches!(app.modal, Modal::PasswordPrompt(_)));
+    }
+
+    #[test]
+    fn test_copy_log_records_field_but_never_the_value() {
+        let (_dir, mut app, memory) = test_app_with_clipboard(AppConfig::default());
+        let id = app
+            .vault_service
+            .create_item(ItemDraft {
+                title: "Bank".to_string(),
+                password: "hunter2".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        let other = add_item(&mut app, "Mail");
+        app.refresh_ui();
+        app.main_screen.items_panel.select_item(other);
+
+        app.handle_action(Action::CopyPassword(id));
+        assert_eq!(memory.contents(), "hunter2");
+        // A register is copied under the item it was yanked from, not the selection.
+        app.handle_action(Action::YankToRegister('a', id));
+        app.handle_action(Action::CopyRegister('a'));
+
+        let records: Vec<_> = app.copy_log.records().collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].source, "Bank");
+        assert_eq!(records[0].field, "Password");
+        assert_eq!(records[1].source, "Bank");
+        assert_eq!(records[1].field, "Register \"a");
+        assert!(records
+            .iter()
+            .all(|r| !r.source.contains("hunter2") && !r.field.contains("hunter2")));
+
+        app.handle_action(Action::OpenCopyLog);
+        assert!(matches!(app.modal, Modal::CopyLog(_)));
+        app.handle_action(Action::Lock);
+        assert!(app.copy_log.is_empty());
+    }
+
+    #[test]
+    fn test_confirmed_form_copy_restores_the_form() {
+        let (_dir, mut app) = test_app(AppConfig {
+            confirm_clipboard_copy: true,
+            ..AppConfig::default()
+        });
+        app.handle_action(Action::OpenNewItemForm);
+        app.handle_action(Action::copy_text(
+            "New item",
+            "draft".to_string(),
+            "Title",
+            false,
+        ));
+        assert!(matches!(app.modal, Modal::Confirm(_)));
+
+        app.handle_action(Action::CloseModal);
+        assert!(matches!(app.modal, Modal::ItemForm(_)));
+    }
+
+    fn render_to_text(app: &App, width: u16, height: u16) -> String {
+        crate::ui::render_rows(width, height, |frame| app.render(frame)).concat()
+    }
+
+    #[test]
+    fn test_tiny_terminal_shows_size_message() {
+        let (_dir, app) = test_app(AppConfig::default());
+
+        let text = render_to_text(&app, 59, 24);
+        assert!(text.contains("Terminal too small (need 60x20)"));
+
+        let text = render_to_text(&app, 80, 24);
+        assert!(!text.contains("Terminal too small"));
+        assert!(text.contains("Items"));
+    }
+
+    #[test]
+    fn test_onboarding_hints_shown_only_for_empty_vault() {
+        let (_dir, mut app) = test_app(AppConfig::default());
+        assert!(render_to_text(&app, 100, 30).contains("Your vault is empty"));
+
+        // Dismissed by a key, which is still handled as usual.
+        let action = app.handle_input(KeyEvent::from(KeyCode::Char('g')));
+        assert!(matches!(action, Action::OpenNewGroupForm));
+        assert!(!render_to_text(&app, 100, 30).contains("Your vault is empty"));
+
+        // Back after a lock while the vault is still empty, gone once it has an item.
+        app.handle_action(Action::Lock);
+        app.handle_action(Action::UnlockVault("password".to_string()));
+        assert!(render_to_text(&app, 100, 30).contains("Your vault is empty"));
+        add_item(&mut app, "Login");
+        app.refresh_ui();
+        assert!(!render_to_text(&app, 100, 30).contains("Your vault is empty"));
+    }
+
+    #[test]
+    fn test_lock_discards_all_unlocked_state() {
+        let (_dir, mut app) = test_app(AppConfig {
+            lock_key: Some('L'),
+            ..AppConfig::default()
+        });
+        let id = add_item(&mut app, "Login");
+        app.refresh_ui();
+        let action = app.handle_input(KeyEvent::from(KeyCode::Char('L')));
+        assert!(matches!(action, Action::Lock));
+
+        app.handle_action(Action::OpenEditItemForm(id));
+        app.handle_action(Action::OpenPasswordGenerator);
+        assert!(app.stashed_item_form.is_some());
+        app.handle_action(Action::Lock);
+
+        assert!(!app.vault_service.is_unlocked());
+        assert!(!app.vault_service.holds_secrets());
+        assert!(matches!(app.current_screen, Screen::Lock));
+        assert!(matches!(app.modal, Modal::None));
+        assert!(app.stashed_item_form.is_none());
+        assert!(app.credential_sequence.is_none());
+        assert!(app.reauth_at.is_none());
+        assert!(app.deleted_item.is_none());
+        assert_eq!(app.main_screen.selected_item_id(), None);
+    }
+
+    #[test]
+    fn test_undo_restores_deleted_item_within_window() {
+        let (_dir, mut app) = test_app(AppConfig::default());
+        let id = add_item(&mut app, "Doomed");
+        app.refresh_ui();
+
+        app.handle_action(Action::DeleteItem(id));
+        assert!(app.vault_service.get_item(id).is_err());
+
+        let action = app.handle_input(KeyEvent::from(KeyCode::Char('u')));
+        assert!(matches!(action, Action::UndoDelete));
+        app.handle_action(action);
+        assert_eq!(app.vault_service.get_item(id).unwrap().title, "Doomed");
+        assert_eq!(app.main_screen.selected_item_id(), Some(id));
+        assert!(app.deleted_item.is_none());
+    }
+
+    #[test]
+    fn test_undo_unavailable_after_window() {
+        let (_dir, mut app) = test_app(AppConfig::default());
+        let id = add_item(&mut app, "Doomed");
+        app.refresh_ui();
+        app.handle_action(Action::DeleteItem(id));
+
+        if let Some(ref mut deleted) = app.deleted_item {
+            deleted.deleted_at -= Duration::from_secs(UNDO_DELETE_SECS);
+        }
+        assert!(!app.undo_available(Instant::now()));
+        assert!(!matches!(
+            app.handle_input(KeyEvent::from(KeyCode::Char('u'))),
+            Action::UndoDelete
+        ));
+
+        app.handle_action(Action::UndoDelete);
+        assert!(app.vault_service.get_item(id).is_err());
+    }
+
+    #[test]
+    fn test_effective_auto_lock_prefers_vault_override() {
+        assert_eq!(effective_auto_lock_secs(300, None), 300);
+        assert_eq!(effective_auto_lock_secs(300, Some(60)), 60);
+        assert_eq!(effective_auto_lock_secs(300, Some(0)), 300);
+
+        let (_dir, mut app) = test_app(AppConfig {
+            auto_lock_secs: 300,
+            ..AppConfig::default()
+        });
+        assert_eq!(app.auto_lock_secs(), 300);
+        app.handle_action(Action::SaveVaultSettings {
+            auto_lock_override: Some(30),
+            record_password_history: true,
+        });
+        assert_eq!(app.auto_lock_secs(), 30);
+
+        // Locked, the override is sealed in the payload and the global applies.
+        app.handle_action(Action::Lock);
+        assert_eq!(app.auto_lock_secs(), 300);
+    }
+
+    #[test]
+    fn test_discarding_an_edited_form_asks_first() {
+        let (_dir, mut app) = test_app(AppConfig::default());
+        app.handle_action(Action::OpenNewGroupForm);
+        let esc = KeyEvent::from(KeyCode::Esc);
+        let action = app.handle_input(esc);
+        assert!(matches!(action, Action::CloseModal));
+
+        app.handle_action(Action::OpenNewGroupForm);
+        app.handle_input(KeyEvent::from(KeyCode::Char('W')));
+        let action = app.handle_input(esc);
+        app.handle_action(action);
+        assert!(matches!(app.modal, Modal::Confirm(_)));
+
+        // "No" goes back to the form with the edit intact.
+        let action = app.handle_input(KeyEvent::from(KeyCode::Char('n')));
+        app.handle_action(action);
+        assert!(matches!(app.modal, Modal::GroupForm(ref form) if form.is_dirty()));
+
+        let action = app.handle_input(esc);
+        app.handle_action(action);
+        let action = app.handle_input(KeyEvent::from(KeyCode::Char('y')));
+        app.handle_action(action);
+        assert!(matches!(app.modal, Modal::None));
+        assert!(app.stashed_group_form.is_none());
+    }
+
+    #[test]
+    fn test_changes_view_lists_unsaved_items() {
+        let (_dir, mut app) = test_app(AppConfig {
+            autosave: false,
+            ..AppConfig::default()
+        });
+        add_item(&mut app, "Unsaved");
+
+        app.handle_action(Action::OpenChanges);
+        assert!(matches!(app.modal, Modal::Changes(_)));
+        assert!(render_to_text(&app, 100, 30).contains("+ item 'Unsaved'"));
+    }
+
+    #[test]
+    fn test_launch_command_needs_opt_in_and_confirmation() {
+        let (_dir, mut app) = test_app(AppConfig::default());
+        let id = app
+            .vault_service
+            .create_item(ItemDraft {
+                title: "Server".to_string(),
+                username: "deploy".to_string(),
+                launch_command: Some("ssh {username}@host".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        app.main_screen.clear_status();
+        app.handle_action(Action::RunLaunchCommand(id));
+        assert!(matches!(app.modal, Modal::None));
+        assert!(app
+            .main_screen
+            .status_message()
+            .is_some_and(|m| m.contains("enable_launch_commands")));
+
+        app.config.enable_launch_commands = true;
+        app.handle_action(Action::RunLaunchCommand(id));
+        assert!(
+            matches!(app.modal, Modal::Confirm(ref dialog) if dialog.message() == "Run `ssh deploy@host`?")
+        );
+    }
+
+    #[test]
+    fn test_event_log_keeps_status_messages_after_they_expire() {
+        let (_dir, mut app) = test_app(AppConfig::default());
+        app.main_screen
+            .set_error("Save failed: disk full".to_string());
+        app.main_screen.clear_status();
+
+        app.handle_action(Action::OpenEventLog);
+        assert!(matches!(app.modal, Modal::EventLog(_)));
+        let last = app.event_log.events().next_back().unwrap();
+        assert_eq!(last.text, "Save failed: disk full");
+        assert_eq!(last.level, StatusLevel::Error);
+        assert!(render_to_text(&app, 100, 30).contains("Save failed: disk full"));
+    }
+
+    #[test]
+    fn test_min_size_boundaries() {
+        let min = (60, 20);
+        assert!(fits_min_size(Rect::new(0, 0, 60, 20), min));
+        assert!(!fits_min_size(Rect::new(0, 0, 59, 20), min));
+        assert!(!fits_min_size(Rect::new(0, 0, 60, 19), min));
+        assert!(fits_min_size(Rect::new(0, 0, 200, 60), min));
+        assert!(fits_min_size(Rect::new(0, 0, 0, 0), (0, 0)));
+    }
+
+    #[test]
+    fn test_discard_changes_confirms_then_reloads() {
+        let (_dir, mut app) = test_app(AppConfig::default());
+        add_item(&mut app, "Unsaved");
+        app.refresh_ui();
+        assert_eq!(app.main_screen.items_panel.item_count(), 1);
+
+        app.handle_action(Action::DiscardChanges);
+        assert!(matches!(app.modal, Modal::Confirm(_)));
+
+        let confirmed = app.handle_input(KeyEvent::from(KeyCode::Char('y')));
+        app.handle_action(confirmed);
+        assert!(matches!(app.modal, Modal::None));
+        assert!(!app.vault_service.is_dirty());
+        assert!(app.vault_service.items().unwrap().is_empty());
+        assert_eq!(app.main_screen.items_panel.item_count(), 0);
+    }
+
+    #[test]
+    fn test_discard_changes_forgets_the_deleted_item() {
+        let (_dir, mut app) = test_app(AppConfig {
+            autosave: false,
+            ..AppConfig::default()
+        });
+        let id = add_item(&mut app, "Saved");
+        app.vault_service.save().unwrap();
+        app.refresh_ui();
+
+        app.handle_action(Action::DeleteItem(id));
+        assert!(app.deleted_item.is_some());
+        app.handle_action(Action::DiscardChanges);
+        let confirmed = app.handle_input(KeyEvent::from(KeyCode::Char('y')));
+        app.handle_action(confirmed);
+
+        assert!(app.deleted_item.is_none());
+        assert_eq!(app.vault_service.get_item(id).unwrap().title, "Saved");
+        assert!(!app.vault_service.is_dirty());
+    }
+
+    #[test]
+    fn test_selecting_group_shows_its_description() {
+        let (_dir, mut app) = test_app(AppConfig::default());
+        app.handle_action(Action::CreateGroup(
+            "Work".to_string(),
+            None,
+            "Office accounts".to_string(),
+        ));
+        app.handle_action(Action::CreateGroup("Home".to_string(), None, String::new()));
+        let groups = app.vault_service.groups().unwrap().to_vec();
+        let id_of = |name: &str| groups.iter().find(|g| g.name == name).unwrap().id;
+
+        app.main_screen.clear_status();
+        app.handle_action(Action::SelectGroup(Some(id_of("Work"))));
+        assert_eq!(app.main_screen.status_message(), Some("Office accounts"));
+
+        app.main_screen.clear_status();
+        app.handle_action(Action::SelectGroup(Some(id_of("Home"))));
+        assert!(app.main_screen.status_message().is_none());
+    }
+
+    #[test]
+    fn test_autosave_off_defers_writes_until_save() {
+        let (_dir, mut app) = test_app(AppConfig {
+            autosave: false,
+            ..AppConfig::default()
+        });
+        let path = app.vault_service.vault_path().to_path_buf();
+        let before = std::fs::read(&path).unwrap();
+
+        app.handle_action(Action::CreateGroup("Work".to_string(), None, String::new()));
+        assert!(app.vault_service.is_dirty());
+        assert_eq!(std::fs::read(&path).unwrap(), before);
+
+        app.handle_action(Action::Save);
+        assert!(!app.vault_service.is_dirty());
+        assert_ne!(std::fs::read(&path).unwrap(), before);
+    }
+
+    #[test]
+    fn test_restores_last_selection_after_unlock() {
+        let (_dir, mut app) = test_app(AppConfig {
+            restore_last_selection: true,
+            ..AppConfig::default()
+        });
+        let group = app
+            .vault_service
+            .create_group("Work".to_string(), None)
+            .unwrap();
+        add_item(&mut app, "First");
+        let second = app
+            .vault_service
+            .create_item(ItemDraft {
+                title: "Second".to_string(),
+                group_id: Some(group),
+                ..Default::default()
+            })
+            .unwrap();
+        app.refresh_ui();
+        app.main_screen.groups_panel.select_group(group);
+        app.refresh_items(Some(group));
+
+        app.handle_action(Action::Lock);
+        assert_eq!(app.config.last_selected_group, Some(group));
+        assert_eq!(app.config.last_selected_item, Some(second));
+
+        app.handle_action(Action::UnlockVault("password".to_string()));
+        assert_eq!(app.main_screen.selected_group_id(), Some(group));
+        assert_eq!(app.main_screen.selected_item_id(), Some(second));
+        assert_eq!(
+            app.main_screen.details_panel.selected_item_id(),
+            Some(second)
+        );
+    }
+
+    #[test]
+    fn test_restore_falls_back_when_selection_was_deleted() {
+        let (_dir, mut app) = test_app(AppConfig {
+            restore_last_selection: true,
+            last_selected_group: Some(Uuid::new_v4()),
+            last_selected_item: Some(Uuid::new_v4()),
+            ..AppConfig::default()
+        });
+        let first = add_item(&mut app, "First");
+        app.handle_action(Action::Save);
+        app.vault_service.lock();
+
+        app.handle_action(Action::UnlockVault("password".to_string()));
+        assert_eq!(app.main_screen.selected_group_id(), None);
+        assert_eq!(app.main_screen.selected_item_id(), Some(first));
+    }
+
+    #[test]
+    fn test_compact_requires_confirmation() {
+        let (_dir, mut app) = test_app(AppConfig::default());
+        let id = add_item(&mut app, "Site");
+        app.vault_service
+            .update_item(
+                id,
+                ItemDraft {
+                    title: "Site".to_string(),
+                    password: "new".to_string(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        app.vault_service
+            .update_item(
+                id,
+                ItemDraft {
+                    title: "Site".to_string(),
+                    password: "newer".to_string(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        app.main_screen.clear_status();
+        app.handle_action(Action::OpenCompactConfirm);
+        assert!(matches!(app.modal, Modal::None));
+        assert!(app
+            .main_screen
+            .status_message()
+            .is_some_and(|msg| msg.starts_with("Save or discard")));
+
+        app.vault_service.save().unwrap();
+        app.main_screen.clear_status();
+        app.handle_action(Action::OpenCompactConfirm);
+        assert!(matches!(app.modal, Modal::Confirm(_)));
+        assert_eq!(
+            app.vault_service
+                .get_item(id)
+                .unwrap()
+                .password_history
+                .len(),
+            1
+        );
+
+        let confirmed = app.handle_input(KeyEvent::from(KeyCode::Char('y')));
+        app.handle_action(confirmed);
+        assert!(app
+            .vault_service
+            .get_item(id)
+            .unwrap()
+            .password_history
+            .is_empty());
+        assert!(app
+            .main_screen
+            .status_message()
+            .is_some_and(|msg| msg.starts_with("Compacted: 1 history")));
+    }
+
+    #[test]
+    fn test_embedding_past_threshold_warns() {
+        let (dir, mut app) = test_app(AppConfig {
+            embedded_warn_total_bytes: 4,
+            ..AppConfig::default()
+        });
+        let id = add_item(&mut app, "Bank");
+        let source = dir.path().join("codes.txt");
+        std::fs::write(&source, "12345678").unwrap();
+        app.main_screen.clear_status();
+
+        app.handle_action(Action::EmbedAttachment(id, source));
+        assert_eq!(app.vault_service.get_item(id).unwrap().embedded.len(), 1);
+        assert_eq!(
+            app.main_screen.status_level(),
+            Some(crate::ui::screens::main_screen::StatusLevel::Warning)
+        );
+    }
+
+    #[test]
+    fn test_trim_on_copy_removes_trailing_newline() {
+        assert_eq!(copy_payload("hunter2\n", true), "hunter2");
+        assert_eq!(copy_payload(" hunter2 \r\n", true), " hunter2");
+        assert_eq!(copy_payload("hunter2\n", false), "hunter2\n");
+    }
+
+    #[test]
+    fn test_trim_on_save_normalizes_credentials() {
+        let (_dir, mut app) = test_app(AppConfig {
+            trim_on_save: true,
+            ..AppConfig::default()
+        });
+        app.handle_action(Action::CreateItem(ItemDraft {
+            title: "Imported".to_string(),
+            username: " alice ".to_string(),
+            password: "secret\n".to_string(),
+            ..Default::default()
+        }));
+
+        let item = &app.vault_service.items().unwrap()[0];
+        assert_eq!(item.username, "alice");
+        assert_eq!(item.password, "secret");
+    }
+
+    #[test]
+    fn test_totp_view_lists_only_items_with_totp() {
+        let (_dir, mut app) = test_app(AppConfig::default());
+        add_item(&mut app, "Mail");
+        let github = app
+            .vault_service
+            .create_item(ItemDraft {
+                title: "GitHub".to_string(),
+                otp_secret: Some("JBSWY3DP".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        app.refresh_ui();
+        assert_eq!(app.main_screen.items_panel.item_count(), 2);
+
+        app.handle_action(Action::ToggleTotpView);
+        assert_eq!(app.main_screen.items_panel.item_count(), 1);
+        assert_eq!(app.main_screen.selected_item_id(), Some(github));
+
+        app.handle_action(Action::ToggleTotpView);
+        assert_eq!(app.main_screen.items_panel.item_count(), 2);
+    }
+
+    #[test]
+    fn test_copy_totp_copies_a_code() {
+        let (_dir, mut app, memory) = test_app_with_clipboard(AppConfig {
+            confirm_clipboard_copy: true,
+            ..AppConfig::default()
+        });
+        let plain = add_item(&mut app, "Mail");
+        let github = app
+            .vault_service
+            .create_item(ItemDraft {
+                title: "GitHub".to_string(),
+                otp_secret: Some("JBSWY3DP".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        app.main_screen.clear_status();
+        app.handle_action(Action::CopyTotp(plain));
+        assert!(app
+            .main_screen
+            .status_message()
+            .unwrap()
+            .contains("No 2FA secret"));
+        // Nothing is computed or copied until the dialog is answered.
+        let confirmed = confirmed_copy(&mut app, Action::CopyTotp(github));
+        assert!(matches!(confirmed, Action::CopyTotp(id) if id == github));
+        assert_eq!(memory.contents(), "");
+
+        app.main_screen.clear_status();
+        app.handle_action(Action::ConfirmedCopy(Box::new(confirmed)));
+        let code = memory.contents();
+        assert!(code.len() == 6 && code.bytes().all(|b| b.is_ascii_digit()));
+        assert!(app
+            .main_screen
+            .status_message()
+            .unwrap()
+            .contains("TOTP code"));
+
+        // A sensitive item asks the way its password copy does.
+        let bank = app
+            .vault_service
+            .create_item(ItemDraft {
+                title: "Bank".to_string(),
+                otp_secret: Some("JBSWY3DP".to_string()),
+                sensitive: true,
+                ..Default::default()
+            })
+            .unwrap();
+        app.handle_action(Action::CopyTotp(bank));
+        assert!(matches!(
+            app.modal,
+            Modal::Confirm(ref dialog) if dialog.message().contains("sensitive")
+        ));
+    }
+
+    /// Confirm the copy request so the resulting `CopyText` can be inspected.
+    fn confirmed_copy(app: &mut App, request: Action) -> Action {
+        app.handle_action(request);
+        match app.handle_input(KeyEvent::from(KeyCode::Char('y'))) {
+            Action::ConfirmedCopy(copy) => *copy,
+            other => panic!("expected a confirmed copy, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_registers_store_recall_and_clear_on_lock() {
+        let (_dir, mut app) = test_app(AppConfig {
+            confirm_clipboard_copy: true,
+            ..AppConfig::default()
+        });
+        let id = app
+            .vault_service
+            .create_item(ItemDraft {
+                title: "Bank".to_string(),
+                password: "hunter2".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        app.handle_action(Action::YankToRegister('a', id));
+        assert!(matches!(
+            confirmed_copy(&mut app, Action::CopyRegister('a')),
+            Action::CopyText { text, sensitive: true, .. } if text.as_str() == "hunter2"
+        ));
+
+        app.handle_action(Action::CloseModal);
+        app.main_screen.clear_status();
+        app.handle_action(Action::CopyRegister('b'));
+        assert!(matches!(app.modal, Modal::None));
+        assert_eq!(
+            app.main_screen.status_message(),
+            Some("Register \"b is empty")
+        );
+
+        app.handle_action(Action::Lock);
+        assert!(app.registers.is_empty());
+    }
+
+    #[test]
+    fn test_copy_confirmation_names_the_item() {
+        let (_dir, mut app) = test_app(AppConfig {
+            confirm_clipboard_copy: true,
+            ..AppConfig::default()
+        });
+        add_item(&mut app, "Other");
+        let id = add_item(&mut app, "GitHub");
+
+        app.handle_action(Action::CopyPassword(id));
+        match app.modal {
+            Modal::Confirm(ref dialog) => {
+                assert_eq!(dialog.message(), "Copy password for 'GitHub'?")
+            }
+            _ => panic!("expected a copy confirmation"),
+        }
+
+        app.handle_action(Action::CloseModal);
+        app.handle_action(Action::CopyUsername(id));
+        assert!(
+            matches!(app.modal, Modal::Confirm(ref dialog) if dialog.message() == "Copy username for 'GitHub'?")
+        );
+    }
+
+    #[test]
+    fn test_no_clipboard_clear_item_copies_persistently() {
+        let (_dir, mut app) = test_app(AppConfig {
+            confirm_clipboard_copy: true,
+            ..AppConfig::default()
+        });
+        let kept = add_item(&mut app, "Wi-Fi");
+        let cleared = add_item(&mut app, "Bank");
+        app.handle_action(Action::ToggleNoClipboardClear(kept));
+        assert!(app.vault_service.get_item(kept).unwrap().no_clipboard_clear);
+
+        assert!(matches!(
+            confirmed_copy(&mut app, Action::CopyPassword(kept)),
+            Action::CopyText {
+                sensitive: false,
+                ..
+            }
+        ));
+        assert!(matches!(
+            confirmed_copy(&mut app, Action::CopyUsername(kept)),
+            Action::CopyText {
+                sensitive: false,
+                ..
+            }
+        ));
+        assert!(matches!(
+            confirmed_copy(&mut app, Action::CopyPassword(cleared)),
+            Action::CopyText {
+                sensitive: true,
+                ..
+            }
+        ));
+    }
+
+    /// Start an import of `path` and drive it to the end, returning the progress shown.
+    fn run_import(app: &mut App, path: PathBuf) -> Vec<String> {
+        app.handle_action(Action::StartImport(
+            path,
+            Zeroizing::new("share".to_string()),
+        ));
+        let mut seen = Vec::new();
+        while app.import.is_some() {
+            if matches!(app.import, Some(ImportTask::Reading(_))) {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            app.poll_import();
+            seen.extend(app.main_screen.progress().map(str::to_string));
+        }
+        seen
+    }
+
+    /// Export `count` logins as a CSV archive, one with a zero-width space in its password.
+    fn csv_archive(app: &mut App, dir: &TempDir, count: usize) -> PathBuf {
+        for i in 0..count {
+            add_item(app, &format!("Site {i}"));
+        }
+        app.vault_service
+            .create_item(ItemDraft {
+                title: "Padded".to_string(),
+                password: "hunter\u{200B}2".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        let path = dir.path().join("share.vlta");
+        app.vault_service
+            .export_encrypted_archive(&path, "share", ArchiveFormat::Csv)
+            .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_import_merges_in_batches_and_reports_cleaned_fields() {
+        let (dir, mut app) = test_app(AppConfig::default());
+        let path = csv_archive(&mut app, &dir, 449);
+
+        let seen = run_import(&mut app, path);
+        assert_eq!(seen.first().map(String::as_str), Some("Decrypting import…"));
+        assert!(seen.contains(&"Importing 200/450…".to_string()));
+        assert!(seen.contains(&"Importing 400/450…".to_string()));
+        assert_eq!(app.main_screen.progress(), None);
+        assert_eq!(app.vault_service.items().unwrap().len(), 900);
+        assert_eq!(
+            app.main_screen.status_message(),
+            Some(
+                "Imported 450 groups and items; removed invisible characters from 1 usernames and passwords"
+            )
+        );
+    }
+
+    #[test]
+    fn test_escape_cancels_import_and_restores_the_vault() {
+        let (dir, mut app) = test_app(AppConfig::default());
+        let path = csv_archive(&mut app, &dir, 449);
+        let before = app.vault_service.items().unwrap().to_vec();
+
+        app.handle_action(Action::StartImport(
+            path,
+            Zeroizing::new("share".to_string()),
+        ));
+        while !matches!(app.import, Some(ImportTask::Merging(_))) {
+            std::thread::sleep(Duration::from_millis(10));
+            app.poll_import();
+        }
+        // The first batch is merged as soon as the file has been read.
+        assert_eq!(app.vault_service.items().unwrap().len(), 650);
+
+        // Other keys are ignored while the import runs.
+        assert!(matches!(
+            app.handle_input(KeyEvent::from(KeyCode::Char('n'))),
+            Action::None
+        ));
+        let action = app.handle_input(KeyEvent::from(KeyCode::Esc));
+        app.handle_action(action);
+        assert!(app.import.is_none());
+        assert_eq!(app.vault_service.items().unwrap(), before);
+        assert_eq!(app.main_screen.progress(), None);
+    }
+
+    #[test]
+    fn test_undo_import_asks_then_restores_the_vault() {
+        let (dir, mut app) = test_app(AppConfig::default());
+        let path = csv_archive(&mut app, &dir, 2);
+        let before = app.vault_service.items().unwrap().to_vec();
+
+        app.handle_action(Action::OpenUndoImportConfirm);
+        assert!(matches!(app.modal, Modal::None));
+        assert_eq!(app.main_screen.status_message(), Some("No import to undo"));
+
+        run_import(&mut app, path);
+        assert_eq!(app.vault_service.items().unwrap().len(), 6);
+        let action = app.handle_input(KeyEvent::from(KeyCode::Char('U')));
+        app.handle_action(action);
+        assert!(matches!(app.modal, Modal::Confirm(_)));
+        answer_yes(&mut app);
+        assert_eq!(app.vault_service.items().unwrap(), before);
+        assert!(!app.vault_service.can_undo_import());
+    }
 }
@@ -0,0 +1,94 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+
+/// How many copies the log keeps before dropping the oldest.
+pub const COPY_LOG_CAPACITY: usize = 100;
+
+/// One clipboard copy: where it came from and what kind of value it was.
+///
+/// The copied value itself is never stored.
+#[derive(Debug, Clone)]
+pub struct CopyRecord {
+    pub at: DateTime<Local>,
+    /// Title of the item the value came from, or what else it was copied from.
+    pub source: String,
+    /// Field label, e.g. "Password" or "TOTP code".
+    pub field: String,
+}
+
+impl CopyRecord {
+    /// Case-insensitive match of `query` against the source and the field.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        self.source.to_lowercase().contains(&query) || self.field.to_lowercase().contains(&query)
+    }
+}
+
+/// Session-only ring buffer of recent copies, viewable with Ctrl+Y; emptied on lock.
+pub struct CopyLog {
+    records: VecDeque<CopyRecord>,
+    capacity: usize,
+}
+
+impl CopyLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&mut self, source: String, field: String) {
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(CopyRecord {
+            at: Local::now(),
+            source,
+            field,
+        });
+    }
+
+    /// Copies from oldest to newest.
+    pub fn records(&self) -> impl DoubleEndedIterator<Item = &CopyRecord> {
+        self.records.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_caps_at_capacity_and_matches_source_or_field() {
+        let mut log = CopyLog::new(2);
+        log.push("GitHub".to_string(), "Username".to_string());
+        log.push("GitHub".to_string(), "Password".to_string());
+        log.push("Bank".to_string(), "TOTP code".to_string());
+        assert_eq!(log.len(), 2);
+
+        let matching: Vec<&str> = log
+            .records()
+            .filter(|r| r.matches("git"))
+            .map(|r| r.field.as_str())
+            .collect();
+        assert_eq!(matching, ["Password"]);
+        assert!(log.records().any(|r| r.matches("totp")));
+
+        log.clear();
+        assert!(log.is_empty());
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+
+use crate::ui::screens::main_screen::StatusLevel;
+
+/// How many status events the log keeps before dropping the oldest.
+pub const EVENT_LOG_CAPACITY: usize = 200;
+
+/// A status message as it was shown, with the time it was raised.
+///
+/// Only status-bar text is recorded, which never contains item secrets.
+#[derive(Debug, Clone)]
+pub struct LoggedEvent {
+    pub at: DateTime<Local>,
+    pub level: StatusLevel,
+    pub text: String,
+}
+
+/// In-memory ring buffer of recent status events, viewable with Ctrl+G.
+pub struct EventLog {
+    events: VecDeque<LoggedEvent>,
+    capacity: usize,
+}
+
+impl EventLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            events: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&mut self, level: StatusLevel, text: String) {
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+        }
+        self.events.push_back(LoggedEvent {
+            at: Local::now(),
+            level,
+            text,
+        });
+    }
+
+    /// Events from oldest to newest.
+    pub fn events(&self) -> impl DoubleEndedIterator<Item = &LoggedEvent> {
+        self.events.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_caps_at_capacity_and_drops_oldest() {
+        let mut log = EventLog::new(3);
+        for i in 0..5 {
+            log.push(StatusLevel::Info, format!("event {i}"));
+        }
+        assert_eq!(log.len(), 3);
+        let texts: Vec<&str> = log.events().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, ["event 2", "event 3", "event 4"]);
+    }
+}
@@ -0,0 +1,185 @@
+use ratatui::widgets::ListState;
+
+/// An entry that can be identified across list rebuilds.
+pub trait Keyed {
+    type Key: Copy + PartialEq;
+
+    fn key(&self) -> Self::Key;
+}
+
+/// A list whose selection follows the selected entry's key rather than its index.
+///
+/// When the entries are replaced (re-sorted, filtered, reloaded), the same
+/// logical entry stays selected. If it is gone, the selection stays at the
+/// nearest surviving position.
+pub struct SelectableList<T: Keyed> {
+    entries: Vec<T>,
+    state: ListState,
+    /// Moving past either end continues from the other end.
+    wraparound: bool,
+}
+
+impl<T: Keyed> Default for SelectableList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Keyed> SelectableList<T> {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            state: ListState::default(),
+            wraparound: false,
+        }
+    }
+
+    pub fn set_wraparound(&mut self, wraparound: bool) {
+        self.wraparound = wraparound;
+    }
+
+    pub fn entries(&self) -> &[T] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Replace the entries, keeping the previously selected key selected if present.
+    pub fn set_entries(&mut self, entries: Vec<T>) {
+        let previous_key = self.selected_key();
+        let previous_index = self.state.selected();
+        self.entries = entries;
+
+        if self.entries.is_empty() {
+            self.state.select(None);
+            return;
+        }
+        let index = previous_key
+            .and_then(|key| self.position(key))
+            .or_else(|| previous_index.map(|i| i.min(self.entries.len() - 1)))
+            .unwrap_or(0);
+        self.state.select(Some(index));
+    }
+
+    pub fn selected_index(&self) -> Option<usize> {
+        self.state.selected()
+    }
+
+    pub fn selected(&self) -> Option<&T> {
+        self.state.selected().and_then(|i| self.entries.get(i))
+    }
+
+    pub fn selected_key(&self) -> Option<T::Key> {
+        self.selected().map(Keyed::key)
+    }
+
+    /// Select the entry with `key`. Returns false (leaving the selection alone) if absent.
+    pub fn select_key(&mut self, key: T::Key) -> bool {
+        match self.position(key) {
+            Some(index) => {
+                self.state.select(Some(index));
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn select_first(&mut self) {
+        if !self.entries.is_empty() {
+            self.state.select(Some(0));
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        if let Some(i) = self.state.selected() {
+            if i > 0 {
+                self.state.select(Some(i - 1));
+            } else if self.wraparound {
+                self.state.select(Some(self.entries.len() - 1));
+            }
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if let Some(i) = self.state.selected() {
+            if i + 1 < self.entries.len() {
+                self.state.select(Some(i + 1));
+            } else if self.wraparound {
+                self.state.select(Some(0));
+            }
+        }
+    }
+
+    /// A copy of the widget state for rendering.
+    pub fn state(&self) -> ListState {
+        self.state.clone()
+    }
+
+    fn position(&self, key: T::Key) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.key() == key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Keyed for (u32, &'static str) {
+        type Key = u32;
+
+        fn key(&self) -> u32 {
+            self.0
+        }
+    }
+
+    fn list(entries: &[(u32, &'static str)]) -> SelectableList<(u32, &'static str)> {
+        let mut list = SelectableList::new();
+        list.set_entries(entries.to_vec());
+        list
+    }
+
+    #[test]
+    fn test_first_entry_selected_initially() {
+        assert_eq!(list(&[(1, "a"), (2, "b")]).selected_key(), Some(1));
+        assert_eq!(list(&[]).selected_index(), None);
+    }
+
+    #[test]
+    fn test_resort_preserves_selected_key() {
+        let mut list = list(&[(1, "a"), (2, "b"), (3, "c")]);
+        list.move_down();
+        assert_eq!(list.selected_key(), Some(2));
+
+        list.set_entries(vec![(3, "c"), (1, "a"), (2, "b")]);
+        assert_eq!(list.selected_key(), Some(2));
+        assert_eq!(list.selected_index(), Some(2));
+    }
+
+    #[test]
+    fn test_removed_selection_falls_back_to_nearest() {
+        let mut list = list(&[(1, "a"), (2, "b"), (3, "c")]);
+        list.move_down();
+        list.move_down();
+
+        list.set_entries(vec![(1, "a"), (2, "b")]);
+        assert_eq!(list.selected_key(), Some(2));
+
+        list.set_entries(Vec::new());
+        assert_eq!(list.selected(), None);
+    }
+
+    #[test]
+    fn test_select_key() {
+        let mut list = list(&[(1, "a"), (2, "b")]);
+        assert!(list.select_key(2));
+        assert_eq!(list.selected_index(), Some(1));
+        assert!(!list.select_key(9));
+        assert_eq!(list.selected_key(), Some(2));
+    }
+}
@@ -1,13 +1,21 @@
 pub mod app;
+pub mod copy_log;
+pub mod event_log;
+pub mod list;
 pub mod modals;
 pub mod panels;
 pub mod screens;
+pub mod text;
 pub mod theme;
 
+use std::path::PathBuf;
+
 use crossterm::event::KeyEvent;
 use ratatui::Frame;
 use uuid::Uuid;
+use zeroize::Zeroizing;
 
+use crate::core::qr::QrField;
 use crate::core::vault_service::ItemDraft;
 
 /// Actions emitted by UI components, dispatched by App.
@@ -16,29 +24,141 @@ pub enum Action {
     Quit,
     Lock,
     Save,
+    /// Drop unsaved edits, asking first if there are any.
+    DiscardChanges,
+    /// Re-read the vault from disk.
+    ReloadVault,
+    OpenCompactConfirm,
+    /// Clear password history and orphaned references, then save.
+    CompactVault,
 
     // Vault lifecycle
     CreateVault(String),
+    /// Copy the master passphrase suggested on the create-vault screen.
+    CopyPassphraseSuggestion(String),
     UnlockVault(String),
 
     // Navigation
     SelectGroup(Option<Uuid>),
     SelectItem(Option<Uuid>),
+    /// Show the item in the details panel across the whole window.
+    ShowDetailsFullScreen(Uuid),
 
     // CRUD
     CreateItem(ItemDraft),
     UpdateItem(Uuid, ItemDraft),
     DeleteItem(Uuid),
-    CreateGroup(String, Option<Uuid>),
-    UpdateGroup(Uuid, String, Option<Uuid>),
+    /// Restore the last deleted item while the undo window is open.
+    UndoDelete,
+    /// Swap the item with its neighbour in the manual order of its group.
+    MoveItemUp(Uuid),
+    MoveItemDown(Uuid),
+    /// Name, parent and description of a new group.
+    CreateGroup(String, Option<Uuid>, String),
+    UpdateGroup(Uuid, String, Option<Uuid>, String),
     DeleteGroup(Uuid),
 
     // Clipboard
+    /// Copy an item's password or username, auto-cleared unless the item opts out.
     CopyPassword(Uuid),
     CopyUsername(Uuid),
+    /// Let the user pick which of the item's fields to show as a QR code.
+    ChooseQrField(Uuid),
+    /// Show a field as a QR code, once any reveal gating has passed.
+    RequestQr(Uuid, QrField),
+    /// Show a field as a QR code.
+    ShowQr(Uuid, QrField),
+    /// Copy the item's current TOTP code, or the next one when the current is about to expire.
+    CopyTotp(Uuid),
+    /// Run the item's launch command, after confirmation.
+    RunLaunchCommand(Uuid),
+    /// The user confirmed running the item's launch command.
+    ConfirmedLaunch(Uuid),
+    /// Store the item's password in the named register (vim-style `"ay`).
+    YankToRegister(char, Uuid),
+    /// Copy the value held in the named register (`"ap`).
+    CopyRegister(char),
+    /// Copy an SSH key item's public key; it is not secret, so it is never auto-cleared.
+    CopyPublicKey(Uuid),
+    /// Flip the item's `no_clipboard_clear` flag.
+    ToggleNoClipboardClear(Uuid),
+    /// First press copies the username, the next press (or the delay) copies the password.
+    CopyCredentialSequence(Uuid),
+    /// Copy the whole item rendered through the configured template.
+    CopyFormattedItem(Uuid),
+    /// Open an attachment's file (or the vault's directory) with the default application.
+    OpenAttachment(PathBuf),
+    /// Open a URL in the browser.
+    OpenUrl(String),
+    /// Let the user pick which of several URLs to open.
+    ChooseUrl(Vec<String>),
+    /// Copy a referenced file into the item as an embedded file.
+    EmbedAttachment(Uuid, PathBuf),
+    /// Write the item's embedded file at this index to the working directory.
+    ExportEmbedded(Uuid, usize),
+    /// Copy `text`; `label` names it in the status bar and sensitive text is auto-cleared.
+    /// `source` is what it was copied from (an item's title), for the copy log. A
+    /// `secret` is confirmed even under `confirm_copy_secrets_only`, whether or not the
+    /// item lets it be auto-cleared.
+    CopyText {
+        source: String,
+        text: Zeroizing<String>,
+        label: String,
+        sensitive: bool,
+        secret: bool,
+    },
+    /// A copy the user has already confirmed; performed without asking again.
+    ConfirmedCopy(Box<Action>),
+    /// A reveal or copy of a sensitive item's password, confirmed by the user.
+    ConfirmedSensitive(Box<Action>),
+
+    // Reveal
+    /// Reveal requested while re-authentication may be required.
+    RequestReveal(Uuid),
+    /// Reveal the password of the item shown in the details panel.
+    RevealPassword(Uuid),
+    SubmitReauth(String),
+
+    // Saved filters
+    OpenSavedFilters,
+    /// Apply the saved filter at this index in the config.
+    ApplySavedFilter(usize),
+    SaveCurrentFilter,
+
+    // Vault settings
+    OpenVaultSettings,
+    /// Store this vault's own settings: its auto-lock timeout (`None` falls back
+    /// to the config) and whether previous passwords are recorded.
+    SaveVaultSettings {
+        auto_lock_override: Option<u64>,
+        record_password_history: bool,
+    },
+
+    // Import
+    /// Ask for a file to import and its password.
+    OpenImport,
+    /// Decrypt the file on a worker thread, then merge it a batch at a time.
+    StartImport(PathBuf, Zeroizing<String>),
+    /// Stop the running import and put the vault back as it was.
+    CancelImport,
+    /// Ask before undoing the last import.
+    OpenUndoImportConfirm,
+    /// Put the vault back as it was before the last import.
+    UndoImport,
+
+    // Event log
+    OpenEventLog,
+    /// Show this session's clipboard copies (sources and field names only).
+    OpenCopyLog,
+    /// Show what changed since the last save.
+    OpenChanges,
+    /// Show the vault file's location.
+    OpenVaultInfo,
 
     // Search
     SetSearchQuery(String),
+    /// Show only items with TOTP (the "2FA" view), or everything again.
+    ToggleTotpView,
     ClearSearch,
 
     // Modals
@@ -50,6 +170,10 @@ pub enum Action {
     OpenDeleteGroupConfirm(Uuid),
     OpenPasswordGenerator,
     UseGeneratedPassword,
+    /// Esc on a form with edits: ask before discarding them.
+    ConfirmDiscardForm,
+    /// Close the stashed form without saving.
+    DiscardForm,
     CloseModal,
 
     // Status
@@ -59,8 +183,53 @@ pub enum Action {
     None,
 }
 
+impl Action {
+    pub fn copy_text(
+        source: &str,
+        text: impl Into<Zeroizing<String>>,
+        label: &str,
+        sensitive: bool,
+    ) -> Self {
+        Self::CopyText {
+            source: source.to_string(),
+            text: text.into(),
+            label: label.to_string(),
+            sensitive,
+            secret: false,
+        }
+    }
+
+    /// Like `copy_text`, for a password or other secret.
+    pub fn copy_secret(
+        source: &str,
+        text: impl Into<Zeroizing<String>>,
+        label: &str,
+        sensitive: bool,
+    ) -> Self {
+        Self::CopyText {
+            source: source.to_string(),
+            text: text.into(),
+            label: label.to_string(),
+            sensitive,
+            secret: true,
+        }
+    }
+}
+
 /// Trait implemented by all UI components (screens, panels, modals).
 pub trait Component {
     fn handle_key(&mut self, key: KeyEvent) -> Action;
     fn render(&self, frame: &mut Frame, area: ratatui::layout::Rect);
 }
+
+/// Draw with `draw` on a `width` by `height` test terminal and return its rows as text.
+#[cfg(test)]
+pub(crate) fn render_rows(width: u16, height: u16, draw: impl FnOnce(&mut Frame)) -> Vec<String> {
+    let backend = ratatui::backend::TestBackend::new(width, height);
+    let mut terminal = ratatui::Terminal::new(backend).unwrap();
+    terminal.draw(draw).unwrap();
+    let buffer = terminal.backend().buffer();
+    (0..height)
+        .map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect())
+        .collect()
+}
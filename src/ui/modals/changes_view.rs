@@ -0,0 +1,96 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::core::changes::{ChangeKind, ChangeSummary};
+use crate::ui::theme;
+use crate::ui::{Action, Component};
+
+/// Read-only list of what changed since the vault was unlocked or last saved.
+pub struct ChangesView {
+    changes: Vec<ChangeSummary>,
+    scroll: usize,
+}
+
+impl ChangesView {
+    pub fn new(changes: Vec<ChangeSummary>) -> Self {
+        Self { changes, scroll: 0 }
+    }
+}
+
+impl Component for ChangesView {
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) | (KeyCode::Char('q'), _) => Action::CloseModal,
+            (KeyCode::Char('d'), KeyModifiers::CONTROL) => Action::CloseModal,
+            (KeyCode::Char('j'), _) | (KeyCode::Down, _) => {
+                if self.scroll + 1 < self.changes.len() {
+                    self.scroll += 1;
+                }
+                Action::None
+            }
+            (KeyCode::Char('k'), _) | (KeyCode::Up, _) => {
+                self.scroll = self.scroll.saturating_sub(1);
+                Action::None
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let width = 60u16.min(area.width.saturating_sub(4));
+        let height = (self.changes.len() as u16 + 4)
+            .clamp(6, 20)
+            .min(area.height.saturating_sub(2));
+
+        let vert = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+        let horiz = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+        let [v] = vert.areas(area);
+        let [center] = horiz.areas(v);
+
+        frame.render_widget(Clear, center);
+
+        let block = Block::default()
+            .title(" Unsaved Changes ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(theme::style_border(true));
+
+        let inner = block.inner(center);
+        frame.render_widget(block, center);
+
+        let chunks = Layout::vertical([
+            Constraint::Min(1),    // Changes
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+        let lines: Vec<Line> = if self.changes.is_empty() {
+            vec![Line::styled(
+                "No changes since the last save",
+                theme::style_muted(),
+            )]
+        } else {
+            self.changes
+                .iter()
+                .skip(self.scroll)
+                .map(|change| {
+                    let style = match change.kind {
+                        ChangeKind::Added => theme::style_success(),
+                        ChangeKind::Modified => theme::style_warning(),
+                        ChangeKind::Removed => theme::style_error(),
+                    };
+                    Line::styled(change.describe(), style)
+                })
+                .collect()
+        };
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        let hints = Paragraph::new("j/k scroll · Esc close")
+            .alignment(Alignment::Center)
+            .style(theme::style_muted());
+        frame.render_widget(hints, chunks[1]);
+    }
+}
@@ -21,6 +21,10 @@ impl ConfirmDialog {
             selected: false,
         }
     }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
 }
 
 impl Component for ConfirmDialog {
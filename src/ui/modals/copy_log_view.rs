@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::ui::copy_log::CopyRecord;
+use crate::ui::theme;
+use crate::ui::{Action, Component};
+
+/// Searchable list of this session's clipboard copies, newest first.
+pub struct CopyLogView {
+    records: Vec<CopyRecord>,
+    query: String,
+    scroll: usize,
+    /// How long before the view opened vaultura last cleared the clipboard.
+    last_cleared_ago: Option<Duration>,
+}
+
+impl CopyLogView {
+    /// `records` are given oldest first, as stored in the log.
+    pub fn new(records: Vec<CopyRecord>, last_cleared_ago: Option<Duration>) -> Self {
+        let mut records = records;
+        records.reverse();
+        Self {
+            records,
+            query: String::new(),
+            scroll: 0,
+            last_cleared_ago,
+        }
+    }
+
+    fn cleared_line(&self) -> String {
+        match self.last_cleared_ago {
+            Some(ago) => format!("Clipboard last cleared {}s ago", ago.as_secs()),
+            None => "Clipboard not cleared yet this session".to_string(),
+        }
+    }
+
+    fn visible(&self) -> impl Iterator<Item = &CopyRecord> {
+        self.records.iter().filter(|r| r.matches(&self.query))
+    }
+}
+
+impl Component for CopyLogView {
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) => Action::CloseModal,
+            (KeyCode::Char('y'), KeyModifiers::CONTROL) => Action::CloseModal,
+            (KeyCode::Down, _) => {
+                if self.scroll + 1 < self.visible().count() {
+                    self.scroll += 1;
+                }
+                Action::None
+            }
+            (KeyCode::Up, _) => {
+                self.scroll = self.scroll.saturating_sub(1);
+                Action::None
+            }
+            (KeyCode::Backspace, _) => {
+                self.query.pop();
+                self.scroll = 0;
+                Action::None
+            }
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                self.query.push(c);
+                self.scroll = 0;
+                Action::None
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let width = 76u16.min(area.width.saturating_sub(4));
+        let height = 20u16.min(area.height.saturating_sub(2));
+
+        let vert = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+        let horiz = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+        let [v] = vert.areas(area);
+        let [center] = horiz.areas(v);
+
+        frame.render_widget(Clear, center);
+
+        let block = Block::default()
+            .title(" Copy Log ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(theme::style_border(true));
+
+        let inner = block.inner(center);
+        frame.render_widget(block, center);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Search
+            Constraint::Length(1), // Last clear
+            Constraint::Min(1),    // Copies
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+        let search = Line::from(vec![
+            Span::styled("Search: ", theme::style_muted()),
+            Span::raw(self.query.as_str()),
+            Span::styled("█", theme::style_accent()),
+        ]);
+        frame.render_widget(Paragraph::new(search), chunks[0]);
+        frame.render_widget(
+            Paragraph::new(Line::styled(self.cleared_line(), theme::style_muted())),
+            chunks[1],
+        );
+
+        let lines: Vec<Line> = if self.records.is_empty() {
+            vec![Line::styled("Nothing copied yet", theme::style_muted())]
+        } else {
+            self.visible()
+                .skip(self.scroll)
+                .map(|record| {
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{} ", record.at.format("%H:%M:%S")),
+                            theme::style_muted(),
+                        ),
+                        Span::raw(record.source.as_str()),
+                        Span::styled(format!("  {}", record.field), theme::style_muted()),
+                    ])
+                })
+                .collect()
+        };
+        frame.render_widget(Paragraph::new(lines), chunks[2]);
+
+        let hints = Paragraph::new("type to search · ↑/↓ scroll · Esc close")
+            .alignment(Alignment::Center)
+            .style(theme::style_muted());
+        frame.render_widget(hints, chunks[3]);
+    }
+}
@@ -0,0 +1,95 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::ui::event_log::LoggedEvent;
+use crate::ui::theme;
+use crate::ui::{Action, Component};
+
+/// Read-only list of recent status events, newest first.
+pub struct EventLogView {
+    events: Vec<LoggedEvent>,
+    scroll: usize,
+}
+
+impl EventLogView {
+    /// `events` are given oldest first, as stored in the log.
+    pub fn new(events: Vec<LoggedEvent>) -> Self {
+        let mut events = events;
+        events.reverse();
+        Self { events, scroll: 0 }
+    }
+}
+
+impl Component for EventLogView {
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) | (KeyCode::Char('q'), _) => Action::CloseModal,
+            (KeyCode::Char('g'), KeyModifiers::CONTROL) => Action::CloseModal,
+            (KeyCode::Char('j'), _) | (KeyCode::Down, _) => {
+                if self.scroll + 1 < self.events.len() {
+                    self.scroll += 1;
+                }
+                Action::None
+            }
+            (KeyCode::Char('k'), _) | (KeyCode::Up, _) => {
+                self.scroll = self.scroll.saturating_sub(1);
+                Action::None
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let width = 76u16.min(area.width.saturating_sub(4));
+        let height = 20u16.min(area.height.saturating_sub(2));
+
+        let vert = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+        let horiz = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+        let [v] = vert.areas(area);
+        let [center] = horiz.areas(v);
+
+        frame.render_widget(Clear, center);
+
+        let block = Block::default()
+            .title(" Event Log ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(theme::style_border(true));
+
+        let inner = block.inner(center);
+        frame.render_widget(block, center);
+
+        let chunks = Layout::vertical([
+            Constraint::Min(1),    // Events
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+        let lines: Vec<Line> = if self.events.is_empty() {
+            vec![Line::styled("No events yet", theme::style_muted())]
+        } else {
+            self.events
+                .iter()
+                .skip(self.scroll)
+                .map(|event| {
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{} ", event.at.format("%H:%M:%S")),
+                            theme::style_muted(),
+                        ),
+                        Span::styled(event.text.as_str(), event.level.style()),
+                    ])
+                })
+                .collect()
+        };
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        let hints = Paragraph::new("j/k scroll · Esc close")
+            .alignment(Alignment::Center)
+            .style(theme::style_muted());
+        frame.render_widget(hints, chunks[1]);
+    }
+}
@@ -0,0 +1,101 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+
+use crate::ui::theme;
+use crate::ui::{Action, Component};
+
+/// Quick-pick list of saved filters, or of any other named choices.
+pub struct FilterPicker {
+    title: String,
+    names: Vec<String>,
+    selected: usize,
+    /// Builds the action for the picked index.
+    pick: Box<dyn Fn(usize) -> Action>,
+}
+
+impl FilterPicker {
+    pub fn new(names: Vec<String>) -> Self {
+        Self::with_action(" Saved Filters ", names, Action::ApplySavedFilter)
+    }
+
+    pub fn with_action(
+        title: &str,
+        names: Vec<String>,
+        pick: impl Fn(usize) -> Action + 'static,
+    ) -> Self {
+        Self {
+            title: title.to_string(),
+            names,
+            selected: 0,
+            pick: Box::new(pick),
+        }
+    }
+}
+
+impl Component for FilterPicker {
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Esc => Action::CloseModal,
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.selected + 1 < self.names.len() {
+                    self.selected += 1;
+                }
+                Action::None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                Action::None
+            }
+            KeyCode::Enter if !self.names.is_empty() => (self.pick)(self.selected),
+            _ => Action::None,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let width = 44u16.min(area.width.saturating_sub(4));
+        let height = (self.names.len() as u16 + 4)
+            .min(16)
+            .min(area.height.saturating_sub(2));
+
+        let vert = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+        let horiz = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+        let [v] = vert.areas(area);
+        let [center] = horiz.areas(v);
+
+        frame.render_widget(Clear, center);
+
+        let block = Block::default()
+            .title(self.title.as_str())
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(theme::style_border(true));
+
+        let inner = block.inner(center);
+        frame.render_widget(block, center);
+
+        let chunks = Layout::vertical([
+            Constraint::Min(1),    // Filters
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+        let items: Vec<ListItem> = self
+            .names
+            .iter()
+            .map(|name| ListItem::new(Line::raw(name.as_str())))
+            .collect();
+        let list = List::new(items)
+            .highlight_style(theme::style_selected())
+            .highlight_symbol("▸ ");
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        let hints = Paragraph::new("Enter choose · Esc cancel")
+            .alignment(Alignment::Center)
+            .style(theme::style_muted());
+        frame.render_widget(hints, chunks[1]);
+    }
+}
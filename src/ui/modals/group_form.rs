@@ -12,9 +12,14 @@ use crate::ui::{Action, Component};
 pub struct GroupForm {
     editing_id: Option<Uuid>,
     name: String,
+    description: String,
     parent_groups: Vec<(Uuid, String)>,
     selected_parent_index: Option<usize>,
-    current_field: usize, // 0 = name, 1 = parent
+    current_field: usize, // 0 = name, 1 = description, 2 = parent
+    /// Name, description and parent when the form opened, for the unsaved-edits check on Esc.
+    initial: (String, String, Option<usize>),
+    /// Ask before Esc throws away edits.
+    confirm_discard: bool,
 }
 
 impl GroupForm {
@@ -24,9 +29,12 @@ impl GroupForm {
         Self {
             editing_id: None,
             name: String::new(),
+            description: String::new(),
             parent_groups,
             selected_parent_index: None,
             current_field: 0,
+            initial: (String::new(), String::new(), None),
+            confirm_discard: false,
         }
     }
 
@@ -43,23 +51,43 @@ impl GroupForm {
         Self {
             editing_id: Some(group.id),
             name: group.name.clone(),
+            description: group.description.clone(),
             parent_groups,
             selected_parent_index,
             current_field: 0,
+            initial: (
+                group.name.clone(),
+                group.description.clone(),
+                selected_parent_index,
+            ),
+            confirm_discard: false,
         }
     }
+
+    pub fn set_confirm_discard(&mut self, confirm: bool) {
+        self.confirm_discard = confirm;
+    }
+
+    /// Whether the name, description or parent differs from when the form opened.
+    pub fn is_dirty(&self) -> bool {
+        (&self.name, &self.description, self.selected_parent_index)
+            != (&self.initial.0, &self.initial.1, self.initial.2)
+    }
 }
 
 impl Component for GroupForm {
     fn handle_key(&mut self, key: KeyEvent) -> Action {
         match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) if self.confirm_discard && self.is_dirty() => {
+                Action::ConfirmDiscardForm
+            }
             (KeyCode::Esc, _) => Action::CloseModal,
             (KeyCode::Tab, _) | (KeyCode::Down, _) => {
-                self.current_field = (self.current_field + 1) % 2;
+                self.current_field = (self.current_field + 1) % 3;
                 Action::None
             }
             (KeyCode::BackTab, _) | (KeyCode::Up, _) => {
-                self.current_field = if self.current_field == 0 { 1 } else { 0 };
+                self.current_field = (self.current_field + 2) % 3;
                 Action::None
             }
             (KeyCode::Enter, KeyModifiers::CONTROL)
@@ -70,21 +98,28 @@ impl Component for GroupForm {
                     let parent_id = self
                         .selected_parent_index
                         .and_then(|i| self.parent_groups.get(i).map(|(id, _)| *id));
+                    let name = self.name.clone();
+                    let description = self.description.trim().to_string();
                     match self.editing_id {
-                        Some(id) => Action::UpdateGroup(id, self.name.clone(), parent_id),
-                        None => Action::CreateGroup(self.name.clone(), parent_id),
+                        Some(id) => Action::UpdateGroup(id, name, parent_id, description),
+                        None => Action::CreateGroup(name, parent_id, description),
                     }
                 }
             }
             _ => {
-                if self.current_field == 0 {
+                if self.current_field < 2 {
+                    let text = if self.current_field == 0 {
+                        &mut self.name
+                    } else {
+                        &mut self.description
+                    };
                     match key.code {
                         KeyCode::Char(c) => {
-                            self.name.push(c);
+                            text.push(c);
                             Action::None
                         }
                         KeyCode::Backspace => {
-                            self.name.pop();
+                            text.pop();
                             Action::None
                         }
                         _ => Action::None,
@@ -128,7 +163,7 @@ impl Component for GroupForm {
 
     fn render(&self, frame: &mut Frame, area: Rect) {
         let width = 50u16.min(area.width.saturating_sub(4));
-        let height = 14u16.min(area.height.saturating_sub(2));
+        let height = 17u16.min(area.height.saturating_sub(2));
 
         let vert = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
         let horiz = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
@@ -154,6 +189,7 @@ impl Component for GroupForm {
 
         let chunks = Layout::vertical([
             Constraint::Length(3), // Name
+            Constraint::Length(3), // Description
             Constraint::Length(3), // Parent
             Constraint::Length(2), // Hints
             Constraint::Min(0),
@@ -185,8 +221,36 @@ impl Component for GroupForm {
 
         frame.render_widget(Paragraph::new(name_content).block(name_block), chunks[0]);
 
+        // Description field
+        let description_focused = self.current_field == 1;
+        let description_block = Block::default()
+            .title(" Description ")
+            .title_style(if description_focused {
+                theme::style_accent()
+            } else {
+                theme::style_muted()
+            })
+            .borders(Borders::ALL)
+            .border_style(theme::style_border(description_focused));
+
+        let description_content = if description_focused {
+            Line::from(vec![
+                Span::raw(&self.description),
+                Span::styled("█", theme::style_accent()),
+            ])
+        } else if self.description.is_empty() {
+            Line::from(Span::styled("Optional note...", theme::style_muted()))
+        } else {
+            Line::from(Span::raw(&self.description))
+        };
+
+        frame.render_widget(
+            Paragraph::new(description_content).block(description_block),
+            chunks[1],
+        );
+
         // Parent field
-        let parent_focused = self.current_field == 1;
+        let parent_focused = self.current_field == 2;
         let parent_block = Block::default()
             .title(" Parent Group ")
             .title_style(if parent_focused {
@@ -209,7 +273,7 @@ impl Component for GroupForm {
         };
         frame.render_widget(
             Paragraph::new(Span::styled(parent_display, parent_style)).block(parent_block),
-            chunks[1],
+            chunks[2],
         );
 
         // Hints
@@ -222,6 +286,47 @@ impl Component for GroupForm {
             Span::raw(" cancel"),
         ]))
         .style(theme::style_muted());
-        frame.render_widget(hints, chunks[2]);
+        frame.render_widget(hints, chunks[3]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(form: &mut GroupForm, text: &str) {
+        for c in text.chars() {
+            form.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    fn save() -> KeyEvent {
+        KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)
+    }
+
+    #[test]
+    fn test_description_is_carried_into_create_and_update() {
+        let mut form = GroupForm::new_create(&[]);
+        type_text(&mut form, "Work");
+        form.handle_key(KeyEvent::from(KeyCode::Tab));
+        type_text(&mut form, "Office accounts ");
+        assert!(matches!(
+            form.handle_key(save()),
+            Action::CreateGroup(name, None, description)
+                if name == "Work" && description == "Office accounts"
+        ));
+
+        let mut group = Group::new("Work".to_string(), None);
+        group.description = "Office".to_string();
+        let mut form = GroupForm::new_edit(&group, &[]);
+        assert!(!form.is_dirty());
+        form.current_field = 1;
+        type_text(&mut form, " VPN");
+        assert!(form.is_dirty());
+        assert!(matches!(
+            form.handle_key(save()),
+            Action::UpdateGroup(id, _, None, description)
+                if id == group.id && description == "Office VPN"
+        ));
     }
 }
@@ -0,0 +1,197 @@
+use std::path::PathBuf;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+use zeroize::Zeroizing;
+
+use crate::ui::theme;
+use crate::ui::{Action, Component};
+
+/// Asks for a vault export or encrypted archive to import, and its password.
+pub struct ImportForm {
+    path: String,
+    password: Zeroizing<String>,
+    /// Whether the password field has focus rather than the path.
+    on_password: bool,
+    error_message: Option<String>,
+}
+
+impl Default for ImportForm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImportForm {
+    pub fn new() -> Self {
+        Self {
+            path: String::new(),
+            password: Zeroizing::default(),
+            on_password: false,
+            error_message: None,
+        }
+    }
+
+    fn submit(&mut self) -> Action {
+        let path = self.path.trim();
+        if path.is_empty() {
+            self.on_password = false;
+            self.error_message = Some("Enter the file to import".to_string());
+            return Action::None;
+        }
+        if self.password.is_empty() {
+            self.on_password = true;
+            self.error_message = Some("Enter the file's password".to_string());
+            return Action::None;
+        }
+        Action::StartImport(PathBuf::from(path), std::mem::take(&mut self.password))
+    }
+
+    fn field(&mut self) -> &mut String {
+        if self.on_password {
+            &mut self.password
+        } else {
+            &mut self.path
+        }
+    }
+}
+
+impl Component for ImportForm {
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Esc => Action::CloseModal,
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                self.on_password = !self.on_password;
+                Action::None
+            }
+            KeyCode::Enter if !self.on_password => {
+                self.on_password = true;
+                Action::None
+            }
+            KeyCode::Enter => self.submit(),
+            KeyCode::Char(c) => {
+                self.field().push(c);
+                self.error_message = None;
+                Action::None
+            }
+            KeyCode::Backspace => {
+                self.field().pop();
+                Action::None
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let width = 60u16.min(area.width.saturating_sub(4));
+        let height = 11u16.min(area.height.saturating_sub(2));
+
+        let vert = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+        let horiz = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+        let [v] = vert.areas(area);
+        let [center] = horiz.areas(v);
+
+        frame.render_widget(Clear, center);
+
+        let block = Block::default()
+            .title(" Import ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(theme::style_border(true));
+
+        let inner = block.inner(center);
+        frame.render_widget(block, center);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(3), // Path
+            Constraint::Length(3), // Password
+            Constraint::Length(1), // Error message
+            Constraint::Min(0),    // Hint
+        ])
+        .split(inner);
+
+        let fields = [
+            (
+                " File (.vltr export or .vlta archive) ",
+                self.path.clone(),
+                false,
+            ),
+            (
+                " Password ",
+                "•".repeat(self.password.chars().count()),
+                true,
+            ),
+        ];
+        for ((title, value, is_password), chunk) in fields.into_iter().zip(&chunks[..2]) {
+            let focused = self.on_password == is_password;
+            let mut spans = vec![Span::raw(value)];
+            if focused {
+                spans.push(Span::styled("█", theme::style_accent()));
+            }
+            let field_block = Block::default()
+                .title(title)
+                .title_style(if focused {
+                    theme::style_accent()
+                } else {
+                    theme::style_muted()
+                })
+                .borders(Borders::ALL)
+                .border_style(theme::style_border(focused));
+            frame.render_widget(Paragraph::new(Line::from(spans)).block(field_block), *chunk);
+        }
+
+        if let Some(ref err) = self.error_message {
+            let err_para = Paragraph::new(err.as_str()).style(theme::style_error());
+            frame.render_widget(err_para, chunks[2]);
+        }
+
+        let hint = Paragraph::new(Line::from(vec![
+            Span::styled("Tab", theme::style_accent()),
+            Span::raw(" switch field  "),
+            Span::styled("Enter", theme::style_accent()),
+            Span::raw(" import  "),
+            Span::styled("Esc", theme::style_accent()),
+            Span::raw(" cancel"),
+        ]))
+        .alignment(Alignment::Center)
+        .style(theme::style_muted());
+        frame.render_widget(hint, chunks[3]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(form: &mut ImportForm, text: &str) {
+        for c in text.chars() {
+            form.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_submit_needs_path_and_password() {
+        let mut form = ImportForm::new();
+        assert!(matches!(
+            form.handle_key(KeyEvent::from(KeyCode::Enter)),
+            Action::None
+        ));
+        assert!(form.on_password);
+        assert!(matches!(form.submit(), Action::None));
+        assert!(!form.on_password);
+
+        type_text(&mut form, "backup.vlta");
+        form.handle_key(KeyEvent::from(KeyCode::Enter));
+        type_text(&mut form, "share");
+        let action = form.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(matches!(
+            action,
+            Action::StartImport(ref path, ref password)
+                if path == &PathBuf::from("backup.vlta") && password.as_str() == "share"
+        ));
+        assert!(form.password.is_empty());
+    }
+}
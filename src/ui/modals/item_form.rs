@@ -1,3 +1,6 @@
+use std::ops::Range;
+use std::path::PathBuf;
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
 use ratatui::text::{Line, Span};
@@ -5,12 +8,16 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 use uuid::Uuid;
 
-use crate::core::models::{Group, Item};
+use crate::core::models::{Attachment, Group, Item, ItemKind};
+use crate::core::username_generator;
 use crate::core::vault_service::ItemDraft;
 use crate::ui::theme;
 use crate::ui::{Action, Component};
 
-const FIELD_COUNT: usize = 7;
+const FIELD_COUNT: usize = 13;
+const DEFAULT_HANDLE_LENGTH: usize = 12;
+/// Suggestions listed under the Tags field.
+const MAX_TAG_SUGGESTIONS: usize = 5;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Field {
@@ -18,9 +25,15 @@ enum Field {
     Username,
     Password,
     Url,
+    MoreUrls,
     Notes,
     Tags,
+    Attachments,
+    Totp,
     Group,
+    PublicKey,
+    PrivateKey,
+    LaunchCommand,
 }
 
 const FIELDS: [Field; FIELD_COUNT] = [
@@ -28,17 +41,110 @@ const FIELDS: [Field; FIELD_COUNT] = [
     Field::Username,
     Field::Password,
     Field::Url,
+    Field::MoreUrls,
+    Field::Notes,
+    Field::Tags,
+    Field::Attachments,
+    Field::Totp,
+    Field::Group,
+    Field::PublicKey,
+    Field::PrivateKey,
+    Field::LaunchCommand,
+];
+
+/// Fields shown for a login item.
+const LOGIN_FIELDS: [Field; 11] = [
+    Field::Title,
+    Field::Username,
+    Field::Password,
+    Field::Url,
+    Field::MoreUrls,
+    Field::Notes,
+    Field::Tags,
+    Field::Attachments,
+    Field::Totp,
+    Field::LaunchCommand,
+    Field::Group,
+];
+
+/// Fields shown for an SSH key item.
+const SSH_KEY_FIELDS: [Field; 7] = [
+    Field::Title,
+    Field::PublicKey,
+    Field::PrivateKey,
     Field::Notes,
     Field::Tags,
+    Field::LaunchCommand,
     Field::Group,
 ];
 
+impl Field {
+    /// Name used for the field in `item_form_field_order`.
+    fn config_name(self) -> &'static str {
+        match self {
+            Field::Title => "title",
+            Field::Username => "username",
+            Field::Password => "password",
+            Field::Url => "url",
+            Field::MoreUrls => "more_urls",
+            Field::Notes => "notes",
+            Field::Tags => "tags",
+            Field::Attachments => "attachments",
+            Field::Totp => "totp",
+            Field::Group => "group",
+            Field::PublicKey => "public_key",
+            Field::PrivateKey => "private_key",
+            Field::LaunchCommand => "launch_command",
+        }
+    }
+}
+
+/// Parse a configured field order, which must name every field exactly once.
+fn parse_field_order(names: &[String]) -> std::result::Result<Vec<Field>, String> {
+    let mut order = Vec::with_capacity(FIELD_COUNT);
+    for name in names {
+        let field = FIELDS
+            .into_iter()
+            .find(|f| f.config_name() == name)
+            .ok_or_else(|| format!("unknown field \"{name}\""))?;
+        if order.contains(&field) {
+            return Err(format!("\"{name}\" is listed twice"));
+        }
+        order.push(field);
+    }
+    match FIELDS.into_iter().find(|f| !order.contains(f)) {
+        Some(missing) => Err(format!("\"{}\" is missing", missing.config_name())),
+        None => Ok(order),
+    }
+}
+
 pub struct ItemForm {
     editing_id: Option<Uuid>,
     field_values: [String; FIELD_COUNT],
+    /// Index into the visible fields for the current kind.
     current_field: usize,
+    /// Edit an SSH key instead of a login; toggled with Ctrl+T.
+    ssh_key: bool,
     groups: Vec<(Uuid, String)>,
     selected_group_index: Option<usize>, // None = no group
+    /// Base email for Ctrl+U aliases; random handles are generated without one.
+    alias_base: Option<String>,
+    handle_length: usize,
+    /// Mask the notes like the password; toggled with Ctrl+N.
+    notes_secret: bool,
+    /// Confirm every reveal or copy of the password; toggled with Ctrl+E.
+    sensitive: bool,
+    /// Values when the form opened, for the unsaved-edits check on Esc.
+    initial: (Vec<String>, Option<usize>, bool, bool, bool),
+    /// Ask before Esc throws away edits.
+    confirm_discard: bool,
+    /// Tags already in the vault, offered as completions in the Tags field.
+    known_tags: Vec<String>,
+    /// Save typed tags with the casing of the matching known tag.
+    normalize_tag_case: bool,
+    /// Fields shown for each kind, in display order.
+    login_fields: Vec<Field>,
+    ssh_key_fields: Vec<Field>,
 }
 
 impl ItemForm {
@@ -48,13 +154,26 @@ impl ItemForm {
         let selected_group_index =
             default_group.and_then(|gid| group_list.iter().position(|g| g.0 == gid));
 
-        Self {
+        let mut form = Self {
             editing_id: None,
             field_values: Default::default(),
             current_field: 0,
+            ssh_key: false,
             groups: group_list,
             selected_group_index,
-        }
+            alias_base: None,
+            handle_length: DEFAULT_HANDLE_LENGTH,
+            notes_secret: false,
+            sensitive: false,
+            initial: Default::default(),
+            confirm_discard: false,
+            known_tags: Vec::new(),
+            normalize_tag_case: false,
+            login_fields: LOGIN_FIELDS.to_vec(),
+            ssh_key_fields: SSH_KEY_FIELDS.to_vec(),
+        };
+        form.initial = form.state();
+        form
     }
 
     pub fn new_edit(item: &Item, groups: &[Group]) -> Self {
@@ -64,53 +183,320 @@ impl ItemForm {
             .group_id
             .and_then(|gid| group_list.iter().position(|g| g.0 == gid));
 
-        let field_values = [
-            item.title.clone(),
-            item.username.clone(),
-            item.password.clone(),
-            item.url.clone(),
-            item.notes.clone(),
-            item.tags.join(", "),
-            String::new(), // Group handled by selected_group_index
-        ];
-
-        Self {
+        let mut form = Self {
             editing_id: Some(item.id),
-            field_values,
+            field_values: Default::default(),
             current_field: 0,
+            ssh_key: matches!(item.kind, ItemKind::SshKey { .. }),
             groups: group_list,
             selected_group_index,
+            alias_base: None,
+            handle_length: DEFAULT_HANDLE_LENGTH,
+            notes_secret: item.notes_secret,
+            sensitive: item.sensitive,
+            initial: Default::default(),
+            confirm_discard: false,
+            known_tags: Vec::new(),
+            normalize_tag_case: false,
+            login_fields: LOGIN_FIELDS.to_vec(),
+            ssh_key_fields: SSH_KEY_FIELDS.to_vec(),
+        };
+        *form.value_mut(Field::Title) = item.title.clone();
+        *form.value_mut(Field::Username) = item.username.clone();
+        *form.value_mut(Field::Password) = item.password.clone();
+        *form.value_mut(Field::Url) = item.url.clone();
+        *form.value_mut(Field::MoreUrls) = item.urls.join("; ");
+        *form.value_mut(Field::Notes) = item.notes.clone();
+        *form.value_mut(Field::Tags) = item.tags.join(", ");
+        *form.value_mut(Field::Attachments) = item
+            .attachments
+            .iter()
+            .map(|a| a.path.display().to_string())
+            .collect::<Vec<_>>()
+            .join("; ");
+        *form.value_mut(Field::Totp) = item.otp_secret.clone().unwrap_or_default();
+        *form.value_mut(Field::LaunchCommand) = item.launch_command.clone().unwrap_or_default();
+        if let ItemKind::SshKey {
+            ref private_key,
+            ref public_key,
+        } = item.kind
+        {
+            *form.value_mut(Field::PublicKey) = public_key.clone();
+            *form.value_mut(Field::PrivateKey) = private_key.clone();
+        }
+        // The group is tracked by selected_group_index.
+        form.initial = form.state();
+        form
+    }
+
+    /// The title as typed so far.
+    pub fn title(&self) -> &str {
+        self.value(Field::Title)
+    }
+
+    pub fn set_confirm_discard(&mut self, confirm: bool) {
+        self.confirm_discard = confirm;
+    }
+
+    /// Offer `known_tags` as completions, and optionally adopt their casing on save.
+    pub fn set_known_tags(&mut self, known_tags: Vec<String>, normalize_case: bool) {
+        self.known_tags = known_tags;
+        self.normalize_tag_case = normalize_case;
+    }
+
+    /// Known tags starting with the tag being typed (the text after the last comma),
+    /// leaving out ones the field already has.
+    fn tag_suggestions(&self) -> Vec<&str> {
+        let value = self.value(Field::Tags);
+        let (entered, partial) = match value.rsplit_once(',') {
+            Some((entered, partial)) => (entered, partial.trim_start()),
+            None => ("", value.trim_start()),
+        };
+        if partial.is_empty() {
+            return Vec::new();
+        }
+        let partial = partial.to_lowercase();
+        self.known_tags
+            .iter()
+            .filter(|tag| tag.to_lowercase().starts_with(&partial))
+            .filter(|tag| {
+                !entered
+                    .split(',')
+                    .any(|t| t.trim().eq_ignore_ascii_case(tag))
+            })
+            .filter(|tag| !tag.eq_ignore_ascii_case(&partial))
+            .take(MAX_TAG_SUGGESTIONS)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Replace the tag being typed with the first suggestion.
+    fn complete_tag(&mut self) -> bool {
+        let Some(suggestion) = self.tag_suggestions().first().map(|s| s.to_string()) else {
+            return false;
+        };
+        let value = self.value_mut(Field::Tags);
+        match value.rfind(',') {
+            Some(comma) => {
+                value.truncate(comma + 1);
+                value.push(' ');
+            }
+            None => value.clear(),
         }
+        value.push_str(&suggestion);
+        true
+    }
+
+    fn state(&self) -> (Vec<String>, Option<usize>, bool, bool, bool) {
+        (
+            self.field_values.to_vec(),
+            self.selected_group_index,
+            self.notes_secret,
+            self.ssh_key,
+            self.sensitive,
+        )
+    }
+
+    /// Whether the item is marked sensitive, so copies of its secrets are confirmed.
+    pub fn is_sensitive(&self) -> bool {
+        self.sensitive
+    }
+
+    /// Whether anything differs from the values the form opened with.
+    pub fn is_dirty(&self) -> bool {
+        self.state() != self.initial
+    }
+
+    fn index_of(field: Field) -> usize {
+        FIELDS.iter().position(|f| *f == field).unwrap_or(0)
+    }
+
+    /// Show the fields in the order `names` gives (see `Field::config_name`). An empty
+    /// list keeps the default order, as does an invalid one, which is reported.
+    pub fn set_field_order(&mut self, names: &[String]) -> std::result::Result<(), String> {
+        if names.is_empty() {
+            return Ok(());
+        }
+        let order = parse_field_order(names)?;
+        let shown = |kind: &[Field]| order.iter().copied().filter(|f| kind.contains(f)).collect();
+        self.login_fields = shown(&LOGIN_FIELDS);
+        self.ssh_key_fields = shown(&SSH_KEY_FIELDS);
+        Ok(())
+    }
+
+    /// The fields shown for the item kind being edited.
+    fn fields(&self) -> &[Field] {
+        if self.ssh_key {
+            &self.ssh_key_fields
+        } else {
+            &self.login_fields
+        }
+    }
+
+    fn focused(&self) -> Field {
+        self.fields()[self.current_field]
+    }
+
+    #[cfg(test)]
+    fn position(&self, field: Field) -> usize {
+        self.fields().iter().position(|f| *f == field).unwrap_or(0)
+    }
+
+    fn value(&self, field: Field) -> &str {
+        &self.field_values[Self::index_of(field)]
+    }
+
+    fn value_mut(&mut self, field: Field) -> &mut String {
+        &mut self.field_values[Self::index_of(field)]
+    }
+
+    /// Configure what Ctrl+U generates in the Username field.
+    pub fn set_username_generator(&mut self, alias_base: Option<String>, handle_length: usize) {
+        self.alias_base = alias_base;
+        self.handle_length = handle_length;
+    }
+
+    /// Fill the Username field with an alias of the base email, or a random handle.
+    fn generate_username(&mut self) -> Action {
+        let generated = match self.alias_base {
+            Some(ref base) => {
+                let tag = username_generator::alias_tag_for(
+                    self.value(Field::Url),
+                    self.value(Field::Title),
+                );
+                if tag.is_empty() {
+                    return Action::SetStatus("Enter a title or URL for the alias".to_string());
+                }
+                match username_generator::generate_plus_alias(base, &tag) {
+                    Ok(alias) => alias,
+                    Err(e) => return Action::SetStatus(e.to_string()),
+                }
+            }
+            None => username_generator::generate_handle(self.handle_length),
+        };
+        *self.value_mut(Field::Username) = generated;
+        Action::None
     }
 
     pub fn set_password(&mut self, password: String) {
-        self.field_values[2] = password;
+        *self.value_mut(Field::Password) = password;
     }
 
     fn current_value(&mut self) -> &mut String {
-        &mut self.field_values[self.current_field]
+        self.value_mut(self.focused())
+    }
+
+    /// Text of the focused field as it would be saved (the group field yields the group name).
+    fn current_text(&self) -> String {
+        match self.focused() {
+            Field::Group => self
+                .selected_group_index
+                .and_then(|i| self.groups.get(i))
+                .map(|(_, name)| name.clone())
+                .unwrap_or_default(),
+            field => self.value(field).to_string(),
+        }
     }
 
     fn build_draft(&self) -> ItemDraft {
-        let tags: Vec<String> = self.field_values[5]
-            .split(',')
-            .map(|s| s.trim().to_string())
+        let mut tags: Vec<String> = Vec::new();
+        for tag in self.value(Field::Tags).split(',').map(str::trim) {
+            if tag.is_empty() {
+                continue;
+            }
+            let tag = match self.known_tags.iter().find(|t| t.eq_ignore_ascii_case(tag)) {
+                Some(known) if self.normalize_tag_case => known.clone(),
+                _ => tag.to_string(),
+            };
+            if !self.normalize_tag_case || !tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+                tags.push(tag);
+            }
+        }
+
+        let attachments = self
+            .value(Field::Attachments)
+            .split(';')
+            .map(str::trim)
             .filter(|s| !s.is_empty())
+            .map(|s| Attachment::from_path(PathBuf::from(s)))
             .collect();
 
         let group_id = self
             .selected_group_index
             .and_then(|i| self.groups.get(i).map(|(id, _)| *id));
 
+        let urls = self
+            .value(Field::MoreUrls)
+            .split(';')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        let otp_secret = self.value(Field::Totp).trim();
+        let otp_secret = (!otp_secret.is_empty()).then(|| otp_secret.to_string());
+
+        let launch_command = self.value(Field::LaunchCommand).trim();
+        let launch_command = (!launch_command.is_empty()).then(|| launch_command.to_string());
+
+        let kind = if self.ssh_key {
+            ItemKind::SshKey {
+                private_key: self.value(Field::PrivateKey).to_string(),
+                public_key: self.value(Field::PublicKey).to_string(),
+            }
+        } else {
+            ItemKind::Login
+        };
+
         ItemDraft {
-            title: self.field_values[0].clone(),
-            username: self.field_values[1].clone(),
-            password: self.field_values[2].clone(),
-            url: self.field_values[3].clone(),
-            notes: self.field_values[4].clone(),
+            title: self.value(Field::Title).to_string(),
+            username: self.value(Field::Username).to_string(),
+            password: self.value(Field::Password).to_string(),
+            url: self.value(Field::Url).to_string(),
+            urls,
+            notes: self.value(Field::Notes).to_string(),
             tags,
             group_id,
+            attachments,
+            otp_secret,
+            notes_secret: self.notes_secret,
+            kind,
+            launch_command,
+            sensitive: self.sensitive,
+        }
+    }
+
+    /// Rows a field takes in the form, borders included; keys span several lines.
+    fn field_height(field: Field) -> u16 {
+        match field {
+            Field::PublicKey | Field::PrivateKey => 5,
+            _ => 3,
+        }
+    }
+
+    /// Range of fields to draw in `available` rows, keeping `focused` in view.
+    ///
+    /// Shows as many fields before the focused one as fit, then fills the rest
+    /// of the space with the fields after it.
+    fn visible_fields(heights: &[u16], focused: usize, available: u16) -> Range<usize> {
+        let focused = focused.min(heights.len().saturating_sub(1));
+        let mut start = focused;
+        let mut used = heights.get(focused).copied().unwrap_or(0);
+        while start > 0 && used + heights[start - 1] <= available {
+            start -= 1;
+            used += heights[start];
+        }
+        let mut end = (focused + 1).min(heights.len());
+        while end < heights.len() && used + heights[end] <= available {
+            used += heights[end];
+            end += 1;
         }
+        start..end
+    }
+
+    /// Fields whose value is hidden unless focused.
+    fn is_masked(field: Field) -> bool {
+        matches!(field, Field::Password | Field::Totp | Field::PrivateKey)
     }
 
     fn field_label(field: Field) -> &'static str {
@@ -119,9 +505,15 @@ impl ItemForm {
             Field::Username => "Username",
             Field::Password => "Password",
             Field::Url => "URL",
+            Field::MoreUrls => "More URLs (;-separated)",
             Field::Notes => "Notes",
             Field::Tags => "Tags (comma-separated)",
+            Field::Attachments => "Attachments (file paths, ;-separated)",
+            Field::Totp => "TOTP secret",
             Field::Group => "Group",
+            Field::PublicKey => "Public key",
+            Field::PrivateKey => "Private key",
+            Field::LaunchCommand => "Launch command ({username}, {url}, ...)",
         }
     }
 }
@@ -129,14 +521,17 @@ impl ItemForm {
 impl Component for ItemForm {
     fn handle_key(&mut self, key: KeyEvent) -> Action {
         match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) if self.confirm_discard && self.is_dirty() => {
+                Action::ConfirmDiscardForm
+            }
             (KeyCode::Esc, _) => Action::CloseModal,
             (KeyCode::Tab, _) | (KeyCode::Down, _) => {
-                self.current_field = (self.current_field + 1) % FIELD_COUNT;
+                self.current_field = (self.current_field + 1) % self.fields().len();
                 Action::None
             }
             (KeyCode::BackTab, _) | (KeyCode::Up, _) => {
                 self.current_field = if self.current_field == 0 {
-                    FIELD_COUNT - 1
+                    self.fields().len() - 1
                 } else {
                     self.current_field - 1
                 };
@@ -144,7 +539,7 @@ impl Component for ItemForm {
             }
             (KeyCode::Enter, KeyModifiers::CONTROL)
             | (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
-                if self.field_values[0].is_empty() {
+                if self.value(Field::Title).is_empty() {
                     Action::SetStatus("Title is required".to_string())
                 } else {
                     let draft = self.build_draft();
@@ -155,9 +550,62 @@ impl Component for ItemForm {
                 }
             }
             (KeyCode::Char('p'), KeyModifiers::CONTROL) => Action::OpenPasswordGenerator,
+            (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
+                self.notes_secret = !self.notes_secret;
+                Action::None
+            }
+            (KeyCode::Char('e'), KeyModifiers::CONTROL) => {
+                self.sensitive = !self.sensitive;
+                Action::None
+            }
+            (KeyCode::Char('t'), KeyModifiers::CONTROL) => {
+                // Keep the focus on the same field when the other kind has it.
+                let focused = self.focused();
+                self.ssh_key = !self.ssh_key;
+                self.current_field = self
+                    .fields()
+                    .iter()
+                    .position(|f| *f == focused)
+                    .unwrap_or(0);
+                Action::None
+            }
+            (KeyCode::Enter, _)
+                if matches!(self.focused(), Field::PublicKey | Field::PrivateKey) =>
+            {
+                self.current_value().push('\n');
+                Action::None
+            }
+            (KeyCode::Right, _) if self.focused() == Field::Tags => {
+                self.complete_tag();
+                Action::None
+            }
+            (KeyCode::Char('u'), KeyModifiers::CONTROL) if self.focused() == Field::Username => {
+                self.generate_username()
+            }
+            (KeyCode::Char('y'), KeyModifiers::CONTROL) => {
+                let text = self.current_text();
+                let field = self.focused();
+                if text.is_empty() {
+                    Action::SetStatus("Nothing to copy".to_string())
+                } else {
+                    let label = Self::field_label(field)
+                        .split(" (")
+                        .next()
+                        .unwrap_or("Field");
+                    let source = match self.title() {
+                        "" => "New item",
+                        title => title,
+                    };
+                    if Self::is_masked(field) {
+                        Action::copy_secret(source, text, label, true)
+                    } else {
+                        Action::copy_text(source, text, label, false)
+                    }
+                }
+            }
             _ => {
                 // Group field uses left/right to cycle
-                if FIELDS[self.current_field] == Field::Group {
+                if self.focused() == Field::Group {
                     match key.code {
                         KeyCode::Left | KeyCode::Char('h') => {
                             self.selected_group_index = match self.selected_group_index {
@@ -208,7 +656,9 @@ impl Component for ItemForm {
 
     fn render(&self, frame: &mut Frame, area: Rect) {
         let width = 60u16.min(area.width.saturating_sub(4));
-        let height = (FIELD_COUNT as u16 * 3 + 6).min(area.height.saturating_sub(2));
+        let fields = self.fields();
+        let fields_height: u16 = fields.iter().map(|f| Self::field_height(*f)).sum();
+        let height = (fields_height + 6).min(area.height.saturating_sub(2));
 
         let vert = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
         let horiz = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
@@ -217,31 +667,64 @@ impl Component for ItemForm {
 
         frame.render_widget(Clear, center);
 
-        let title = if self.editing_id.is_some() {
-            " Edit Item "
-        } else {
-            " New Item "
+        let title = match (self.editing_id.is_some(), self.ssh_key) {
+            (true, false) => " Edit Item ",
+            (false, false) => " New Item ",
+            (true, true) => " Edit SSH Key ",
+            (false, true) => " New SSH Key ",
         };
 
-        let block = Block::default()
+        let mut block = Block::default()
             .title(title)
             .title_alignment(Alignment::Center)
             .borders(Borders::ALL)
             .border_style(theme::style_border(true));
 
+        // On short terminals only the fields around the focused one are drawn.
+        let heights: Vec<u16> = fields.iter().map(|f| Self::field_height(*f)).collect();
+        let available = height.saturating_sub(4);
+        let window = Self::visible_fields(&heights, self.current_field, available);
+        if window.len() < fields.len() {
+            let more_above = if window.start > 0 { "↑" } else { " " };
+            let more_below = if window.end < fields.len() {
+                "↓"
+            } else {
+                " "
+            };
+            block = block.title_bottom(
+                Line::from(Span::styled(
+                    format!(" {more_above}{more_below} more fields "),
+                    theme::style_muted(),
+                ))
+                .right_aligned(),
+            );
+        }
+
         let inner = block.inner(center);
         frame.render_widget(block, center);
 
-        let mut constraints: Vec<Constraint> =
-            FIELDS.iter().map(|_| Constraint::Length(3)).collect();
+        let mut constraints: Vec<Constraint> = heights[window.clone()]
+            .iter()
+            .map(|h| Constraint::Length(*h))
+            .collect();
         constraints.push(Constraint::Length(2)); // hints
         constraints.push(Constraint::Min(0));
 
         let chunks = Layout::vertical(constraints).split(inner);
 
-        for (i, field) in FIELDS.iter().enumerate() {
+        for (chunk, (i, field)) in fields
+            .iter()
+            .enumerate()
+            .skip(window.start)
+            .take(window.len())
+            .enumerate()
+        {
             let is_current = i == self.current_field;
-            let label = Self::field_label(*field);
+            let label = match field {
+                Field::Notes if self.notes_secret => "Notes (secret)",
+                Field::Password if self.sensitive => "Password (sensitive)",
+                _ => Self::field_label(*field),
+            };
 
             let value_display = if *field == Field::Group {
                 match self.selected_group_index {
@@ -249,13 +732,15 @@ impl Component for ItemForm {
                     Some(idx) => format!("< {} >", self.groups[idx].1),
                 }
             } else {
-                let val = &self.field_values[i];
+                let val = self.value(*field);
                 if val.is_empty() {
                     format!("{label}...")
-                } else if *field == Field::Password && !is_current {
+                } else if (Self::is_masked(*field) || (*field == Field::Notes && self.notes_secret))
+                    && !is_current
+                {
                     theme::PASSWORD_MASK.to_string()
                 } else {
-                    val.clone()
+                    val.to_string()
                 }
             };
 
@@ -265,7 +750,7 @@ impl Component for ItemForm {
                 theme::style_muted()
             };
 
-            let field_block = Block::default()
+            let mut field_block = Block::default()
                 .title(format!(" {label} "))
                 .title_style(if is_current {
                     theme::style_accent()
@@ -274,23 +759,37 @@ impl Component for ItemForm {
                 })
                 .borders(Borders::ALL)
                 .border_style(theme::style_border(is_current));
+            if is_current && *field == Field::Tags {
+                let suggestions = self.tag_suggestions();
+                if !suggestions.is_empty() {
+                    field_block = field_block.title_bottom(Line::from(vec![
+                        Span::styled(" → ", theme::style_accent()),
+                        Span::styled(format!("{} ", suggestions.join(", ")), theme::style_muted()),
+                    ]));
+                }
+            }
 
-            let content = if is_current && *field != Field::Group {
-                Line::from(vec![
-                    Span::raw(&value_display),
-                    Span::styled("█", theme::style_accent()),
-                ])
+            let text_style = if self.value(*field).is_empty() && *field != Field::Group {
+                theme::style_muted()
             } else {
-                let text_style = if self.field_values[i].is_empty() && *field != Field::Group {
-                    theme::style_muted()
-                } else {
-                    style
-                };
-                Line::from(Span::styled(value_display, text_style))
+                style
             };
+            let mut lines: Vec<Line> = value_display
+                .split('\n')
+                .map(|l| Line::from(Span::styled(l.to_string(), text_style)))
+                .collect();
+            if is_current && *field != Field::Group {
+                if let Some(last) = lines.last_mut() {
+                    last.spans.push(Span::styled("█", theme::style_accent()));
+                }
+                // Keep the cursor line in view for multi-line keys.
+                let visible = Self::field_height(*field).saturating_sub(2) as usize;
+                let skip = lines.len().saturating_sub(visible);
+                lines.drain(..skip);
+            }
 
-            let para = Paragraph::new(content).block(field_block);
-            frame.render_widget(para, chunks[i]);
+            let para = Paragraph::new(lines).block(field_block);
+            frame.render_widget(para, chunks[chunk]);
         }
 
         // Hints
@@ -301,10 +800,323 @@ impl Component for ItemForm {
             Span::raw(" save  "),
             Span::styled("Ctrl+P", theme::style_accent()),
             Span::raw(" gen pw  "),
+            Span::styled("Ctrl+Y", theme::style_accent()),
+            Span::raw(" copy  "),
+            Span::styled("Ctrl+N", theme::style_accent()),
+            Span::raw(" secret notes  "),
+            Span::styled("Ctrl+E", theme::style_accent()),
+            Span::raw(" sensitive  "),
+            Span::styled("Ctrl+T", theme::style_accent()),
+            Span::raw(" kind  "),
             Span::styled("Esc", theme::style_accent()),
             Span::raw(" cancel"),
         ]))
         .style(theme::style_muted());
-        frame.render_widget(hints, chunks[FIELD_COUNT]);
+        frame.render_widget(hints, chunks[window.len()]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    #[test]
+    fn test_dirty_detection_and_discard_prompt() {
+        let mut item = Item::new("GitHub".to_string(), None);
+        item.notes = "codes".to_string();
+        let mut form = ItemForm::new_edit(&item, &[]);
+        form.set_confirm_discard(true);
+        assert!(!form.is_dirty());
+        assert!(matches!(
+            form.handle_key(KeyEvent::from(KeyCode::Esc)),
+            Action::CloseModal
+        ));
+
+        form.handle_key(KeyEvent::from(KeyCode::Char('x')));
+        assert!(form.is_dirty());
+        assert!(matches!(
+            form.handle_key(KeyEvent::from(KeyCode::Esc)),
+            Action::ConfirmDiscardForm
+        ));
+
+        // Undoing the edit makes the form clean again.
+        form.handle_key(KeyEvent::from(KeyCode::Backspace));
+        assert!(!form.is_dirty());
+        form.handle_key(ctrl('n'));
+        assert!(form.is_dirty());
+        form.handle_key(ctrl('n'));
+        form.handle_key(ctrl('e'));
+        assert!(form.is_dirty());
+        assert!(form.build_draft().sensitive);
+
+        form.set_confirm_discard(false);
+        assert!(matches!(
+            form.handle_key(KeyEvent::from(KeyCode::Esc)),
+            Action::CloseModal
+        ));
+    }
+
+    #[test]
+    fn test_copy_carries_current_field_value() {
+        let groups = [Group::new("Work".to_string(), None)];
+        let mut form = ItemForm::new_create(&groups, Some(groups[0].id));
+        for c in "https://example.com".chars() {
+            form.value_mut(Field::Url).push(c);
+        }
+        form.set_password("typed-secret".to_string());
+
+        form.current_field = form.position(Field::Url);
+        assert!(
+            matches!(form.handle_key(ctrl('y')), Action::CopyText { text, label, sensitive, .. }
+                if text.as_str() == "https://example.com" && label == "URL" && !sensitive)
+        );
+
+        form.current_field = form.position(Field::Password);
+        assert!(
+            matches!(form.handle_key(ctrl('y')), Action::CopyText { text, sensitive, secret, .. } if text.as_str() == "typed-secret" && sensitive && secret)
+        );
+
+        form.current_field = form.position(Field::Group);
+        assert!(
+            matches!(form.handle_key(ctrl('y')), Action::CopyText { text, .. } if text.as_str() == "Work")
+        );
+
+        form.current_field = form.position(Field::Username);
+        assert!(matches!(form.handle_key(ctrl('y')), Action::SetStatus(_)));
+    }
+
+    #[test]
+    fn test_ssh_key_form_fields() {
+        let mut form = ItemForm::new_create(&[], None);
+        assert_eq!(form.fields(), LOGIN_FIELDS);
+        form.handle_key(ctrl('t'));
+        assert_eq!(
+            form.fields(),
+            [
+                Field::Title,
+                Field::PublicKey,
+                Field::PrivateKey,
+                Field::Notes,
+                Field::Tags,
+                Field::LaunchCommand,
+                Field::Group,
+            ]
+        );
+
+        form.current_field = form.position(Field::PrivateKey);
+        for c in "line1".chars() {
+            form.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+        form.handle_key(KeyEvent::from(KeyCode::Enter));
+        form.handle_key(KeyEvent::from(KeyCode::Char('x')));
+        assert!(matches!(
+            form.handle_key(ctrl('y')),
+            Action::CopyText {
+                sensitive: true,
+                ..
+            }
+        ));
+        assert_eq!(
+            form.build_draft().kind,
+            ItemKind::SshKey {
+                private_key: "line1\nx".to_string(),
+                public_key: String::new(),
+            }
+        );
+
+        let mut item = Item::new("deploy".to_string(), None);
+        item.kind = form.build_draft().kind;
+        let form = ItemForm::new_edit(&item, &[]);
+        assert_eq!(form.fields(), SSH_KEY_FIELDS);
+        assert_eq!(form.value(Field::PrivateKey), "line1\nx");
+    }
+
+    #[test]
+    fn test_attachment_paths_parsed_into_draft() {
+        let mut item = Item::new("Server".to_string(), None);
+        item.attachments = vec![Attachment::from_path(PathBuf::from("/keys/a.pem"))];
+        let mut form = ItemForm::new_edit(&item, &[]);
+        assert_eq!(form.value(Field::Attachments), "/keys/a.pem");
+
+        form.value_mut(Field::Attachments)
+            .push_str(";  /docs/codes.pdf ; ");
+        let draft = form.build_draft();
+        let names: Vec<&str> = draft.attachments.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["a.pem", "codes.pdf"]);
+    }
+
+    #[test]
+    fn test_totp_secret_round_trips_through_form() {
+        let mut item = Item::new("GitHub".to_string(), None);
+        item.otp_secret = Some("JBSWY3DP".to_string());
+        let mut form = ItemForm::new_edit(&item, &[]);
+        assert_eq!(form.build_draft().otp_secret.as_deref(), Some("JBSWY3DP"));
+
+        *form.value_mut(Field::Totp) = "  ".to_string();
+        assert_eq!(form.build_draft().otp_secret, None);
+    }
+
+    fn tags_form(known: &[&str], typed: &str) -> ItemForm {
+        let mut form = ItemForm::new_create(&[], None);
+        form.set_known_tags(known.iter().map(|t| t.to_string()).collect(), true);
+        form.current_field = form.position(Field::Tags);
+        for c in typed.chars() {
+            form.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+        form
+    }
+
+    #[test]
+    fn test_tag_suggestions_match_the_tag_being_typed() {
+        let known = ["Dev", "devops", "email", "Work"];
+        assert_eq!(tags_form(&known, "de").tag_suggestions(), ["Dev", "devops"]);
+        assert_eq!(
+            tags_form(&known, "work, DEVO").tag_suggestions(),
+            ["devops"]
+        );
+        // Tags already entered, and an exact match, are not offered again.
+        assert_eq!(
+            tags_form(&known, "devops, dev").tag_suggestions(),
+            Vec::<&str>::new()
+        );
+        assert!(tags_form(&known, "email, ").tag_suggestions().is_empty());
+
+        let mut form = tags_form(&known, "email, wo");
+        form.handle_key(KeyEvent::from(KeyCode::Right));
+        assert_eq!(form.value(Field::Tags), "email, Work");
+    }
+
+    #[test]
+    fn test_typed_tags_take_the_existing_casing() {
+        let known = ["Dev", "Work"];
+        let form = tags_form(&known, "dev, new, DEV, work");
+        assert_eq!(form.build_draft().tags, ["Dev", "new", "Work"]);
+
+        let mut form = tags_form(&known, "dev, new");
+        form.set_known_tags(vec!["Dev".to_string()], false);
+        assert_eq!(form.build_draft().tags, ["dev", "new"]);
+    }
+
+    #[test]
+    fn test_more_urls_round_trip_through_form() {
+        let mut item = Item::new("Google".to_string(), None);
+        item.url = "https://accounts.google.com".to_string();
+        item.urls = vec!["https://youtube.com".to_string()];
+        let mut form = ItemForm::new_edit(&item, &[]);
+        assert_eq!(form.value(Field::MoreUrls), "https://youtube.com");
+
+        form.value_mut(Field::MoreUrls).push_str("; gmail.com ;");
+        let draft = form.build_draft();
+        assert_eq!(draft.url, "https://accounts.google.com");
+        assert_eq!(draft.urls, ["https://youtube.com", "gmail.com"]);
+    }
+
+    #[test]
+    fn test_ctrl_u_fills_username_only_when_focused() {
+        let mut form = ItemForm::new_create(&[], None);
+        *form.value_mut(Field::Url) = "https://github.com".to_string();
+
+        form.handle_key(ctrl('u'));
+        assert!(form.value(Field::Username).is_empty());
+
+        form.current_field = form.position(Field::Username);
+        form.handle_key(ctrl('u'));
+        assert_eq!(form.value(Field::Username).len(), DEFAULT_HANDLE_LENGTH);
+
+        form.set_username_generator(Some("me@x.com".to_string()), 8);
+        form.handle_key(ctrl('u'));
+        assert_eq!(form.value(Field::Username), "me+github@x.com");
+    }
+
+    #[test]
+    fn test_visible_fields_keep_focus_in_view() {
+        let heights = [3, 3, 3, 5, 3, 3];
+        // Everything fits.
+        assert_eq!(ItemForm::visible_fields(&heights, 0, 20), 0..6);
+        // Top of the form: as many fields as fit after the focused one.
+        assert_eq!(ItemForm::visible_fields(&heights, 0, 9), 0..3);
+        // Moving down scrolls so the focused field is the last one shown.
+        assert_eq!(ItemForm::visible_fields(&heights, 3, 9), 2..4);
+        assert_eq!(ItemForm::visible_fields(&heights, 5, 9), 4..6);
+        // A field taller than the space is still shown on its own.
+        assert_eq!(ItemForm::visible_fields(&heights, 3, 4), 3..4);
+        assert_eq!(ItemForm::visible_fields(&[], 0, 10), 0..0);
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_custom_field_order_applies_to_both_kinds() {
+        let order = names(&[
+            "title",
+            "url",
+            "username",
+            "password",
+            "group",
+            "public_key",
+            "private_key",
+            "more_urls",
+            "notes",
+            "tags",
+            "attachments",
+            "totp",
+            "launch_command",
+        ]);
+        let mut form = ItemForm::new_create(&[], None);
+        form.set_field_order(&order).unwrap();
+        assert_eq!(
+            form.fields()[..5],
+            [
+                Field::Title,
+                Field::Url,
+                Field::Username,
+                Field::Password,
+                Field::Group
+            ]
+        );
+        assert_eq!(form.fields().len(), LOGIN_FIELDS.len());
+        form.handle_key(KeyEvent::from(KeyCode::Tab));
+        assert_eq!(form.focused(), Field::Url);
+
+        form.handle_key(ctrl('t'));
+        assert_eq!(
+            form.fields(),
+            [
+                Field::Title,
+                Field::Group,
+                Field::PublicKey,
+                Field::PrivateKey,
+                Field::Notes,
+                Field::Tags,
+                Field::LaunchCommand,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_field_order_keeps_default() {
+        let mut all: Vec<String> = FIELDS.iter().map(|f| f.config_name().to_string()).collect();
+        let mut form = ItemForm::new_create(&[], None);
+
+        let missing = &all[1..];
+        assert_eq!(
+            form.set_field_order(missing).unwrap_err(),
+            "\"title\" is missing"
+        );
+        all.push("title".to_string());
+        assert!(form.set_field_order(&all).unwrap_err().contains("twice"));
+        all.pop();
+        all[0] = "subject".to_string();
+        assert!(form.set_field_order(&all).unwrap_err().contains("unknown"));
+        assert_eq!(form.fields(), LOGIN_FIELDS);
+
+        form.set_field_order(&[]).unwrap();
+        assert_eq!(form.fields(), LOGIN_FIELDS);
     }
 }
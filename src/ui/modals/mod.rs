@@ -1,4 +1,14 @@
+pub mod changes_view;
 pub mod confirm_dialog;
+pub mod copy_log_view;
+pub mod event_log_view;
+pub mod filter_picker;
 pub mod group_form;
+pub mod import_form;
 pub mod item_form;
 pub mod password_generator_modal;
+pub mod password_prompt;
+#[cfg(feature = "qr")]
+pub mod qr_modal;
+pub mod vault_info_view;
+pub mod vault_settings;
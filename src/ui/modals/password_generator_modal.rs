@@ -1,19 +1,36 @@
+use std::collections::VecDeque;
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
+use zeroize::Zeroizing;
 
 use crate::core::password_generator::{self, PasswordConfig};
 use crate::ui::theme;
 use crate::ui::{Action, Component};
 
 const OPTION_COUNT: usize = 6;
+const MIN_LENGTH: usize = 4;
+const MAX_LENGTH: usize = 128;
+const DEFAULT_LENGTH_STEP: usize = 8;
+/// Candidates kept for `[` / `]`; older ones are dropped (and wiped).
+const HISTORY_CAPACITY: usize = 10;
 
 pub struct PasswordGeneratorModal {
     config: PasswordConfig,
     generated: String,
     current_option: usize,
+    /// Length change for Shift+Left/Right and PageUp/PageDown.
+    length_step: usize,
+    /// Candidates generated while the modal is open, oldest first. Lives only as
+    /// long as the modal and is never saved.
+    history: VecDeque<Zeroizing<String>>,
+    /// Index in `history` of the candidate currently shown.
+    history_pos: usize,
+    /// Why the current options cannot produce a password; shown instead of one.
+    error: Option<String>,
 }
 
 impl Default for PasswordGeneratorModal {
@@ -25,16 +42,82 @@ impl Default for PasswordGeneratorModal {
 impl PasswordGeneratorModal {
     pub fn new() -> Self {
         let config = PasswordConfig::default();
-        let generated = password_generator::generate_password(&config);
-        Self {
+        let mut modal = Self {
             config,
-            generated,
+            generated: String::new(),
             current_option: 0,
+            length_step: DEFAULT_LENGTH_STEP,
+            history: VecDeque::new(),
+            history_pos: 0,
+            error: None,
+        };
+        modal.regenerate();
+        modal
+    }
+
+    pub fn set_length_step(&mut self, step: usize) {
+        self.length_step = step.max(1);
+    }
+
+    pub fn set_ambiguous_chars(&mut self, chars: &str) {
+        self.config.ambiguous_chars = chars.to_string();
+        if self.config.exclude_ambiguous {
+            self.regenerate();
+        }
+    }
+
+    /// Change the length by `delta` (clamped) and regenerate when the length option is selected.
+    fn adjust_length(&mut self, delta: isize) {
+        if self.current_option != 0 {
+            return;
+        }
+        let length = step_length(self.config.length, delta);
+        if length != self.config.length {
+            self.config.length = length;
+            self.regenerate();
         }
     }
 
     fn regenerate(&mut self) {
-        self.generated = password_generator::generate_password(&self.config);
+        match password_generator::generate_password(&self.config) {
+            Ok(password) => {
+                self.generated = password;
+                self.error = None;
+            }
+            Err(e) => {
+                self.generated.clear();
+                self.error = Some(e.to_string());
+                return;
+            }
+        }
+        if self.history.len() >= HISTORY_CAPACITY {
+            self.history.pop_front();
+        }
+        self.history
+            .push_back(Zeroizing::new(self.generated.clone()));
+        self.history_pos = self.history.len() - 1;
+    }
+
+    /// Show the previous (`-1`) or next (`1`) candidate from the history, stopping at either end.
+    fn step_history(&mut self, delta: isize) {
+        let Some(pos) = self
+            .history_pos
+            .checked_add_signed(delta)
+            .filter(|pos| *pos < self.history.len())
+        else {
+            return;
+        };
+        self.history_pos = pos;
+        self.generated = self.history[pos].to_string();
+        self.error = None;
+    }
+
+    /// Forget every earlier candidate, keeping only the one shown.
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.history
+            .push_back(Zeroizing::new(self.generated.clone()));
+        self.history_pos = 0;
     }
 
     pub fn generated_password(&self) -> &str {
@@ -45,13 +128,27 @@ impl PasswordGeneratorModal {
 impl Component for PasswordGeneratorModal {
     fn handle_key(&mut self, key: KeyEvent) -> Action {
         match (key.code, key.modifiers) {
-            (KeyCode::Esc, _) => Action::CloseModal,
+            (KeyCode::Esc, _) => {
+                self.clear_history();
+                Action::CloseModal
+            }
             (KeyCode::Char('r'), _) => {
                 self.regenerate();
                 Action::None
             }
+            (KeyCode::Char('['), _) => {
+                self.step_history(-1);
+                Action::None
+            }
+            (KeyCode::Char(']'), _) => {
+                self.step_history(1);
+                Action::None
+            }
             (KeyCode::Enter, KeyModifiers::CONTROL)
             | (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+                if self.error.is_some() {
+                    return Action::None;
+                }
                 // "Use" the generated password
                 Action::UseGeneratedPassword
             }
@@ -69,12 +166,8 @@ impl Component for PasswordGeneratorModal {
             }
             (KeyCode::Char(' '), _) | (KeyCode::Enter, _) => {
                 match self.current_option {
-                    0 => {
-                        // Length: increase by 1
-                        if self.config.length < 128 {
-                            self.config.length += 1;
-                        }
-                    }
+                    // Length: increase by 1
+                    0 if self.config.length < MAX_LENGTH => self.config.length += 1,
                     1 => self.config.uppercase = !self.config.uppercase,
                     2 => self.config.lowercase = !self.config.lowercase,
                     3 => self.config.digits = !self.config.digits,
@@ -85,18 +178,20 @@ impl Component for PasswordGeneratorModal {
                 self.regenerate();
                 Action::None
             }
+            (KeyCode::Left, KeyModifiers::SHIFT) | (KeyCode::PageDown, _) => {
+                self.adjust_length(-(self.length_step as isize));
+                Action::None
+            }
+            (KeyCode::Right, KeyModifiers::SHIFT) | (KeyCode::PageUp, _) => {
+                self.adjust_length(self.length_step as isize);
+                Action::None
+            }
             (KeyCode::Left | KeyCode::Char('h'), _) => {
-                if self.current_option == 0 && self.config.length > 4 {
-                    self.config.length -= 1;
-                    self.regenerate();
-                }
+                self.adjust_length(-1);
                 Action::None
             }
             (KeyCode::Right | KeyCode::Char('l'), _) => {
-                if self.current_option == 0 && self.config.length < 128 {
-                    self.config.length += 1;
-                    self.regenerate();
-                }
+                self.adjust_length(1);
                 Action::None
             }
             _ => Action::None,
@@ -133,12 +228,18 @@ impl Component for PasswordGeneratorModal {
 
         // Generated password
         let pw_block = Block::default()
-            .title(" Generated ")
+            .title(format!(
+                " Generated {}/{} ",
+                self.history_pos + 1,
+                self.history.len()
+            ))
             .borders(Borders::ALL)
             .border_style(theme::style_border(false));
-        let pw = Paragraph::new(self.generated.as_str())
-            .style(theme::style_accent())
-            .block(pw_block);
+        let pw = match self.error {
+            Some(ref error) => Paragraph::new(error.as_str()).style(theme::style_warning()),
+            None => Paragraph::new(self.generated.as_str()).style(theme::style_accent()),
+        }
+        .block(pw_block);
         frame.render_widget(pw, chunks[0]);
 
         // Options
@@ -184,6 +285,8 @@ impl Component for PasswordGeneratorModal {
         let hints = Paragraph::new(Line::from(vec![
             Span::styled("[r]", theme::style_accent()),
             Span::raw(" regenerate  "),
+            Span::styled("[ ]", theme::style_accent()),
+            Span::raw(" history  "),
             Span::styled("[Space]", theme::style_accent()),
             Span::raw(" toggle  "),
             Span::styled("Ctrl+S", theme::style_accent()),
@@ -195,3 +298,102 @@ impl Component for PasswordGeneratorModal {
         frame.render_widget(hints, chunks[3]);
     }
 }
+
+/// Apply a length change, clamped to the generator's supported range.
+fn step_length(length: usize, delta: isize) -> usize {
+    length
+        .saturating_add_signed(delta)
+        .clamp(MIN_LENGTH, MAX_LENGTH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_length_clamps_to_bounds() {
+        assert_eq!(step_length(20, 1), 21);
+        assert_eq!(step_length(20, -8), 12);
+        assert_eq!(step_length(8, -8), MIN_LENGTH);
+        assert_eq!(step_length(4, -1), MIN_LENGTH);
+        assert_eq!(step_length(124, 8), MAX_LENGTH);
+        assert_eq!(step_length(128, 1), MAX_LENGTH);
+    }
+
+    #[test]
+    fn test_large_step_modifier() {
+        let mut modal = PasswordGeneratorModal::new();
+        modal.set_length_step(10);
+        let start = modal.config.length;
+
+        modal.handle_key(KeyEvent::new(KeyCode::Right, KeyModifiers::SHIFT));
+        assert_eq!(modal.config.length, start + 10);
+        assert_eq!(modal.generated_password().chars().count(), start + 10);
+
+        modal.handle_key(KeyEvent::from(KeyCode::PageDown));
+        modal.handle_key(KeyEvent::from(KeyCode::Left));
+        assert_eq!(modal.config.length, start - 1);
+
+        for _ in 0..20 {
+            modal.handle_key(KeyEvent::from(KeyCode::PageUp));
+        }
+        assert_eq!(modal.config.length, MAX_LENGTH);
+    }
+
+    #[test]
+    fn test_history_caps_cycles_and_clears() {
+        let mut modal = PasswordGeneratorModal::new();
+        let first = modal.generated_password().to_string();
+        for _ in 0..HISTORY_CAPACITY + 3 {
+            modal.handle_key(KeyEvent::from(KeyCode::Char('r')));
+        }
+        assert_eq!(modal.history.len(), HISTORY_CAPACITY);
+        assert!(!modal.history.iter().any(|p| p.as_str() == first));
+
+        let latest = modal.generated_password().to_string();
+        modal.handle_key(KeyEvent::from(KeyCode::Char('[')));
+        let previous = modal.generated_password().to_string();
+        assert_eq!(previous, modal.history[HISTORY_CAPACITY - 2].as_str());
+        modal.handle_key(KeyEvent::from(KeyCode::Char(']')));
+        assert_eq!(modal.generated_password(), latest);
+        // Stops at the newest entry.
+        modal.handle_key(KeyEvent::from(KeyCode::Char(']')));
+        assert_eq!(modal.generated_password(), latest);
+
+        modal.handle_key(KeyEvent::from(KeyCode::Char('[')));
+        assert!(matches!(
+            modal.handle_key(KeyEvent::from(KeyCode::Esc)),
+            Action::CloseModal
+        ));
+        assert_eq!(modal.history.len(), 1);
+        assert_eq!(modal.generated_password(), previous);
+    }
+
+    #[test]
+    fn test_everything_excluded_is_reported_and_cannot_be_used() {
+        let mut modal = PasswordGeneratorModal::new();
+        modal.config.exclude_ambiguous = true;
+        modal.set_ambiguous_chars("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!@#$%^&*()-_=+[]{}|;:,.<>?");
+        assert!(modal.error.is_some());
+        assert_eq!(modal.generated_password(), "");
+        assert!(matches!(
+            modal.handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)),
+            Action::None
+        ));
+
+        // Turning the exclusion back off recovers.
+        modal.current_option = 5;
+        modal.handle_key(KeyEvent::from(KeyCode::Char(' ')));
+        assert!(modal.error.is_none());
+        assert!(!modal.generated_password().is_empty());
+    }
+
+    #[test]
+    fn test_length_keys_ignored_on_other_options() {
+        let mut modal = PasswordGeneratorModal::new();
+        let start = modal.config.length;
+        modal.handle_key(KeyEvent::from(KeyCode::Down));
+        modal.handle_key(KeyEvent::from(KeyCode::PageUp));
+        assert_eq!(modal.config.length, start);
+    }
+}
@@ -0,0 +1,126 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::ui::theme;
+use crate::ui::{Action, Component};
+
+/// Asks for the master password again before running a protected action.
+pub struct PasswordPrompt {
+    message: String,
+    input: String,
+    error_message: Option<String>,
+    /// Dispatched by the app once the password has been verified.
+    pending_action: Action,
+}
+
+impl PasswordPrompt {
+    pub fn new(message: String, pending_action: Action) -> Self {
+        Self {
+            message,
+            input: String::new(),
+            error_message: None,
+            pending_action,
+        }
+    }
+
+    pub fn pending_action(&self) -> &Action {
+        &self.pending_action
+    }
+
+    pub fn set_error(&mut self, msg: String) {
+        self.input.clear();
+        self.error_message = Some(msg);
+    }
+}
+
+impl Component for PasswordPrompt {
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Esc => Action::CloseModal,
+            KeyCode::Enter => {
+                if self.input.is_empty() {
+                    self.error_message = Some("Password cannot be empty".to_string());
+                    Action::None
+                } else {
+                    Action::SubmitReauth(std::mem::take(&mut self.input))
+                }
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                self.error_message = None;
+                Action::None
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                Action::None
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let width = 50u16.min(area.width.saturating_sub(4));
+        let height = 9u16.min(area.height.saturating_sub(2));
+
+        let vert = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+        let horiz = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+        let [v] = vert.areas(area);
+        let [center] = horiz.areas(v);
+
+        frame.render_widget(Clear, center);
+
+        let block = Block::default()
+            .title(" Confirm Identity ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(theme::style_border(true));
+
+        let inner = block.inner(center);
+        frame.render_widget(block, center);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Message
+            Constraint::Length(3), // Password input
+            Constraint::Length(1), // Error message
+            Constraint::Min(0),    // Hint
+        ])
+        .split(inner);
+
+        let msg = Paragraph::new(self.message.as_str()).style(theme::style_default());
+        frame.render_widget(msg, chunks[0]);
+
+        let display = if self.input.is_empty() {
+            Span::styled("master password...", theme::style_muted())
+        } else {
+            Span::styled(
+                "•".repeat(self.input.chars().count()),
+                theme::style_default(),
+            )
+        };
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme::style_border(true));
+        frame.render_widget(
+            Paragraph::new(Line::from(display)).block(input_block),
+            chunks[1],
+        );
+
+        if let Some(ref err) = self.error_message {
+            let err_para = Paragraph::new(err.as_str()).style(theme::style_error());
+            frame.render_widget(err_para, chunks[2]);
+        }
+
+        let hint = Paragraph::new(Line::from(vec![
+            Span::styled("Enter", theme::style_accent()),
+            Span::raw(" confirm  "),
+            Span::styled("Esc", theme::style_accent()),
+            Span::raw(" cancel"),
+        ]))
+        .alignment(Alignment::Center)
+        .style(theme::style_muted());
+        frame.render_widget(hint, chunks[3]);
+    }
+}
@@ -0,0 +1,105 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+use zeroize::Zeroize;
+
+use crate::ui::theme;
+use crate::ui::{Action, Component};
+
+/// A field drawn as a QR code for scanning with a phone; any key closes it.
+pub struct QrModal {
+    label: String,
+    /// The rendered code; it encodes the field, so it is wiped on close.
+    lines: Vec<String>,
+    /// Warn that the code exposes a secret to anyone who can see the screen.
+    secret: bool,
+}
+
+impl QrModal {
+    pub fn new(label: &str, lines: Vec<String>, secret: bool) -> Self {
+        Self {
+            label: label.to_string(),
+            lines,
+            secret,
+        }
+    }
+}
+
+impl Drop for QrModal {
+    fn drop(&mut self) {
+        self.lines.zeroize();
+    }
+}
+
+impl Component for QrModal {
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Char(_) | KeyCode::Esc | KeyCode::Enter => Action::CloseModal,
+            _ => Action::None,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let code_width = self
+            .lines
+            .first()
+            .map_or(0, |line| line.chars().count() as u16);
+        let width = (code_width + 4).max(50).min(area.width.saturating_sub(2));
+        let height = (self.lines.len() as u16 + 6).min(area.height.saturating_sub(2));
+
+        let vert = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+        let horiz = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+        let [v] = vert.areas(area);
+        let [center] = horiz.areas(v);
+
+        frame.render_widget(Clear, center);
+
+        let block = Block::default()
+            .title(format!(" {} QR ", self.label))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(theme::style_border(true));
+
+        let inner = block.inner(center);
+        frame.render_widget(block, center);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(2), // Warning
+            Constraint::Min(1),    // Code
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+        if self.secret {
+            let warning = Paragraph::new(format!(
+                "⚠ The {} is on screen in this code; close it once scanned",
+                self.label.to_lowercase()
+            ))
+            .alignment(Alignment::Center)
+            .style(theme::style_warning())
+            .wrap(Wrap { trim: true });
+            frame.render_widget(warning, chunks[0]);
+        }
+
+        let code = if code_width > chunks[1].width || self.lines.len() as u16 > chunks[1].height {
+            Paragraph::new("Terminal too small for this QR code; enlarge it and try again")
+                .style(theme::style_warning())
+                .wrap(Wrap { trim: true })
+        } else {
+            Paragraph::new(
+                self.lines
+                    .iter()
+                    .map(|l| Line::raw(l.as_str()))
+                    .collect::<Vec<_>>(),
+            )
+        };
+        frame.render_widget(code.alignment(Alignment::Center), chunks[1]);
+
+        let hints = Paragraph::new("any key to close")
+            .alignment(Alignment::Center)
+            .style(theme::style_muted());
+        frame.render_widget(hints, chunks[2]);
+    }
+}
@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::ui::theme;
+use crate::ui::{Action, Component};
+
+/// Where the open vault lives, with shortcuts to copy the path or open its directory.
+pub struct VaultInfoView {
+    path: PathBuf,
+}
+
+impl VaultInfoView {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    fn directory(&self) -> PathBuf {
+        match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        }
+    }
+}
+
+impl Component for VaultInfoView {
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) | (KeyCode::Char('q'), _) => Action::CloseModal,
+            (KeyCode::Char('p'), KeyModifiers::CONTROL) => Action::CloseModal,
+            // The path is not secret, so it is copied without the auto-clear timer.
+            (KeyCode::Char('c'), KeyModifiers::NONE) => Action::copy_text(
+                "Vault",
+                self.path.display().to_string(),
+                "Vault path",
+                false,
+            ),
+            (KeyCode::Char('o'), KeyModifiers::NONE) => Action::OpenAttachment(self.directory()),
+            _ => Action::None,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let width = 70u16.min(area.width.saturating_sub(4));
+        let height = 9u16.min(area.height.saturating_sub(2));
+
+        let vert = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+        let horiz = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+        let [v] = vert.areas(area);
+        let [center] = horiz.areas(v);
+
+        frame.render_widget(Clear, center);
+
+        let block = Block::default()
+            .title(" Vault Location ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(theme::style_border(true));
+
+        let inner = block.inner(center);
+        frame.render_widget(block, center);
+
+        let chunks = Layout::vertical([
+            Constraint::Min(1),    // Path
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+        let lines = vec![
+            Line::from(Span::styled("File:", theme::style_muted())),
+            Line::from(self.path.display().to_string()),
+            Line::raw(""),
+            Line::from(Span::styled("Directory:", theme::style_muted())),
+            Line::from(self.directory().display().to_string()),
+        ];
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+        let hints = Paragraph::new("c copy path · o open directory · Esc close")
+            .alignment(Alignment::Center)
+            .style(theme::style_muted());
+        frame.render_widget(hints, chunks[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_path_carries_vault_path_without_auto_clear() {
+        let mut view = VaultInfoView::new(Path::new("/home/me/vaults/main.vltr"));
+        assert!(matches!(
+            view.handle_key(KeyEvent::from(KeyCode::Char('c'))),
+            Action::CopyText { source, text, label, sensitive, secret }
+                if text.as_str() == "/home/me/vaults/main.vltr" && label == "Vault path" && !sensitive && !secret && source == "Vault"
+        ));
+        assert!(matches!(
+            view.handle_key(KeyEvent::from(KeyCode::Char('o'))),
+            Action::OpenAttachment(dir) if dir == Path::new("/home/me/vaults")
+        ));
+    }
+}
@@ -0,0 +1,219 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::ui::theme;
+use crate::ui::{Action, Component};
+
+/// Settings stored inside the open vault rather than in the config file.
+pub struct VaultSettings {
+    /// Auto-lock override in seconds; empty means "use the global setting".
+    auto_lock: String,
+    global_auto_lock_secs: u64,
+    /// Toggled with `h`; saved along with the timeout.
+    record_password_history: bool,
+}
+
+impl VaultSettings {
+    pub fn new(
+        auto_lock_override: Option<u64>,
+        global_auto_lock_secs: u64,
+        record_password_history: bool,
+    ) -> Self {
+        Self {
+            auto_lock: auto_lock_override
+                .map(|secs| secs.to_string())
+                .unwrap_or_default(),
+            global_auto_lock_secs,
+            record_password_history,
+        }
+    }
+
+    fn submit(&self) -> Action {
+        let auto_lock_override = if self.auto_lock.is_empty() {
+            None
+        } else {
+            match self.auto_lock.parse::<u64>() {
+                // An override may shorten or lengthen the timeout, but not switch it off.
+                Ok(0) => {
+                    return Action::SetStatus(
+                        "Auto-lock must be at least 1 second; leave it empty to use the config"
+                            .to_string(),
+                    )
+                }
+                Ok(secs) => Some(secs),
+                Err(_) => {
+                    return Action::SetStatus("Auto-lock must be a number of seconds".to_string())
+                }
+            }
+        };
+        Action::SaveVaultSettings {
+            auto_lock_override,
+            record_password_history: self.record_password_history,
+        }
+    }
+}
+
+impl Component for VaultSettings {
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) => Action::CloseModal,
+            (KeyCode::Enter, _) | (KeyCode::Char('s'), KeyModifiers::CONTROL) => self.submit(),
+            (KeyCode::Char(c), _) if c.is_ascii_digit() => {
+                self.auto_lock.push(c);
+                Action::None
+            }
+            (KeyCode::Char('h'), KeyModifiers::NONE) => {
+                self.record_password_history = !self.record_password_history;
+                Action::None
+            }
+            (KeyCode::Backspace, _) => {
+                self.auto_lock.pop();
+                Action::None
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let width = 50u16.min(area.width.saturating_sub(4));
+        let height = 10u16.min(area.height.saturating_sub(2));
+
+        let vert = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+        let horiz = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+        let [v] = vert.areas(area);
+        let [center] = horiz.areas(v);
+
+        frame.render_widget(Clear, center);
+
+        let block = Block::default()
+            .title(" Vault Settings ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(theme::style_border(true));
+
+        let inner = block.inner(center);
+        frame.render_widget(block, center);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(3), // Auto-lock
+            Constraint::Length(1), // Explanation
+            Constraint::Length(1), // Password history
+            Constraint::Length(2), // Hints
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+        let field_block = Block::default()
+            .title(" Auto-lock (seconds) ")
+            .title_style(theme::style_accent())
+            .borders(Borders::ALL)
+            .border_style(theme::style_border(true));
+        let content = if self.auto_lock.is_empty() {
+            Line::from(vec![
+                Span::styled("█", theme::style_accent()),
+                Span::styled(
+                    format!(" global ({}s)", self.global_auto_lock_secs),
+                    theme::style_muted(),
+                ),
+            ])
+        } else {
+            Line::from(vec![
+                Span::raw(&self.auto_lock),
+                Span::styled("█", theme::style_accent()),
+            ])
+        };
+        frame.render_widget(Paragraph::new(content).block(field_block), chunks[0]);
+
+        let note = Paragraph::new("Applies to this vault after unlock; empty uses the config.")
+            .style(theme::style_muted());
+        frame.render_widget(note, chunks[1]);
+
+        let history = Line::from(vec![
+            Span::styled("Password history: ", theme::style_muted()),
+            Span::raw(if self.record_password_history {
+                "recorded"
+            } else {
+                "not recorded"
+            }),
+        ]);
+        frame.render_widget(Paragraph::new(history), chunks[2]);
+
+        let hints = Paragraph::new(Line::from(vec![
+            Span::styled("Enter", theme::style_accent()),
+            Span::raw(" save  "),
+            Span::styled("h", theme::style_accent()),
+            Span::raw(" toggle history  "),
+            Span::styled("Esc", theme::style_accent()),
+            Span::raw(" cancel"),
+        ]))
+        .style(theme::style_muted());
+        frame.render_widget(hints, chunks[3]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(settings: &mut VaultSettings, text: &str) {
+        for c in text.chars() {
+            settings.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_submit_parses_override_or_clears_it() {
+        let mut settings = VaultSettings::new(Some(60), 300, true);
+        assert!(matches!(
+            settings.submit(),
+            Action::SaveVaultSettings {
+                auto_lock_override: Some(60),
+                record_password_history: true
+            }
+        ));
+
+        type_text(&mut settings, "0x");
+        assert!(matches!(
+            settings.submit(),
+            Action::SaveVaultSettings {
+                auto_lock_override: Some(600),
+                ..
+            }
+        ));
+
+        for _ in 0..3 {
+            settings.handle_key(KeyEvent::from(KeyCode::Backspace));
+        }
+        assert!(matches!(
+            settings.submit(),
+            Action::SaveVaultSettings {
+                auto_lock_override: None,
+                ..
+            }
+        ));
+
+        // Zero would switch auto-lock off for this vault, so it is refused.
+        type_text(&mut settings, "0");
+        assert!(matches!(settings.submit(), Action::SetStatus(_)));
+    }
+
+    #[test]
+    fn test_history_toggle_is_saved_with_the_settings() {
+        let mut settings = VaultSettings::new(None, 300, true);
+        // The toggle alone changes nothing until the settings are saved.
+        assert!(matches!(
+            settings.handle_key(KeyEvent::from(KeyCode::Char('h'))),
+            Action::None
+        ));
+        assert!(matches!(
+            settings.handle_key(KeyEvent::from(KeyCode::Enter)),
+            Action::SaveVaultSettings {
+                auto_lock_override: None,
+                record_password_history: false
+            }
+        ));
+    }
+}
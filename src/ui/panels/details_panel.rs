@@ -1,11 +1,17 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 use ratatui::Frame;
 use uuid::Uuid;
+use zeroize::Zeroizing;
 
-use crate::core::models::Item;
+use crate::core::models::{Attachment, Item, ItemKind};
+use crate::core::phonetic;
+use crate::ui::text::{has_edge_whitespace, truncate_to_width};
 use crate::ui::theme;
 use crate::ui::{Action, Component};
 
@@ -14,6 +20,34 @@ pub struct DetailsPanel {
     show_password: bool,
     focused: bool,
     scroll_offset: u16,
+    /// Largest useful `scroll_offset`, measured against the viewport at the last render.
+    max_scroll: Cell<u16>,
+    mask_when_unfocused: bool,
+    /// Follow a masked password with its character count.
+    show_password_length: bool,
+    /// When set, `r` reveals the password only for this long instead of toggling.
+    reveal_flash: Option<Duration>,
+    flash_until: Option<Instant>,
+    /// Revealing asks the app for re-authentication instead of toggling directly.
+    reveal_requires_auth: bool,
+    /// Unmask every sensitive field at once (`R`); cleared when the item changes.
+    reveal_all: bool,
+    /// A gated `R` is waiting for re-authentication.
+    reveal_all_pending: bool,
+    /// Selected file, counting attachments first and then embedded files; `a` advances it.
+    attachment_cursor: usize,
+    /// Progress through a vim-style `"<letter>y` / `"<letter>p` register command.
+    register_prompt: Option<RegisterPrompt>,
+    /// Spell a revealed password out character by character (`S`), for reading aloud.
+    spell_out: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RegisterPrompt {
+    /// `"` was pressed; the next letter names the register.
+    AwaitingName,
+    /// Register chosen; `y` stores the password in it, `p` copies it back.
+    Named(char),
 }
 
 #[derive(Clone)]
@@ -21,14 +55,31 @@ struct DisplayItem {
     id: Uuid,
     title: String,
     username: String,
-    password: String,
+    /// Empty for a sealed password until the app supplies it for a reveal.
+    password: Zeroizing<String>,
+    /// The vault holds this password sealed; the plaintext is dropped again on hide.
+    password_sealed: bool,
+    password_chars: usize,
+    password_edge_whitespace: bool,
     url: String,
+    urls: Vec<String>,
     notes: String,
     tags: Vec<String>,
     group_name: String,
     created_at: String,
     modified_at: String,
     password_history_count: usize,
+    attachments: Vec<Attachment>,
+    /// Name and size of each embedded file; the bytes stay in the vault service.
+    embedded: Vec<(String, usize)>,
+    has_totp: bool,
+    no_clipboard_clear: bool,
+    notes_secret: bool,
+    /// The app confirms every reveal or copy of the password.
+    sensitive: bool,
+    /// Public and private key of an SSH key item.
+    ssh: Option<(String, String)>,
+    launch_command: Option<String>,
 }
 
 impl Default for DetailsPanel {
@@ -44,6 +95,17 @@ impl DetailsPanel {
             show_password: false,
             focused: false,
             scroll_offset: 0,
+            max_scroll: Cell::new(0),
+            mask_when_unfocused: false,
+            show_password_length: false,
+            reveal_flash: None,
+            flash_until: None,
+            reveal_requires_auth: false,
+            reveal_all: false,
+            reveal_all_pending: false,
+            attachment_cursor: 0,
+            register_prompt: None,
+            spell_out: false,
         }
     }
 
@@ -55,33 +117,228 @@ impl DetailsPanel {
         self.focused
     }
 
+    pub fn set_mask_when_unfocused(&mut self, mask: bool) {
+        self.mask_when_unfocused = mask;
+    }
+
+    pub fn set_show_password_length(&mut self, show: bool) {
+        self.show_password_length = show;
+    }
+
+    /// Configure flash-reveal mode; `0` keeps the classic toggle behavior.
+    pub fn set_reveal_flash_ms(&mut self, ms: u64) {
+        self.reveal_flash = (ms > 0).then(|| Duration::from_millis(ms));
+    }
+
+    pub fn set_reveal_requires_auth(&mut self, required: bool) {
+        self.reveal_requires_auth = required;
+    }
+
+    /// Reveal the password after the app has authorized it.
+    pub fn reveal(&mut self) {
+        if std::mem::take(&mut self.reveal_all_pending) {
+            self.toggle_reveal_all(Instant::now());
+            return;
+        }
+        match self.reveal_flash {
+            Some(window) => self.flash_until = Some(Instant::now() + window),
+            None => self.show_password = true,
+        }
+    }
+
+    fn toggle_reveal(&mut self, now: Instant) {
+        match self.reveal_flash {
+            Some(window) => self.flash_until = Some(now + window),
+            None => self.show_password = !self.show_password,
+        }
+        self.drop_sealed_password(now);
+    }
+
+    fn toggle_reveal_all(&mut self, now: Instant) {
+        match self.reveal_flash {
+            Some(window) => {
+                self.reveal_all = true;
+                self.flash_until = Some(now + window);
+            }
+            None => self.reveal_all = !self.reveal_all,
+        }
+        self.drop_sealed_password(now);
+    }
+
+    /// Forget the plaintext of a sealed password once nothing shows it.
+    fn drop_sealed_password(&mut self, now: Instant) {
+        let hidden = !self.is_secret_revealed_at(now);
+        if let Some(item) = self.item.as_mut().filter(|i| i.password_sealed && hidden) {
+            item.password = Zeroizing::default();
+        }
+    }
+
+    /// Supply the plaintext of a sealed password just before revealing it.
+    pub fn set_sealed_password(&mut self, password: Zeroizing<String>) {
+        if let Some(item) = self.item.as_mut().filter(|i| i.password_sealed) {
+            item.password = password;
+        }
+    }
+
+    /// Length and edge whitespace of a sealed password, shown while it stays masked.
+    pub fn set_sealed_password_summary(&mut self, chars: usize, edge_whitespace: bool) {
+        if let Some(item) = self.item.as_mut().filter(|i| i.password_sealed) {
+            item.password_chars = chars;
+            item.password_edge_whitespace = edge_whitespace;
+        }
+    }
+
+    fn is_password_revealed_at(&self, now: Instant) -> bool {
+        match self.reveal_flash {
+            Some(_) => self.flash_until.is_some_and(|until| now < until),
+            None => self.show_password,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn is_password_shown(&self) -> bool {
+        self.is_password_revealed_at(Instant::now())
+    }
+
+    /// Whether `R` currently overrides the masking of every sensitive field.
+    fn is_all_revealed_at(&self, now: Instant) -> bool {
+        self.reveal_all
+            && match self.reveal_flash {
+                Some(_) => self.flash_until.is_some_and(|until| now < until),
+                None => true,
+            }
+    }
+
+    /// Whether all sensitive fields are currently blurred because the pane lost focus.
+    fn is_blurred(&self) -> bool {
+        self.mask_when_unfocused && !self.focused
+    }
+
+    /// Whether `r` or `R` currently unmasks the password and secret notes.
+    fn is_secret_revealed_at(&self, now: Instant) -> bool {
+        self.is_password_revealed_at(now) || self.is_all_revealed_at(now)
+    }
+
+    fn password_display<'a>(&self, item: &'a DisplayItem) -> &'a str {
+        if self.is_secret_revealed_at(Instant::now()) && !self.is_blurred() {
+            item.password.as_str()
+        } else {
+            theme::PASSWORD_MASK
+        }
+    }
+
+    /// The revealed password spelled out, when that view is on.
+    fn spelled_password(&self, item: &DisplayItem) -> Option<String> {
+        let revealed = self.is_secret_revealed_at(Instant::now()) && !self.is_blurred();
+        (self.spell_out && revealed && !item.password.is_empty())
+            .then(|| phonetic::spell_out(&item.password))
+    }
+
+    /// The private key is masked like the password until `r` or `R` reveals it.
+    fn private_key_lines(&self, private_key: &str) -> Vec<String> {
+        if private_key.is_empty() {
+            vec!["  —".to_string()]
+        } else if self.is_secret_revealed_at(Instant::now()) && !self.is_blurred() {
+            private_key
+                .lines()
+                .map(|line| format!("  {line}"))
+                .collect()
+        } else {
+            vec![format!("  {}", theme::PASSWORD_MASK)]
+        }
+    }
+
+    fn notes_lines(&self, item: &DisplayItem) -> Vec<String> {
+        if item.notes.is_empty() {
+            vec!["  —".to_string()]
+        } else if self.is_blurred()
+            || (item.notes_secret && !self.is_secret_revealed_at(Instant::now()))
+        {
+            vec![format!("  {}", theme::PASSWORD_MASK)]
+        } else {
+            item.notes.lines().map(|line| format!("  {line}")).collect()
+        }
+    }
+
     pub fn set_item(&mut self, item: Option<&Item>, group_name: &str) {
+        self.attachment_cursor = 0;
         self.show_password = false;
+        self.flash_until = None;
+        self.reveal_all = false;
+        self.reveal_all_pending = false;
         self.scroll_offset = 0;
         self.item = item.map(|i| DisplayItem {
             id: i.id,
             title: i.title.clone(),
             username: i.username.clone(),
-            password: i.password.clone(),
+            password: Zeroizing::new(i.password.clone()),
+            password_sealed: i.sealed_password.is_some(),
+            password_chars: i.password.chars().count(),
+            password_edge_whitespace: has_edge_whitespace(&i.password),
             url: i.url.clone(),
+            urls: i.urls.clone(),
             notes: i.notes.clone(),
             tags: i.tags.clone(),
             group_name: group_name.to_string(),
             created_at: i.created_at.format("%Y-%m-%d %H:%M").to_string(),
             modified_at: i.modified_at.format("%Y-%m-%d %H:%M").to_string(),
             password_history_count: i.password_history.len(),
+            attachments: i.attachments.clone(),
+            embedded: i
+                .embedded
+                .iter()
+                .map(|f| (f.name.clone(), f.bytes.len()))
+                .collect(),
+            has_totp: i.has_totp(),
+            no_clipboard_clear: i.no_clipboard_clear,
+            notes_secret: i.notes_secret,
+            sensitive: i.sensitive,
+            ssh: match i.kind {
+                ItemKind::SshKey {
+                    ref public_key,
+                    ref private_key,
+                } => Some((public_key.clone(), private_key.clone())),
+                ItemKind::Login => None,
+            },
+            launch_command: i.launch_command.clone(),
         });
     }
 
     pub fn clear(&mut self) {
         self.item = None;
         self.show_password = false;
+        self.flash_until = None;
+        self.reveal_all = false;
+        self.reveal_all_pending = false;
+        self.attachment_cursor = 0;
         self.scroll_offset = 0;
     }
 
     pub fn selected_item_id(&self) -> Option<Uuid> {
         self.item.as_ref().map(|i| i.id)
     }
+
+    /// Continue a register command; any key that does not fit cancels it.
+    fn handle_register_key(&mut self, prompt: RegisterPrompt, code: KeyCode) -> Action {
+        match (prompt, code) {
+            (RegisterPrompt::AwaitingName, KeyCode::Char(c)) if c.is_ascii_lowercase() => {
+                self.register_prompt = Some(RegisterPrompt::Named(c));
+                Action::None
+            }
+            (RegisterPrompt::Named(name), KeyCode::Char('y')) => match self.item {
+                Some(ref item) => Action::YankToRegister(name, item.id),
+                None => Action::None,
+            },
+            (RegisterPrompt::Named(name), KeyCode::Char('p')) => Action::CopyRegister(name),
+            _ => Action::None,
+        }
+    }
+}
+
+/// Scroll offset that puts the last of `content_lines` on the bottom row of the viewport.
+fn max_scroll(content_lines: usize, viewport_height: u16) -> u16 {
+    let overflow = content_lines.saturating_sub(viewport_height as usize);
+    u16::try_from(overflow).unwrap_or(u16::MAX)
 }
 
 impl Component for DetailsPanel {
@@ -89,11 +346,47 @@ impl Component for DetailsPanel {
         if !self.focused {
             return Action::None;
         }
+        if let Some(prompt) = self.register_prompt.take() {
+            return self.handle_register_key(prompt, key.code);
+        }
 
         match key.code {
             KeyCode::Char('r') => {
-                self.show_password = !self.show_password;
-                Action::None
+                let now = Instant::now();
+                match self.item {
+                    Some(ref item)
+                        if (self.reveal_requires_auth
+                            || item.sensitive
+                            || item.password_sealed)
+                            && !self.is_password_revealed_at(now) =>
+                    {
+                        Action::RequestReveal(item.id)
+                    }
+                    _ => {
+                        self.toggle_reveal(now);
+                        Action::None
+                    }
+                }
+            }
+            KeyCode::Char('R') => {
+                let now = Instant::now();
+                match self.item {
+                    Some(ref item)
+                        if (self.reveal_requires_auth
+                            || item.sensitive
+                            || item.password_sealed)
+                            && !self.is_all_revealed_at(now)
+                            && !self.is_password_revealed_at(now) =>
+                    {
+                        self.reveal_all_pending = true;
+                        Action::RequestReveal(item.id)
+                    }
+                    Some(_) => {
+                        self.toggle_reveal_all(now);
+                        Action::None
+                    }
+                    None => Action::None,
+                }
             }
             KeyCode::Char('p') => {
                 if let Some(ref item) = self.item {
@@ -102,6 +395,28 @@ impl Component for DetailsPanel {
                     Action::None
                 }
             }
+            KeyCode::Char('"') => {
+                self.register_prompt = Some(RegisterPrompt::AwaitingName);
+                Action::None
+            }
+            KeyCode::Char('L') => match self.item {
+                Some(ref item) if item.launch_command.is_some() => {
+                    Action::RunLaunchCommand(item.id)
+                }
+                _ => Action::None,
+            },
+            KeyCode::Char('Q') => match self.item {
+                Some(ref item) => Action::ChooseQrField(item.id),
+                None => Action::None,
+            },
+            KeyCode::Char('S') => {
+                self.spell_out = !self.spell_out;
+                Action::None
+            }
+            KeyCode::Char('P') => match self.item {
+                Some(ref item) if item.ssh.is_some() => Action::CopyPublicKey(item.id),
+                _ => Action::None,
+            },
             KeyCode::Char('u') => {
                 if let Some(ref item) = self.item {
                     Action::CopyUsername(item.id)
@@ -109,6 +424,78 @@ impl Component for DetailsPanel {
                     Action::None
                 }
             }
+            KeyCode::Char('t') => match self.item {
+                Some(ref item) if item.has_totp => Action::CopyTotp(item.id),
+                _ => Action::None,
+            },
+            KeyCode::Char('c') => {
+                if let Some(ref item) = self.item {
+                    Action::CopyCredentialSequence(item.id)
+                } else {
+                    Action::None
+                }
+            }
+            KeyCode::Char('y') => {
+                if let Some(ref item) = self.item {
+                    Action::CopyFormattedItem(item.id)
+                } else {
+                    Action::None
+                }
+            }
+            KeyCode::Char('a') => {
+                if let Some(ref item) = self.item {
+                    let count = item.attachments.len() + item.embedded.len();
+                    if count > 0 {
+                        self.attachment_cursor = (self.attachment_cursor + 1) % count;
+                    }
+                }
+                Action::None
+            }
+            KeyCode::Char('o') | KeyCode::Char('i') => {
+                let Some(ref item) = self.item else {
+                    return Action::None;
+                };
+                match item.attachments.get(self.attachment_cursor) {
+                    Some(attachment) if !attachment.exists() => {
+                        Action::SetStatus(format!("Missing file: {}", attachment.path.display()))
+                    }
+                    Some(attachment) if key.code == KeyCode::Char('i') => {
+                        Action::EmbedAttachment(item.id, attachment.path.clone())
+                    }
+                    Some(attachment) => Action::OpenAttachment(attachment.path.clone()),
+                    None => Action::None,
+                }
+            }
+            KeyCode::Char('b') => {
+                let Some(ref item) = self.item else {
+                    return Action::None;
+                };
+                let mut urls: Vec<String> = std::iter::once(&item.url)
+                    .chain(&item.urls)
+                    .filter(|url| !url.trim().is_empty())
+                    .cloned()
+                    .collect();
+                match urls.len() {
+                    0 => Action::SetStatus("No URL to open".to_string()),
+                    1 => Action::OpenUrl(urls.remove(0)),
+                    _ => Action::ChooseUrl(urls),
+                }
+            }
+            KeyCode::Char('x') => match self.item {
+                Some(ref item) => {
+                    match self.attachment_cursor.checked_sub(item.attachments.len()) {
+                        Some(index) if index < item.embedded.len() => {
+                            Action::ExportEmbedded(item.id, index)
+                        }
+                        _ => Action::None,
+                    }
+                }
+                None => Action::None,
+            },
+            KeyCode::Char('K') => match self.item {
+                Some(ref item) => Action::ToggleNoClipboardClear(item.id),
+                None => Action::None,
+            },
             KeyCode::Char('e') => {
                 if let Some(ref item) = self.item {
                     Action::OpenEditItemForm(item.id)
@@ -124,11 +511,17 @@ impl Component for DetailsPanel {
                 }
             }
             KeyCode::Char('j') | KeyCode::Down => {
-                self.scroll_offset = self.scroll_offset.saturating_add(1);
+                self.scroll_offset = self
+                    .scroll_offset
+                    .saturating_add(1)
+                    .min(self.max_scroll.get());
                 Action::None
             }
             KeyCode::Char('k') | KeyCode::Up => {
-                self.scroll_offset = self.scroll_offset.saturating_sub(1);
+                self.scroll_offset = self
+                    .scroll_offset
+                    .min(self.max_scroll.get())
+                    .saturating_sub(1);
                 Action::None
             }
             _ => Action::None,
@@ -161,17 +554,19 @@ impl Component for DetailsPanel {
 
         // Title
         let title = Paragraph::new(Line::from(vec![Span::styled(
-            &item.title,
+            truncate_to_width(&item.title, chunks[0].width as usize),
             theme::style_accent(),
         )]));
         frame.render_widget(title, chunks[0]);
 
         // Fields
-        let password_display = if self.show_password {
-            item.password.as_str()
-        } else {
-            theme::PASSWORD_MASK
-        };
+        let password_display = self.password_display(item);
+        let password_length =
+            if self.show_password_length && password_display == theme::PASSWORD_MASK {
+                format!(" ({} chars)", item.password_chars)
+            } else {
+                String::new()
+            };
 
         let tags_display = if item.tags.is_empty() {
             "—".to_string()
@@ -191,8 +586,21 @@ impl Component for DetailsPanel {
             Line::from(vec![
                 Span::styled("Password:  ", theme::style_muted()),
                 Span::raw(password_display),
+                Span::styled(password_length, theme::style_muted()),
                 Span::styled(
-                    if self.show_password {
+                    if item.password_edge_whitespace {
+                        "  ⚠ whitespace"
+                    } else {
+                        ""
+                    },
+                    theme::style_warning(),
+                ),
+                Span::styled(
+                    if self.reveal_flash.is_some() {
+                        "  [r] flash"
+                    } else if self.reveal_all {
+                        "  [R] hide all"
+                    } else if self.show_password {
                         "  [r] hide"
                     } else {
                         "  [r] reveal"
@@ -200,14 +608,33 @@ impl Component for DetailsPanel {
                     theme::style_muted(),
                 ),
             ]),
-            Line::from(vec![
-                Span::styled("URL:       ", theme::style_muted()),
-                Span::raw(if item.url.is_empty() {
-                    "—"
-                } else {
-                    &item.url
-                }),
-            ]),
+        ];
+        if let Some(spelled) = self.spelled_password(item) {
+            lines.push(Line::from(vec![
+                Span::styled("Spelled:   ", theme::style_muted()),
+                Span::raw(spelled),
+            ]));
+        }
+        lines.extend([Line::from(vec![
+            Span::styled("URL:       ", theme::style_muted()),
+            // Labels are 11 columns wide; the full URL is still copied and edited as-is.
+            Span::raw(if item.url.is_empty() {
+                "—".to_string()
+            } else {
+                truncate_to_width(&item.url, (inner.width as usize).saturating_sub(11))
+            }),
+        ])]);
+        // Additional URLs line up under the primary one.
+        for url in &item.urls {
+            lines.push(Line::from(vec![
+                Span::raw("           "),
+                Span::raw(truncate_to_width(
+                    url,
+                    (inner.width as usize).saturating_sub(11),
+                )),
+            ]));
+        }
+        lines.extend([
             Line::from(vec![
                 Span::styled("Group:     ", theme::style_muted()),
                 Span::raw(&item.group_name),
@@ -216,15 +643,112 @@ impl Component for DetailsPanel {
                 Span::styled("Tags:      ", theme::style_muted()),
                 Span::raw(&tags_display),
             ]),
-            Line::raw(""),
-            Line::from(vec![Span::styled("Notes:", theme::style_muted())]),
-        ];
+        ]);
+        if item.has_totp {
+            lines.push(Line::from(vec![
+                Span::styled("2FA:       ", theme::style_muted()),
+                Span::raw("configured  "),
+                Span::styled("[t] copy code", theme::style_muted()),
+            ]));
+        }
+        if let Some(ref command) = item.launch_command {
+            lines.push(Line::from(vec![
+                Span::styled("Launch:    ", theme::style_muted()),
+                Span::raw(truncate_to_width(
+                    command,
+                    (inner.width as usize).saturating_sub(19),
+                )),
+                Span::styled("  [L] run", theme::style_muted()),
+            ]));
+        }
+        if item.sensitive {
+            lines.push(Line::from(vec![
+                Span::styled("Sensitive: ", theme::style_muted()),
+                Span::raw("reveal and copy ask first"),
+            ]));
+        }
+        if item.no_clipboard_clear {
+            lines.push(Line::from(vec![
+                Span::styled("Clipboard: ", theme::style_muted()),
+                Span::raw("kept (no auto-clear)"),
+                Span::styled("  [K] toggle", theme::style_muted()),
+            ]));
+        }
+        if let Some((ref public_key, ref private_key)) = item.ssh {
+            lines.push(Line::raw(""));
+            lines.push(Line::from(vec![
+                Span::styled("Public key:", theme::style_muted()),
+                Span::styled("  [P] copy", theme::style_muted()),
+            ]));
+            lines.push(Line::from(Span::raw(if public_key.is_empty() {
+                "  —".to_string()
+            } else {
+                format!("  {}", public_key.trim_end())
+            })));
+            lines.push(Line::from(vec![
+                Span::styled("Private key:", theme::style_muted()),
+                Span::styled("  [r] reveal", theme::style_muted()),
+            ]));
+            for line in self.private_key_lines(private_key) {
+                lines.push(Line::from(Span::raw(line)));
+            }
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::from(vec![Span::styled(
+            "Notes:",
+            theme::style_muted(),
+        )]));
 
-        if item.notes.is_empty() {
-            lines.push(Line::from(Span::raw("  —")));
-        } else {
-            for line in item.notes.lines() {
-                lines.push(Line::from(Span::raw(format!("  {line}"))));
+        for line in self.notes_lines(item) {
+            lines.push(Line::from(Span::raw(line)));
+        }
+
+        if !item.attachments.is_empty() {
+            lines.push(Line::raw(""));
+            lines.push(Line::from(vec![
+                Span::styled("Attachments:", theme::style_muted()),
+                Span::styled("  [a] next  [o] open  [i] embed", theme::style_muted()),
+            ]));
+            for (i, attachment) in item.attachments.iter().enumerate() {
+                let marker = if i == self.attachment_cursor {
+                    "▸ "
+                } else {
+                    "  "
+                };
+                let mut spans = vec![
+                    Span::styled(marker, theme::style_accent()),
+                    Span::raw(attachment.name.as_str()),
+                    Span::styled(
+                        format!("  {}", attachment.path.display()),
+                        theme::style_muted(),
+                    ),
+                ];
+                // Checked on every render so files moved away since are flagged.
+                if !attachment.exists() {
+                    spans.push(Span::styled("  [missing]", theme::style_error()));
+                }
+                lines.push(Line::from(spans));
+            }
+        }
+
+        if !item.embedded.is_empty() {
+            lines.push(Line::raw(""));
+            lines.push(Line::from(vec![
+                Span::styled("Embedded:", theme::style_muted()),
+                Span::styled("  [a] next  [x] export", theme::style_muted()),
+            ]));
+            let offset = item.attachments.len();
+            for (i, (name, size)) in item.embedded.iter().enumerate() {
+                let marker = if offset + i == self.attachment_cursor {
+                    "▸ "
+                } else {
+                    "  "
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(marker, theme::style_accent()),
+                    Span::raw(name.as_str()),
+                    Span::styled(format!("  {size} bytes"), theme::style_muted()),
+                ]));
             }
         }
 
@@ -248,23 +772,423 @@ impl Component for DetailsPanel {
             ]));
         }
 
-        let fields = Paragraph::new(lines)
-            .wrap(Wrap { trim: false })
-            .scroll((self.scroll_offset, 0));
-        frame.render_widget(fields, chunks[1]);
+        let fields = Paragraph::new(lines).wrap(Wrap { trim: false });
+        let area = chunks[1];
+        let max = max_scroll(fields.line_count(area.width), area.height);
+        self.max_scroll.set(max);
+        // The viewport may have grown since the offset was set.
+        let offset = self.scroll_offset.min(max);
+        frame.render_widget(fields.scroll((offset, 0)), area);
+        if offset > 0 {
+            let more = Line::styled("↑ more", theme::style_muted()).right_aligned();
+            frame.render_widget(more, Rect { height: 1, ..area });
+        }
+        if offset < max {
+            let more = Line::styled("↓ more", theme::style_muted()).right_aligned();
+            let last_row = Rect {
+                y: area.bottom().saturating_sub(1),
+                height: 1,
+                ..area
+            };
+            frame.render_widget(more, last_row);
+        }
 
         // Key hints
-        let hints = Paragraph::new(Line::from(vec![
-            Span::styled("[p]", theme::style_accent()),
-            Span::raw(" copy pw  "),
-            Span::styled("[u]", theme::style_accent()),
-            Span::raw(" copy user  "),
-            Span::styled("[e]", theme::style_accent()),
-            Span::raw(" edit  "),
-            Span::styled("[d]", theme::style_accent()),
-            Span::raw(" delete"),
-        ]))
-        .style(theme::style_muted());
+        let hints = match self.register_prompt {
+            Some(RegisterPrompt::AwaitingName) => Line::from(vec![
+                Span::styled("\"", theme::style_accent()),
+                Span::raw(" register name (a-z)..."),
+            ]),
+            Some(RegisterPrompt::Named(name)) => Line::from(vec![
+                Span::styled(format!("\"{name}"), theme::style_accent()),
+                Span::raw("  y store password  p copy register"),
+            ]),
+            None => Line::from(vec![
+                Span::styled("[p]", theme::style_accent()),
+                Span::raw(" copy pw  "),
+                Span::styled("[u]", theme::style_accent()),
+                Span::raw(" copy user  "),
+                Span::styled("[e]", theme::style_accent()),
+                Span::raw(" edit  "),
+                Span::styled("[d]", theme::style_accent()),
+                Span::raw(" delete"),
+            ]),
+        };
+        let hints = Paragraph::new(hints).style(theme::style_muted());
         frame.render_widget(hints, chunks[2]);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::EmbeddedFile;
+    use std::path::PathBuf;
+
+    fn panel_with_item() -> DetailsPanel {
+        let mut item = Item::new("GitHub".to_string(), None);
+        item.password = "hunter2".to_string();
+        item.notes = "recovery codes".to_string();
+        let mut panel = DetailsPanel::new();
+        panel.set_item(Some(&item), "None");
+        panel.show_password = true;
+        panel
+    }
+
+    #[test]
+    fn test_spelled_password_only_while_revealed() {
+        let mut panel = panel_with_item();
+        panel.set_focused(true);
+        let item = panel.item.clone().unwrap();
+        assert_eq!(panel.spelled_password(&item), None);
+
+        panel.handle_key(KeyEvent::from(KeyCode::Char('S')));
+        assert_eq!(
+            panel.spelled_password(&item).as_deref(),
+            Some("1:hotel, 2:uniform, 3:november, 4:tango, 5:echo, 6:romeo, 7:digit 2")
+        );
+
+        panel.show_password = false;
+        assert_eq!(panel.spelled_password(&item), None);
+    }
+
+    #[test]
+    fn test_unfocused_masks_sensitive_fields_when_enabled() {
+        let mut panel = panel_with_item();
+        panel.set_mask_when_unfocused(true);
+        panel.set_focused(false);
+
+        let item = panel.item.clone().unwrap();
+        assert_eq!(panel.password_display(&item), theme::PASSWORD_MASK);
+        assert_eq!(
+            panel.notes_lines(&item),
+            vec![format!("  {}", theme::PASSWORD_MASK)]
+        );
+
+        panel.set_focused(true);
+        assert_eq!(panel.password_display(&item), "hunter2");
+        assert_eq!(panel.notes_lines(&item), vec!["  recovery codes"]);
+    }
+
+    #[test]
+    fn test_secret_notes_masked_until_revealed() {
+        let mut item = Item::new("GitHub".to_string(), None);
+        item.notes = "recovery codes".to_string();
+        item.notes_secret = true;
+        let mut panel = DetailsPanel::new();
+        panel.set_focused(true);
+        panel.set_item(Some(&item), "None");
+
+        let display = panel.item.clone().unwrap();
+        assert_eq!(
+            panel.notes_lines(&display),
+            vec![format!("  {}", theme::PASSWORD_MASK)]
+        );
+
+        panel.handle_key(KeyEvent::from(KeyCode::Char('r')));
+        assert_eq!(panel.notes_lines(&display), vec!["  recovery codes"]);
+
+        item.notes_secret = false;
+        panel.set_item(Some(&item), "None");
+        let display = panel.item.clone().unwrap();
+        assert_eq!(panel.notes_lines(&display), vec!["  recovery codes"]);
+    }
+
+    #[test]
+    fn test_ssh_private_key_masked_by_default() {
+        let mut item = Item::new("deploy".to_string(), None);
+        item.kind = ItemKind::SshKey {
+            private_key: "-----BEGIN KEY-----\nabc\n-----END KEY-----".to_string(),
+            public_key: "ssh-ed25519 AAAA deploy@host".to_string(),
+        };
+        let mut panel = DetailsPanel::new();
+        panel.set_focused(true);
+        panel.set_item(Some(&item), "None");
+
+        let (public_key, private_key) = panel.item.clone().unwrap().ssh.unwrap();
+        assert_eq!(public_key, "ssh-ed25519 AAAA deploy@host");
+        assert_eq!(
+            panel.private_key_lines(&private_key),
+            vec![format!("  {}", theme::PASSWORD_MASK)]
+        );
+        assert!(matches!(
+            panel.handle_key(KeyEvent::from(KeyCode::Char('P'))),
+            Action::CopyPublicKey(id) if id == item.id
+        ));
+
+        panel.handle_key(KeyEvent::from(KeyCode::Char('r')));
+        assert_eq!(panel.private_key_lines(&private_key).len(), 3);
+    }
+
+    #[test]
+    fn test_register_key_sequence() {
+        let mut panel = panel_with_item();
+        panel.set_focused(true);
+        let id = panel.selected_item_id().unwrap();
+        let key = |c| KeyEvent::from(KeyCode::Char(c));
+
+        panel.handle_key(key('"'));
+        panel.handle_key(key('a'));
+        assert!(matches!(panel.handle_key(key('y')), Action::YankToRegister('a', i) if i == id));
+        panel.handle_key(key('"'));
+        panel.handle_key(key('q'));
+        assert!(matches!(
+            panel.handle_key(key('p')),
+            Action::CopyRegister('q')
+        ));
+
+        // Anything else cancels, and the next key acts normally again.
+        panel.handle_key(key('"'));
+        assert!(matches!(panel.handle_key(key('1')), Action::None));
+        assert!(matches!(
+            panel.handle_key(key('p')),
+            Action::CopyPassword(_)
+        ));
+    }
+
+    #[test]
+    fn test_unfocused_reveals_when_disabled() {
+        let mut panel = panel_with_item();
+        panel.set_focused(false);
+
+        let item = panel.item.clone().unwrap();
+        assert_eq!(panel.password_display(&item), "hunter2");
+        assert_eq!(panel.notes_lines(&item), vec!["  recovery codes"]);
+    }
+
+    #[test]
+    fn test_flash_reveal_expires_after_window() {
+        let mut panel = DetailsPanel::new();
+        panel.set_reveal_flash_ms(1500);
+        let start = Instant::now();
+
+        assert!(!panel.is_password_revealed_at(start));
+        panel.toggle_reveal(start);
+        assert!(panel.is_password_revealed_at(start));
+        assert!(panel.is_password_revealed_at(start + Duration::from_millis(1499)));
+        assert!(!panel.is_password_revealed_at(start + Duration::from_millis(1500)));
+
+        // Pressing again restarts the window rather than hiding.
+        panel.toggle_reveal(start + Duration::from_millis(1000));
+        assert!(panel.is_password_revealed_at(start + Duration::from_millis(2000)));
+    }
+
+    #[test]
+    fn test_zero_flash_keeps_toggle_behavior() {
+        let mut panel = DetailsPanel::new();
+        panel.set_reveal_flash_ms(0);
+        let now = Instant::now();
+
+        panel.toggle_reveal(now);
+        assert!(panel.is_password_revealed_at(now + Duration::from_secs(60)));
+        panel.toggle_reveal(now);
+        assert!(!panel.is_password_revealed_at(now));
+    }
+
+    #[test]
+    fn test_gated_reveal_requests_auth_but_hides_freely() {
+        let mut panel = panel_with_item();
+        panel.show_password = false;
+        panel.set_reveal_requires_auth(true);
+        panel.set_focused(true);
+        let id = panel.selected_item_id().unwrap();
+
+        let action = panel.handle_key(KeyEvent::from(KeyCode::Char('r')));
+        assert!(matches!(action, Action::RequestReveal(got) if got == id));
+        assert!(!panel.show_password);
+
+        panel.reveal();
+        assert!(panel.show_password);
+        let action = panel.handle_key(KeyEvent::from(KeyCode::Char('r')));
+        assert!(matches!(action, Action::None));
+        assert!(!panel.show_password);
+    }
+
+    #[test]
+    fn test_sealed_password_held_only_while_revealed() {
+        use crate::crypto::session::SessionKey;
+
+        let mut item = Item::new("Email".to_string(), None);
+        item.sealed_password = Some(SessionKey::generate().seal("hunter2").unwrap());
+        let mut panel = DetailsPanel::new();
+        panel.set_item(Some(&item), "None");
+        panel.set_sealed_password_summary(7, false);
+        panel.set_show_password_length(true);
+        panel.set_focused(true);
+        assert!(render_to_text(&panel, 24).contains("(7 chars)"));
+
+        // Even without reveal gating, a sealed password is asked for.
+        let action = panel.handle_key(KeyEvent::from(KeyCode::Char('r')));
+        assert!(matches!(action, Action::RequestReveal(_)));
+
+        panel.set_sealed_password(Zeroizing::new("hunter2".to_string()));
+        panel.reveal();
+        let display = panel.item.clone().unwrap();
+        assert_eq!(panel.password_display(&display), "hunter2");
+
+        panel.handle_key(KeyEvent::from(KeyCode::Char('r')));
+        assert!(panel.item.as_ref().unwrap().password.is_empty());
+    }
+
+    fn render_to_text(panel: &DetailsPanel, height: u16) -> String {
+        crate::ui::render_rows(60, height, |f| panel.render(f, f.area())).concat()
+    }
+
+    #[test]
+    fn test_reveal_all_unmasks_every_sensitive_field() {
+        let mut panel = panel_with_item();
+        panel.show_password = false;
+        panel.set_focused(true);
+        assert!(!render_to_text(&panel, 24).contains("hunter2"));
+
+        panel.handle_key(KeyEvent::from(KeyCode::Char('R')));
+        let text = render_to_text(&panel, 24);
+        assert!(text.contains("hunter2"));
+        assert!(text.contains("recovery codes"));
+        assert!(text.contains("[R] hide all"));
+
+        // Selecting another item masks everything again.
+        let other = Item::new("Other".to_string(), None);
+        panel.set_item(Some(&other), "None");
+        assert!(!panel.reveal_all);
+    }
+
+    #[test]
+    fn test_gated_reveal_all_waits_for_auth() {
+        let mut panel = panel_with_item();
+        panel.show_password = false;
+        panel.set_reveal_requires_auth(true);
+        panel.set_focused(true);
+
+        let action = panel.handle_key(KeyEvent::from(KeyCode::Char('R')));
+        assert!(matches!(action, Action::RequestReveal(_)));
+        assert!(!panel.reveal_all);
+
+        panel.reveal();
+        assert!(panel.reveal_all);
+        assert!(!panel.show_password);
+    }
+
+    #[test]
+    fn test_attachments_listed_with_missing_marker() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let present = dir.path().join("id.pem");
+        std::fs::write(&present, "key").unwrap();
+
+        let mut item = Item::new("Server".to_string(), None);
+        item.attachments = vec![
+            Attachment::from_path(present.clone()),
+            Attachment::from_path(dir.path().join("gone.pdf")),
+        ];
+        let mut panel = DetailsPanel::new();
+        panel.set_item(Some(&item), "None");
+        panel.set_focused(true);
+
+        let text = render_to_text(&panel, 24);
+        assert!(text.contains("id.pem"));
+        assert!(text.contains("gone.pdf"));
+        assert_eq!(text.matches("[missing]").count(), 1);
+
+        let action = panel.handle_key(KeyEvent::from(KeyCode::Char('o')));
+        assert!(matches!(action, Action::OpenAttachment(path) if path == present));
+        panel.handle_key(KeyEvent::from(KeyCode::Char('a')));
+        let action = panel.handle_key(KeyEvent::from(KeyCode::Char('o')));
+        assert!(matches!(action, Action::SetStatus(_)));
+    }
+
+    #[test]
+    fn test_multiple_urls_rendered_and_offered_for_opening() {
+        let mut item = Item::new("Google".to_string(), None);
+        item.url = "https://accounts.google.com".to_string();
+        item.urls = vec!["https://youtube.com".to_string(), "gmail.com".to_string()];
+        let mut panel = DetailsPanel::new();
+        panel.set_item(Some(&item), "None");
+        panel.set_focused(true);
+
+        let text = render_to_text(&panel, 24);
+        for url in ["accounts.google.com", "youtube.com", "gmail.com"] {
+            assert!(text.contains(url), "{url} not shown");
+        }
+        let action = panel.handle_key(KeyEvent::from(KeyCode::Char('b')));
+        assert!(matches!(action, Action::ChooseUrl(urls) if urls.len() == 3));
+
+        item.urls.clear();
+        panel.set_item(Some(&item), "None");
+        let action = panel.handle_key(KeyEvent::from(KeyCode::Char('b')));
+        assert!(matches!(action, Action::OpenUrl(url) if url == "https://accounts.google.com"));
+    }
+
+    #[test]
+    fn test_embedded_files_listed_and_exportable() {
+        let mut item = Item::new("Bank".to_string(), None);
+        item.attachments = vec![Attachment::from_path(PathBuf::from("/nowhere/a.pdf"))];
+        item.embedded = vec![EmbeddedFile {
+            name: "codes.txt".to_string(),
+            bytes: vec![0; 42],
+        }];
+        let mut panel = DetailsPanel::new();
+        panel.set_item(Some(&item), "None");
+        panel.set_focused(true);
+
+        let text = render_to_text(&panel, 24);
+        assert!(text.contains("codes.txt  42 bytes"));
+
+        // The cursor starts on the attachment, which cannot be exported.
+        let action = panel.handle_key(KeyEvent::from(KeyCode::Char('x')));
+        assert!(matches!(action, Action::None));
+        panel.handle_key(KeyEvent::from(KeyCode::Char('a')));
+        let action = panel.handle_key(KeyEvent::from(KeyCode::Char('x')));
+        assert!(matches!(action, Action::ExportEmbedded(id, 0) if id == item.id));
+    }
+
+    #[test]
+    fn test_whitespace_badge_for_padded_password() {
+        let mut item = Item::new("Imported".to_string(), None);
+        item.password = "secret\n".to_string();
+        let mut panel = DetailsPanel::new();
+        panel.set_item(Some(&item), "None");
+        assert!(render_to_text(&panel, 24).contains("⚠ whitespace"));
+
+        item.password = "secret".to_string();
+        panel.set_item(Some(&item), "None");
+        assert!(!render_to_text(&panel, 24).contains("⚠ whitespace"));
+    }
+
+    #[test]
+    fn test_password_length_shown_only_when_enabled() {
+        let mut panel = panel_with_item();
+        panel.show_password = false;
+        assert!(!render_to_text(&panel, 24).contains("(7 chars)"));
+
+        panel.set_show_password_length(true);
+        assert!(render_to_text(&panel, 24).contains("(7 chars)"));
+
+        // Once revealed, the password speaks for itself.
+        panel.show_password = true;
+        assert!(!render_to_text(&panel, 24).contains("(7 chars)"));
+    }
+
+    #[test]
+    fn test_scroll_is_clamped_to_content() {
+        assert_eq!(max_scroll(30, 10), 20);
+        assert_eq!(max_scroll(8, 10), 0);
+        assert_eq!(max_scroll(10, 10), 0);
+
+        let mut panel = panel_with_item();
+        panel.set_focused(true);
+        assert!(render_to_text(&panel, 10).contains("↓ more"));
+        let max = panel.max_scroll.get();
+        assert!(max > 0);
+
+        for _ in 0..100 {
+            panel.handle_key(KeyEvent::from(KeyCode::Char('j')));
+        }
+        assert_eq!(panel.scroll_offset, max);
+        let text = render_to_text(&panel, 10);
+        assert!(text.contains("↑ more"));
+        assert!(!text.contains("↓ more"));
+
+        panel.handle_key(KeyEvent::from(KeyCode::Char('k')));
+        assert_eq!(panel.scroll_offset, max - 1);
+    }
+}
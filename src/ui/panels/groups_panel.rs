@@ -1,20 +1,34 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::layout::Rect;
 use ratatui::text::Line;
-use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
+use ratatui::widgets::{Block, Borders, List, ListItem};
 use ratatui::Frame;
 use uuid::Uuid;
 
 use crate::core::models::Group;
+use crate::ui::list::{Keyed, SelectableList};
 use crate::ui::theme;
 use crate::ui::{Action, Component};
 
+struct GroupEntry {
+    /// None = the "All Items" entry.
+    id: Option<Uuid>,
+    name: String,
+}
+
+impl Keyed for GroupEntry {
+    type Key = Option<Uuid>;
+
+    fn key(&self) -> Option<Uuid> {
+        self.id
+    }
+}
+
 pub struct GroupsPanel {
-    /// None = "All Items" is the first entry, followed by group IDs.
-    entries: Vec<Option<Uuid>>,
-    group_names: Vec<String>,
-    list_state: ListState,
+    /// "All Items" is the first entry, followed by the groups.
+    entries: SelectableList<GroupEntry>,
     focused: bool,
+    sort_alphabetically: bool,
 }
 
 impl Default for GroupsPanel {
@@ -25,11 +39,12 @@ impl Default for GroupsPanel {
 
 impl GroupsPanel {
     pub fn new() -> Self {
+        let mut entries = SelectableList::new();
+        entries.set_entries(vec![GroupEntry::all_items()]);
         Self {
-            entries: vec![None],
-            group_names: vec!["All Items".to_string()],
-            list_state: ListState::default().with_selected(Some(0)),
+            entries,
             focused: true,
+            sort_alphabetically: false,
         }
     }
 
@@ -37,55 +52,71 @@ impl GroupsPanel {
         self.focused = focused;
     }
 
+    /// Let j/k wrap from the last group to the first and back.
+    pub fn set_wraparound(&mut self, wraparound: bool) {
+        self.entries.set_wraparound(wraparound);
+    }
+
+    /// List groups by name instead of in the order they are stored.
+    pub fn set_sort_alphabetically(&mut self, sort: bool) {
+        self.sort_alphabetically = sort;
+    }
+
     pub fn is_focused(&self) -> bool {
         self.focused
     }
 
     pub fn update_groups(&mut self, groups: &[Group]) {
-        self.entries = vec![None];
-        self.group_names = vec!["All Items".to_string()];
-        for group in groups {
-            self.entries.push(Some(group.id));
-            self.group_names.push(group.name.clone());
-        }
-        // Clamp selection
-        if let Some(sel) = self.list_state.selected() {
-            if sel >= self.entries.len() {
-                self.list_state
-                    .select(Some(self.entries.len().saturating_sub(1)));
-            }
-        }
+        self.entries
+            .set_entries(group_entries(groups, self.sort_alphabetically));
     }
 
     pub fn selected_group_id(&self) -> Option<Uuid> {
-        self.list_state
-            .selected()
-            .and_then(|i| self.entries.get(i).copied())
-            .flatten()
+        self.entries.selected_key().flatten()
     }
 
-    fn move_up(&mut self) {
-        let i = self.list_state.selected().unwrap_or(0);
-        if i > 0 {
-            self.list_state.select(Some(i - 1));
-        }
+    /// Select the group with `id`; false if it is not listed.
+    pub fn select_group(&mut self, id: Uuid) -> bool {
+        self.entries.select_key(Some(id))
     }
 
-    fn move_down(&mut self) {
-        let i = self.list_state.selected().unwrap_or(0);
-        if i + 1 < self.entries.len() {
-            self.list_state.select(Some(i + 1));
-        }
+    /// Select the "All Items" entry.
+    pub fn select_all_items(&mut self) {
+        self.entries.select_first();
     }
 
     pub fn selected_group_name(&self) -> Option<String> {
-        self.list_state
-            .selected()
-            .and_then(|i| self.group_names.get(i))
-            .cloned()
+        self.entries.selected().map(|entry| entry.name.clone())
+    }
+}
+
+impl GroupEntry {
+    fn all_items() -> Self {
+        Self {
+            id: None,
+            name: "All Items".to_string(),
+        }
     }
 }
 
+/// "All Items" followed by the groups, in stored order or by name ignoring case.
+fn group_entries(groups: &[Group], alphabetical: bool) -> Vec<GroupEntry> {
+    let mut groups: Vec<GroupEntry> = groups
+        .iter()
+        .map(|group| GroupEntry {
+            id: Some(group.id),
+            name: group.name.clone(),
+        })
+        .collect();
+    if alphabetical {
+        // Stable, so names differing only in case keep their stored order.
+        groups.sort_by_cached_key(|entry| entry.name.to_lowercase());
+    }
+    std::iter::once(GroupEntry::all_items())
+        .chain(groups)
+        .collect()
+}
+
 impl Component for GroupsPanel {
     fn handle_key(&mut self, key: KeyEvent) -> Action {
         if !self.focused {
@@ -93,11 +124,11 @@ impl Component for GroupsPanel {
         }
         match key.code {
             KeyCode::Char('j') | KeyCode::Down => {
-                self.move_down();
+                self.entries.move_down();
                 Action::SelectGroup(self.selected_group_id())
             }
             KeyCode::Char('k') | KeyCode::Up => {
-                self.move_up();
+                self.entries.move_up();
                 Action::SelectGroup(self.selected_group_id())
             }
             KeyCode::Enter => Action::SelectGroup(self.selected_group_id()),
@@ -122,12 +153,16 @@ impl Component for GroupsPanel {
 
     fn render(&self, frame: &mut Frame, area: Rect) {
         let items: Vec<ListItem> = self
-            .group_names
+            .entries
+            .entries()
             .iter()
-            .enumerate()
-            .map(|(i, name)| {
-                let prefix = if i == 0 { "📁 " } else { "  📂 " };
-                ListItem::new(Line::raw(format!("{prefix}{name}")))
+            .map(|entry| {
+                let prefix = if entry.id.is_none() {
+                    "📁 "
+                } else {
+                    "  📂 "
+                };
+                ListItem::new(Line::raw(format!("{prefix}{}", entry.name)))
             })
             .collect();
 
@@ -142,7 +177,50 @@ impl Component for GroupsPanel {
             .highlight_style(theme::style_selected())
             .highlight_symbol("▸ ");
 
-        let mut state = self.list_state.clone();
+        let mut state = self.entries.state();
         frame.render_stateful_widget(list, area, &mut state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selected_group_survives_reorder() {
+        let work = Group::new("Work".to_string(), None);
+        let home = Group::new("Home".to_string(), None);
+
+        let mut panel = GroupsPanel::new();
+        panel.update_groups(&[work.clone(), home.clone()]);
+        panel.handle_key(KeyEvent::from(KeyCode::Down));
+        panel.handle_key(KeyEvent::from(KeyCode::Down));
+        assert_eq!(panel.selected_group_id(), Some(home.id));
+
+        panel.update_groups(&[home.clone(), work]);
+        assert_eq!(panel.selected_group_id(), Some(home.id));
+        assert_eq!(panel.selected_group_name().as_deref(), Some("Home"));
+    }
+
+    #[test]
+    fn test_alphabetical_order_keeps_all_items_first() {
+        let groups = [
+            Group::new("work".to_string(), None),
+            Group::new("Banking".to_string(), None),
+            Group::new("home".to_string(), None),
+        ];
+        let names = |entries: Vec<GroupEntry>| -> Vec<String> {
+            entries.into_iter().map(|entry| entry.name).collect()
+        };
+
+        assert_eq!(
+            names(group_entries(&groups, false)),
+            ["All Items", "work", "Banking", "home"]
+        );
+        assert_eq!(
+            names(group_entries(&groups, true)),
+            ["All Items", "Banking", "home", "work"]
+        );
+        assert_eq!(group_entries(&groups, true)[0].id, None);
+    }
+}
@@ -1,20 +1,45 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
 use ratatui::Frame;
+use unicode_width::UnicodeWidthStr;
 use uuid::Uuid;
 
+use crate::config::ItemEnterAction;
 use crate::core::models::Item;
+use crate::core::url;
+use crate::ui::list::{Keyed, SelectableList};
+use crate::ui::text::truncate_to_width;
 use crate::ui::theme;
 use crate::ui::{Action, Component};
 
+struct ListEntry {
+    id: Uuid,
+    title: String,
+    username: String,
+    /// Letter derived from the item's URL domain, shown as a favicon-style badge.
+    badge: Option<char>,
+}
+
+impl Keyed for ListEntry {
+    type Key = Uuid;
+
+    fn key(&self) -> Uuid {
+        self.id
+    }
+}
+
 pub struct ItemsPanel {
-    items: Vec<(Uuid, String, String)>, // (id, title, username)
-    list_state: ListState,
+    items: SelectableList<ListEntry>,
     focused: bool,
     search_active: bool,
     search_query: String,
+    /// Tag restriction applied by a saved filter.
+    tag_filter: Option<String>,
+    /// Restrict the list to items with TOTP.
+    totp_only: bool,
+    enter_action: ItemEnterAction,
 }
 
 impl Default for ItemsPanel {
@@ -26,11 +51,13 @@ impl Default for ItemsPanel {
 impl ItemsPanel {
     pub fn new() -> Self {
         Self {
-            items: Vec::new(),
-            list_state: ListState::default(),
+            items: SelectableList::new(),
             focused: false,
             search_active: false,
             search_query: String::new(),
+            tag_filter: None,
+            totp_only: false,
+            enter_action: ItemEnterAction::default(),
         }
     }
 
@@ -38,6 +65,15 @@ impl ItemsPanel {
         self.focused = focused;
     }
 
+    /// Let j/k wrap from the last item to the first and back.
+    pub fn set_wraparound(&mut self, wraparound: bool) {
+        self.items.set_wraparound(wraparound);
+    }
+
+    pub fn set_enter_action(&mut self, action: ItemEnterAction) {
+        self.enter_action = action;
+    }
+
     pub fn is_focused(&self) -> bool {
         self.focused
     }
@@ -47,46 +83,60 @@ impl ItemsPanel {
     }
 
     pub fn update_items(&mut self, items: &[&Item]) {
-        self.items = items
-            .iter()
-            .map(|item| (item.id, item.title.clone(), item.username.clone()))
-            .collect();
-        // Clamp selection
-        if self.items.is_empty() {
-            self.list_state.select(None);
-        } else if let Some(sel) = self.list_state.selected() {
-            if sel >= self.items.len() {
-                self.list_state.select(Some(self.items.len() - 1));
-            }
-        } else {
-            self.list_state.select(Some(0));
-        }
+        self.items.set_entries(
+            items
+                .iter()
+                .map(|item| ListEntry {
+                    id: item.id,
+                    title: item.title.clone(),
+                    username: item.username.clone(),
+                    badge: url::domain_initial(&item.url),
+                })
+                .collect(),
+        );
     }
 
     pub fn selected_item_id(&self) -> Option<Uuid> {
-        self.list_state
-            .selected()
-            .and_then(|i| self.items.get(i).map(|(id, _, _)| *id))
+        self.items.selected_key()
+    }
+
+    /// Select the item with `id`; false if it is not listed.
+    pub fn select_item(&mut self, id: Uuid) -> bool {
+        self.items.select_key(id)
     }
 
     pub fn search_query(&self) -> &str {
         &self.search_query
     }
 
-    fn move_up(&mut self) {
-        if let Some(i) = self.list_state.selected() {
-            if i > 0 {
-                self.list_state.select(Some(i - 1));
-            }
-        }
+    pub fn tag_filter(&self) -> Option<&str> {
+        self.tag_filter.as_deref()
     }
 
-    fn move_down(&mut self) {
-        if let Some(i) = self.list_state.selected() {
-            if i + 1 < self.items.len() {
-                self.list_state.select(Some(i + 1));
-            }
-        }
+    pub fn totp_only(&self) -> bool {
+        self.totp_only
+    }
+
+    pub fn toggle_totp_only(&mut self) {
+        self.totp_only = !self.totp_only;
+    }
+
+    pub fn item_count(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Drop the search query and any tag restriction.
+    pub fn clear_search(&mut self) {
+        self.search_active = false;
+        self.search_query.clear();
+        self.tag_filter = None;
+    }
+
+    /// Replace the active search with a saved filter's query and tag.
+    pub fn set_filter(&mut self, query: String, tag: Option<String>) {
+        self.search_active = false;
+        self.search_query = query;
+        self.tag_filter = tag;
     }
 }
 
@@ -101,6 +151,7 @@ impl Component for ItemsPanel {
                 KeyCode::Esc => {
                     self.search_active = false;
                     self.search_query.clear();
+                    self.tag_filter = None;
                     return Action::ClearSearch;
                 }
                 KeyCode::Enter => {
@@ -126,15 +177,46 @@ impl Component for ItemsPanel {
                 Action::None
             }
             KeyCode::Char('j') | KeyCode::Down => {
-                self.move_down();
+                self.items.move_down();
                 Action::SelectItem(self.selected_item_id())
             }
             KeyCode::Char('k') | KeyCode::Up => {
-                self.move_up();
+                self.items.move_up();
                 Action::SelectItem(self.selected_item_id())
             }
-            KeyCode::Enter => Action::SelectItem(self.selected_item_id()),
+            KeyCode::Enter => match self.selected_item_id() {
+                Some(id) => match self.enter_action {
+                    ItemEnterAction::Details => Action::ShowDetailsFullScreen(id),
+                    ItemEnterAction::Edit => Action::OpenEditItemForm(id),
+                    ItemEnterAction::CopyPassword => Action::CopyPassword(id),
+                },
+                None => Action::None,
+            },
+            KeyCode::Char('K') => match self.selected_item_id() {
+                Some(id) => Action::MoveItemUp(id),
+                None => Action::None,
+            },
+            KeyCode::Char('J') => match self.selected_item_id() {
+                Some(id) => Action::MoveItemDown(id),
+                None => Action::None,
+            },
+            KeyCode::Char('S') => Action::SaveCurrentFilter,
+            KeyCode::Char('T') => Action::ToggleTotpView,
             KeyCode::Char('n') => Action::OpenNewItemForm,
+            KeyCode::Char('c') => {
+                if let Some(id) = self.selected_item_id() {
+                    Action::CopyCredentialSequence(id)
+                } else {
+                    Action::None
+                }
+            }
+            KeyCode::Char('y') => {
+                if let Some(id) = self.selected_item_id() {
+                    Action::CopyFormattedItem(id)
+                } else {
+                    Action::None
+                }
+            }
             KeyCode::Char('e') => {
                 if let Some(id) = self.selected_item_id() {
                     Action::OpenEditItemForm(id)
@@ -161,8 +243,15 @@ impl Component for ItemsPanel {
         .split(area);
 
         // Search bar
+        let mut search_title = " Search ".to_string();
+        if let Some(ref tag) = self.tag_filter {
+            search_title.push_str(&format!("[tag:{tag}] "));
+        }
+        if self.totp_only {
+            search_title.push_str("[2FA] ");
+        }
         let search_block = Block::default()
-            .title(" Search ")
+            .title(search_title)
             .borders(Borders::ALL)
             .border_style(theme::style_border(self.search_active));
 
@@ -183,20 +272,27 @@ impl Component for ItemsPanel {
         let search_para = Paragraph::new(search_display).block(search_block);
         frame.render_widget(search_para, chunks[0]);
 
-        // Item list
+        // Item list: borders, highlight symbol and badge take 8 columns per row.
+        let row_width = (chunks[1].width as usize).saturating_sub(8);
         let items: Vec<ListItem> = self
             .items
+            .entries()
             .iter()
-            .map(|(_, title, username)| {
-                let line = if username.is_empty() {
-                    Line::from(Span::raw(title.as_str()))
-                } else {
-                    Line::from(vec![
-                        Span::raw(title.as_str()),
-                        Span::styled(format!("  {username}"), theme::style_muted()),
-                    ])
+            .map(|entry| {
+                let badge = match entry.badge {
+                    Some(c) => Span::styled(format!("[{c}] "), theme::style_accent()),
+                    None => Span::styled("[·] ", theme::style_muted()),
                 };
-                ListItem::new(line)
+                let title = truncate_to_width(&entry.title, row_width);
+                let remaining = row_width.saturating_sub(title.width() + 2);
+                let mut spans = vec![badge, Span::raw(title)];
+                if !entry.username.is_empty() && remaining > 1 {
+                    spans.push(Span::styled(
+                        format!("  {}", truncate_to_width(&entry.username, remaining)),
+                        theme::style_muted(),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
             })
             .collect();
 
@@ -211,7 +307,105 @@ impl Component for ItemsPanel {
             .highlight_style(theme::style_selected())
             .highlight_symbol("▸ ");
 
-        let mut state = self.list_state.clone();
+        let mut state = self.items.state();
         frame.render_stateful_widget(list, chunks[1], &mut state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_items_derives_badges() {
+        let mut with_url = Item::new("GitHub".to_string(), None);
+        with_url.url = "https://www.github.com/x".to_string();
+        let without_url = Item::new("Notes".to_string(), None);
+
+        let mut panel = ItemsPanel::new();
+        panel.update_items(&[&with_url, &without_url]);
+
+        assert_eq!(panel.items.entries()[0].badge, Some('G'));
+        assert_eq!(panel.items.entries()[1].badge, None);
+    }
+
+    #[test]
+    fn test_long_titles_are_truncated_in_rows() {
+        let mut item = Item::new("x".repeat(200), None);
+        item.username = "someone".to_string();
+        let mut panel = ItemsPanel::new();
+        panel.update_items(&[&item]);
+
+        let row = &crate::ui::render_rows(30, 6, |f| panel.render(f, f.area()))[4];
+
+        assert!(row.contains('…'));
+        assert!(row.ends_with('│'));
+        // The stored title stays intact for copying and editing.
+        assert_eq!(panel.items.entries()[0].title.len(), 200);
+    }
+
+    #[test]
+    fn test_resorting_keeps_selected_item() {
+        let alpha = Item::new("Alpha".to_string(), None);
+        let beta = Item::new("Beta".to_string(), None);
+        let gamma = Item::new("Gamma".to_string(), None);
+
+        let mut panel = ItemsPanel::new();
+        panel.set_focused(true);
+        panel.update_items(&[&alpha, &beta, &gamma]);
+        panel.handle_key(KeyEvent::from(KeyCode::Down));
+        assert_eq!(panel.selected_item_id(), Some(beta.id));
+
+        panel.update_items(&[&gamma, &beta, &alpha]);
+        assert_eq!(panel.selected_item_id(), Some(beta.id));
+
+        // Filtering the selection away keeps the cursor at the same position.
+        panel.update_items(&[&gamma, &alpha]);
+        assert_eq!(panel.selected_item_id(), Some(alpha.id));
+    }
+
+    #[test]
+    fn test_move_down_at_last_item_wraps_only_when_enabled() {
+        let alpha = Item::new("Alpha".to_string(), None);
+        let beta = Item::new("Beta".to_string(), None);
+
+        let mut panel = ItemsPanel::new();
+        panel.set_focused(true);
+        panel.update_items(&[&alpha, &beta]);
+        panel.handle_key(KeyEvent::from(KeyCode::Char('j')));
+        panel.handle_key(KeyEvent::from(KeyCode::Char('j')));
+        assert_eq!(panel.selected_item_id(), Some(beta.id));
+
+        panel.set_wraparound(true);
+        panel.handle_key(KeyEvent::from(KeyCode::Char('j')));
+        assert_eq!(panel.selected_item_id(), Some(alpha.id));
+        panel.handle_key(KeyEvent::from(KeyCode::Char('k')));
+        assert_eq!(panel.selected_item_id(), Some(beta.id));
+    }
+
+    #[test]
+    fn test_enter_emits_configured_action() {
+        let alpha = Item::new("Alpha".to_string(), None);
+        let enter = KeyEvent::from(KeyCode::Enter);
+
+        let mut panel = ItemsPanel::new();
+        panel.set_focused(true);
+        assert!(matches!(panel.handle_key(enter), Action::None));
+
+        panel.update_items(&[&alpha]);
+        assert!(matches!(
+            panel.handle_key(enter),
+            Action::ShowDetailsFullScreen(id) if id == alpha.id
+        ));
+        panel.set_enter_action(ItemEnterAction::Edit);
+        assert!(matches!(
+            panel.handle_key(enter),
+            Action::OpenEditItemForm(id) if id == alpha.id
+        ));
+        panel.set_enter_action(ItemEnterAction::CopyPassword);
+        assert!(matches!(
+            panel.handle_key(enter),
+            Action::CopyPassword(id) if id == alpha.id
+        ));
+    }
+}
@@ -1,16 +1,25 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 use ratatui::Frame;
 
+use crate::core::password_generator;
 use crate::ui::theme;
 use crate::ui::{Action, Component};
 
 pub struct LockScreen {
     password_input: String,
     error_message: Option<String>,
+    /// Non-error feedback, shown where errors are.
+    notice: Option<String>,
     vault_exists: bool,
+    /// Passphrase suggested with Ctrl+G when creating a vault; shown, never filled in.
+    suggestion: Option<String>,
+    passphrase_words: usize,
+    /// Ctrl+R may unmask the typed password.
+    allow_show_password: bool,
+    show_password: bool,
 }
 
 impl LockScreen {
@@ -18,17 +27,40 @@ impl LockScreen {
         Self {
             password_input: String::new(),
             error_message: None,
+            notice: None,
             vault_exists,
+            suggestion: None,
+            passphrase_words: 7,
+            allow_show_password: false,
+            show_password: false,
         }
     }
 
     pub fn set_error(&mut self, msg: String) {
+        self.notice = None;
         self.error_message = Some(msg);
     }
 
+    pub fn set_notice(&mut self, msg: String) {
+        self.error_message = None;
+        self.notice = Some(msg);
+    }
+
+    pub fn set_passphrase_words(&mut self, words: usize) {
+        self.passphrase_words = words.max(1);
+    }
+
+    pub fn set_allow_show_password(&mut self, allow: bool) {
+        self.allow_show_password = allow;
+        self.show_password &= allow;
+    }
+
     pub fn clear(&mut self) {
         self.password_input.clear();
+        self.show_password = false;
         self.error_message = None;
+        self.notice = None;
+        self.suggestion = None;
     }
 
     pub fn set_vault_exists(&mut self, exists: bool) {
@@ -40,6 +72,21 @@ impl Component for LockScreen {
     fn handle_key(&mut self, key: KeyEvent) -> Action {
         match (key.code, key.modifiers) {
             (KeyCode::Char('c'), KeyModifiers::CONTROL) => Action::Quit,
+            (KeyCode::Char('g'), KeyModifiers::CONTROL) if !self.vault_exists => {
+                self.suggestion = Some(password_generator::generate_passphrase(
+                    self.passphrase_words,
+                ));
+                self.notice = None;
+                Action::None
+            }
+            (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
+                self.show_password = self.allow_show_password && !self.show_password;
+                Action::None
+            }
+            (KeyCode::Char('y'), KeyModifiers::CONTROL) => match &self.suggestion {
+                Some(suggestion) => Action::CopyPassphraseSuggestion(suggestion.clone()),
+                None => Action::None,
+            },
             (KeyCode::Enter, _) => {
                 if self.password_input.is_empty() {
                     self.error_message = Some("Password cannot be empty".to_string());
@@ -47,6 +94,8 @@ impl Component for LockScreen {
                 } else {
                     let pw = self.password_input.clone();
                     self.error_message = None;
+                    self.notice = None;
+                    self.show_password = false;
                     if self.vault_exists {
                         Action::UnlockVault(pw)
                     } else {
@@ -74,7 +123,8 @@ impl Component for LockScreen {
 
         // Center a box in the middle of the screen
         let box_width = 50u16.min(area.width.saturating_sub(4));
-        let box_height = 10u16.min(area.height.saturating_sub(2));
+        let suggestion_height = if self.suggestion.is_some() { 4 } else { 0 };
+        let box_height = (10 + suggestion_height).min(area.height.saturating_sub(2));
 
         let vertical = Layout::vertical([Constraint::Length(box_height)]).flex(Flex::Center);
         let horizontal = Layout::horizontal([Constraint::Length(box_width)]).flex(Flex::Center);
@@ -102,7 +152,8 @@ impl Component for LockScreen {
             Constraint::Length(1), // Label
             Constraint::Length(3), // Password input
             Constraint::Length(1), // Error message
-            Constraint::Min(0),    // Hint
+            Constraint::Length(suggestion_height),
+            Constraint::Min(0), // Hint
         ])
         .split(inner);
 
@@ -121,16 +172,29 @@ impl Component for LockScreen {
         let label_para = Paragraph::new(label).style(theme::style_default());
         frame.render_widget(label_para, chunks[2]);
 
-        // Password input (masked)
-        let masked: String = "•".repeat(self.password_input.len());
+        // Password input, masked unless shown with Ctrl+R
         let display = if self.password_input.is_empty() {
             Span::styled("type your password...", theme::style_muted())
+        } else if self.show_password {
+            Span::styled(self.password_input.as_str(), theme::style_warning())
         } else {
-            Span::styled(masked, theme::style_default())
+            Span::styled(
+                "•".repeat(self.password_input.len()),
+                theme::style_default(),
+            )
         };
-        let input_block = Block::default()
+        let mut input_block = Block::default()
             .borders(Borders::ALL)
             .border_style(theme::style_border(true));
+        if self.allow_show_password {
+            let toggle = if self.show_password {
+                " Ctrl+R hide "
+            } else {
+                " Ctrl+R show "
+            };
+            input_block = input_block
+                .title_bottom(Line::styled(toggle, theme::style_muted()).right_aligned());
+        }
         let input = Paragraph::new(Line::from(display)).block(input_block);
         frame.render_widget(input, chunks[3]);
 
@@ -138,12 +202,115 @@ impl Component for LockScreen {
         if let Some(ref err) = self.error_message {
             let err_para = Paragraph::new(err.as_str()).style(theme::style_error());
             frame.render_widget(err_para, chunks[4]);
+        } else if let Some(ref notice) = self.notice {
+            let notice_para = Paragraph::new(notice.as_str()).style(theme::style_success());
+            frame.render_widget(notice_para, chunks[4]);
+        }
+
+        // Suggested passphrase, which only counts once typed into the field above
+        if let Some(ref suggestion) = self.suggestion {
+            let lines = vec![
+                Line::from(vec![
+                    Span::styled("Suggestion: ", theme::style_muted()),
+                    Span::styled(suggestion.as_str(), theme::style_accent()),
+                ]),
+                Line::styled(
+                    "Memorize it, then type it above · Ctrl+Y copy",
+                    theme::style_muted(),
+                ),
+            ];
+            let para = Paragraph::new(lines).wrap(Wrap { trim: false });
+            frame.render_widget(para, chunks[5]);
         }
 
         // Hint
-        let hint = Paragraph::new("Enter ↵ submit  |  Esc/Ctrl+C quit")
+        let hint_text = if self.vault_exists {
+            "Enter ↵ submit  |  Esc/Ctrl+C quit"
+        } else {
+            "Enter ↵ submit  |  Ctrl+G suggest  |  Esc quit"
+        };
+        let hint = Paragraph::new(hint_text)
             .alignment(Alignment::Center)
             .style(theme::style_muted());
-        frame.render_widget(hint, chunks[5]);
+        frame.render_widget(hint, chunks[6]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    #[test]
+    fn test_suggested_passphrase_is_shown_but_not_submitted() {
+        let mut screen = LockScreen::new(false);
+        screen.set_passphrase_words(5);
+        assert!(matches!(screen.handle_key(ctrl('y')), Action::None));
+
+        screen.handle_key(ctrl('g'));
+        let suggestion = screen.suggestion.clone().unwrap();
+        assert_eq!(suggestion.split('-').count(), 5);
+        assert!(matches!(
+            screen.handle_key(ctrl('y')),
+            Action::CopyPassphraseSuggestion(copied) if copied == suggestion
+        ));
+
+        // Never filled in: Enter only submits what was typed.
+        assert!(screen.password_input.is_empty());
+        assert!(matches!(
+            screen.handle_key(KeyEvent::from(KeyCode::Enter)),
+            Action::None
+        ));
+        for c in suggestion.chars() {
+            screen.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+        assert!(matches!(
+            screen.handle_key(KeyEvent::from(KeyCode::Enter)),
+            Action::CreateVault(pw) if pw == suggestion
+        ));
+    }
+
+    fn render_to_text(screen: &LockScreen) -> String {
+        crate::ui::render_rows(60, 20, |f| screen.render(f, f.area())).concat()
+    }
+
+    #[test]
+    fn test_show_password_toggle_unmasks_typed_text() {
+        let mut screen = LockScreen::new(true);
+        for c in "HUNTER2".chars() {
+            screen.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+        assert!(!render_to_text(&screen).contains("HUNTER2"));
+
+        // Without the option Ctrl+R does not unmask.
+        screen.handle_key(ctrl('r'));
+        assert!(!screen.show_password);
+        assert_eq!(screen.password_input, "HUNTER2");
+
+        screen.set_allow_show_password(true);
+        screen.handle_key(ctrl('r'));
+        let text = render_to_text(&screen);
+        assert!(text.contains("HUNTER2"));
+        assert!(text.contains("Ctrl+R hide"));
+
+        screen.handle_key(ctrl('r'));
+        let text = render_to_text(&screen);
+        assert!(!text.contains("HUNTER2"));
+        assert!(text.contains("•••••••"));
+
+        // Hidden again once submitted.
+        screen.handle_key(ctrl('r'));
+        screen.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(!screen.show_password);
+    }
+
+    #[test]
+    fn test_no_suggestions_when_unlocking() {
+        let mut screen = LockScreen::new(true);
+        screen.handle_key(ctrl('g'));
+        assert!(screen.suggestion.is_none());
     }
 }
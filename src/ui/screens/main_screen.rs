@@ -1,12 +1,15 @@
+use std::collections::VecDeque;
 use std::time::Instant;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::Style;
 use ratatui::text::{Line, Span};
-use ratatui::widgets::Paragraph;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 use uuid::Uuid;
 
+use crate::config::StatusVerbosity;
 use crate::core::models::{Group, Item};
 use crate::ui::panels::details_panel::DetailsPanel;
 use crate::ui::panels::groups_panel::GroupsPanel;
@@ -21,15 +24,70 @@ enum Pane {
     Details,
 }
 
+/// Severity of a status-bar message, controlling its style and how long it stays visible.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl StatusLevel {
+    fn display_secs(self) -> u64 {
+        match self {
+            StatusLevel::Info | StatusLevel::Success => STATUS_DISPLAY_SECS,
+            StatusLevel::Warning => WARNING_DISPLAY_SECS,
+            StatusLevel::Error => ERROR_DISPLAY_SECS,
+        }
+    }
+
+    pub(crate) fn style(self) -> Style {
+        match self {
+            StatusLevel::Info => theme::style_muted(),
+            StatusLevel::Success => theme::style_success(),
+            StatusLevel::Warning => theme::style_warning(),
+            StatusLevel::Error => theme::style_error(),
+        }
+    }
+}
+
+struct StatusMessage {
+    text: String,
+    level: StatusLevel,
+    set_at: Instant,
+}
+
 pub struct MainScreen {
     pub groups_panel: GroupsPanel,
     pub items_panel: ItemsPanel,
     pub details_panel: DetailsPanel,
     active_pane: Pane,
-    status_message: Option<(String, Instant)>,
+    status_message: Option<StatusMessage>,
+    /// Messages waiting for the current one to expire, shown in arrival order.
+    pending_status: VecDeque<(StatusLevel, String)>,
+    /// Messages raised since `App` last copied them into its event log.
+    unlogged: Vec<(StatusLevel, String)>,
+    /// Whether the vault has unsaved changes, refreshed by `App` before each frame.
+    dirty: bool,
+    /// A long-running operation's progress, shown instead of the status message and
+    /// never logged.
+    progress: Option<String>,
+    /// Extra key that locks the vault, in addition to Ctrl+L.
+    lock_key: Option<char>,
+    verbosity: StatusVerbosity,
+    /// Whether the vault has no items or groups, refreshed by `App`.
+    vault_empty: bool,
+    /// Set by the first key press over the onboarding hints; a new screen shows them again.
+    onboarding_dismissed: bool,
+    /// The details panel fills the window until Esc or a pane switch.
+    details_full_screen: bool,
 }
 
 const STATUS_DISPLAY_SECS: u64 = 3;
+const WARNING_DISPLAY_SECS: u64 = 5;
+const ERROR_DISPLAY_SECS: u64 = 8;
+const MAX_PENDING_STATUS: usize = 4;
 
 impl Default for MainScreen {
     fn default() -> Self {
@@ -48,22 +106,166 @@ impl MainScreen {
             details_panel: DetailsPanel::new(),
             active_pane: Pane::Groups,
             status_message: None,
+            pending_status: VecDeque::new(),
+            unlogged: Vec::new(),
+            dirty: false,
+            progress: None,
+            lock_key: None,
+            verbosity: StatusVerbosity::Verbose,
+            vault_empty: false,
+            onboarding_dismissed: false,
+            details_full_screen: false,
         }
     }
 
+    /// Focus the details panel and draw it over the whole main area.
+    pub fn show_details_full_screen(&mut self) {
+        self.details_full_screen = true;
+        self.active_pane = Pane::Details;
+        self.update_focus();
+    }
+
+    pub fn set_vault_empty(&mut self, empty: bool) {
+        self.vault_empty = empty;
+    }
+
+    /// First-step hints are shown over an empty vault until a key is pressed.
+    fn shows_onboarding(&self) -> bool {
+        self.vault_empty && !self.onboarding_dismissed
+    }
+
+    fn render_onboarding(&self, frame: &mut Frame, area: Rect) {
+        let key_line = |key: &'static str, text: &'static str| {
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled(key, theme::style_accent()),
+                Span::raw(text),
+            ])
+        };
+        let lines = vec![
+            Line::raw(""),
+            Line::styled("  Your vault is empty.", theme::style_default()),
+            Line::raw(""),
+            key_line("Tab", " to the Items pane, then"),
+            key_line("n", " to add your first item"),
+            key_line("g", " in the Groups pane adds a group"),
+            key_line("Ctrl+L", " locks the vault"),
+            Line::raw(""),
+            Line::styled("  Any key closes this.", theme::style_muted()),
+        ];
+
+        let width = 44u16.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let [v] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [center] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(v);
+
+        let block = Block::default()
+            .title(" Welcome ")
+            .title_style(theme::style_title(true))
+            .borders(Borders::ALL)
+            .border_style(theme::style_border(true));
+        frame.render_widget(Clear, center);
+        frame.render_widget(Paragraph::new(lines).block(block), center);
+    }
+
+    pub fn set_lock_key(&mut self, key: Option<char>) {
+        self.lock_key = key;
+    }
+
+    pub fn set_status_verbosity(&mut self, verbosity: StatusVerbosity) {
+        self.verbosity = verbosity;
+    }
+
     pub fn set_status(&mut self, msg: String) {
-        self.status_message = Some((msg, Instant::now()));
+        self.push_status(StatusLevel::Info, msg);
+    }
+
+    pub fn set_success(&mut self, msg: String) {
+        self.push_status(StatusLevel::Success, msg);
+    }
+
+    pub fn set_warning(&mut self, msg: String) {
+        self.push_status(StatusLevel::Warning, msg);
+    }
+
+    pub fn set_error(&mut self, msg: String) {
+        self.push_status(StatusLevel::Error, msg);
+    }
+
+    /// Show a status message, queueing it behind the current one if it is still visible.
+    ///
+    /// Messages hidden by the verbosity setting are still handed to the event log.
+    pub fn push_status(&mut self, level: StatusLevel, msg: String) {
+        self.unlogged.push((level, msg.clone()));
+        let shown = match self.verbosity {
+            StatusVerbosity::Verbose => true,
+            StatusVerbosity::ErrorsOnly => level == StatusLevel::Error,
+            StatusVerbosity::Silent => false,
+        };
+        if !shown {
+            return;
+        }
+        if self.status_message.is_none() {
+            self.status_message = Some(StatusMessage {
+                text: msg,
+                level,
+                set_at: Instant::now(),
+            });
+            return;
+        }
+        if self.pending_status.len() >= MAX_PENDING_STATUS {
+            self.pending_status.pop_front();
+        }
+        self.pending_status.push_back((level, msg));
+    }
+
+    /// Messages raised since the last call, oldest first.
+    pub fn take_unlogged(&mut self) -> Vec<(StatusLevel, String)> {
+        std::mem::take(&mut self.unlogged)
     }
 
     pub fn clear_status(&mut self) {
         self.status_message = None;
+        self.pending_status.clear();
+    }
+
+    /// Show (or with `None`, clear) the progress of a long-running operation.
+    pub fn set_progress(&mut self, progress: Option<String>) {
+        self.progress = progress;
+    }
+
+    pub fn progress(&self) -> Option<&str> {
+        self.progress.as_deref()
+    }
+
+    pub fn set_dirty(&mut self, dirty: bool) {
+        self.dirty = dirty;
+    }
+
+    pub fn status_message(&self) -> Option<&str> {
+        self.status_message.as_ref().map(|s| s.text.as_str())
     }
 
-    /// Clear the status message if it has expired.
+    pub fn status_level(&self) -> Option<StatusLevel> {
+        self.status_message.as_ref().map(|s| s.level)
+    }
+
+    /// Expire the current status message and advance to the next queued one.
     pub fn tick(&mut self) {
-        if let Some((_, set_at)) = &self.status_message {
-            if set_at.elapsed().as_secs() >= STATUS_DISPLAY_SECS {
-                self.status_message = None;
+        if let Some(status) = &self.status_message {
+            if status.set_at.elapsed().as_secs() >= status.level.display_secs() {
+                self.status_message =
+                    self.pending_status
+                        .pop_front()
+                        .map(|(level, text)| StatusMessage {
+                            text,
+                            level,
+                            set_at: Instant::now(),
+                        });
             }
         }
     }
@@ -93,6 +295,7 @@ impl MainScreen {
     }
 
     fn cycle_pane_forward(&mut self) {
+        self.details_full_screen = false;
         self.active_pane = match self.active_pane {
             Pane::Groups => Pane::Items,
             Pane::Items => Pane::Details,
@@ -102,6 +305,7 @@ impl MainScreen {
     }
 
     fn cycle_pane_backward(&mut self) {
+        self.details_full_screen = false;
         self.active_pane = match self.active_pane {
             Pane::Groups => Pane::Details,
             Pane::Items => Pane::Groups,
@@ -122,20 +326,51 @@ impl MainScreen {
 
 impl Component for MainScreen {
     fn handle_key(&mut self, key: KeyEvent) -> Action {
+        // The key that closes the onboarding hints still does its usual job.
+        if self.shows_onboarding() {
+            self.onboarding_dismissed = true;
+        }
+
         // Global keys
         match (key.code, key.modifiers) {
             (KeyCode::Char('c'), KeyModifiers::CONTROL) => return Action::Quit,
             (KeyCode::Char('l'), KeyModifiers::CONTROL) => return Action::Lock,
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT)
+                if Some(c) == self.lock_key && !self.items_panel.is_search_active() =>
+            {
+                return Action::Lock;
+            }
             (KeyCode::Char('s'), KeyModifiers::CONTROL) => return Action::Save,
-            (KeyCode::Char('q'), KeyModifiers::NONE) => {
-                // Don't quit if search is active or in details
-                if !self.items_panel.is_search_active() {
-                    return Action::Quit;
-                }
+            (KeyCode::Char('r'), KeyModifiers::CONTROL) => return Action::DiscardChanges,
+            (KeyCode::Char('k'), KeyModifiers::CONTROL) => return Action::OpenCompactConfirm,
+            (KeyCode::Char('f'), KeyModifiers::CONTROL) => return Action::OpenSavedFilters,
+            (KeyCode::Char('o'), KeyModifiers::CONTROL) => return Action::OpenVaultSettings,
+            (KeyCode::Char('g'), KeyModifiers::CONTROL) => return Action::OpenEventLog,
+            (KeyCode::Char('y'), KeyModifiers::CONTROL) => return Action::OpenCopyLog,
+            (KeyCode::Char('d'), KeyModifiers::CONTROL) => return Action::OpenChanges,
+            (KeyCode::Char('p'), KeyModifiers::CONTROL) => return Action::OpenVaultInfo,
+            (KeyCode::Char('I'), KeyModifiers::NONE | KeyModifiers::SHIFT)
+                if !self.items_panel.is_search_active() =>
+            {
+                return Action::OpenImport;
+            }
+            (KeyCode::Char('U'), KeyModifiers::NONE | KeyModifiers::SHIFT)
+                if !self.items_panel.is_search_active() =>
+            {
+                return Action::OpenUndoImportConfirm;
+            }
+            // Don't quit if search is active or in details
+            (KeyCode::Char('q'), KeyModifiers::NONE) if !self.items_panel.is_search_active() => {
+                return Action::Quit;
             }
             _ => {}
         }
 
+        if self.details_full_screen && key.code == KeyCode::Esc {
+            self.details_full_screen = false;
+            return Action::None;
+        }
+
         // Tab navigation (only when search not active)
         if !self.items_panel.is_search_active() {
             match key.code {
@@ -174,15 +409,29 @@ impl Component for MainScreen {
         ])
         .split(chunks[0]);
 
-        self.groups_panel.render(frame, panes[0]);
-        self.items_panel.render(frame, panes[1]);
-        self.details_panel.render(frame, panes[2]);
+        if self.details_full_screen {
+            self.details_panel.render(frame, chunks[0]);
+        } else {
+            self.groups_panel.render(frame, panes[0]);
+            self.items_panel.render(frame, panes[1]);
+            self.details_panel.render(frame, panes[2]);
+        }
+        if self.shows_onboarding() {
+            self.render_onboarding(frame, chunks[0]);
+        }
 
         // Status bar
-        let status_text = if let Some((ref msg, _)) = self.status_message {
+        let mut status_text = if let Some(ref progress) = self.progress {
+            Line::from(vec![
+                Span::styled(" ", theme::style_default()),
+                Span::styled(progress.as_str(), theme::style_accent()),
+                Span::styled("  Esc", theme::style_accent()),
+                Span::raw(" cancel"),
+            ])
+        } else if let Some(ref status) = self.status_message {
             Line::from(vec![
                 Span::styled(" ", theme::style_default()),
-                Span::raw(msg.as_str()),
+                Span::styled(status.text.as_str(), status.level.style()),
             ])
         } else {
             Line::from(vec![
@@ -200,8 +449,120 @@ impl Component for MainScreen {
                 Span::raw(" quit"),
             ])
         };
+        if self.dirty {
+            status_text
+                .spans
+                .insert(0, Span::styled("*", theme::style_warning()));
+        }
 
         let status = Paragraph::new(status_text).style(theme::style_muted());
         frame.render_widget(status, chunks[1]);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn render_status_row(screen: &MainScreen) -> String {
+        let mut rows = crate::ui::render_rows(80, 10, |frame| screen.render(frame, frame.area()));
+        rows.pop().unwrap()
+    }
+
+    fn expire_current(screen: &mut MainScreen) {
+        if let Some(status) = screen.status_message.as_mut() {
+            status.set_at -= Duration::from_secs(status.level.display_secs());
+        }
+    }
+
+    #[test]
+    fn test_status_queue_shows_messages_in_order() {
+        let mut screen = MainScreen::new();
+        screen.set_status("first".to_string());
+        screen.set_status("second".to_string());
+        screen.set_status("third".to_string());
+
+        assert_eq!(screen.status_message(), Some("first"));
+        screen.tick();
+        assert_eq!(screen.status_message(), Some("first"));
+
+        expire_current(&mut screen);
+        screen.tick();
+        assert_eq!(screen.status_message(), Some("second"));
+
+        expire_current(&mut screen);
+        screen.tick();
+        assert_eq!(screen.status_message(), Some("third"));
+
+        expire_current(&mut screen);
+        screen.tick();
+        assert_eq!(screen.status_message(), None);
+        assert!(screen.pending_status.is_empty());
+    }
+
+    #[test]
+    fn test_status_queue_drops_oldest_when_full() {
+        let mut screen = MainScreen::new();
+        screen.set_status("current".to_string());
+        for i in 0..MAX_PENDING_STATUS + 2 {
+            screen.set_status(format!("msg {i}"));
+        }
+        assert_eq!(screen.pending_status.len(), MAX_PENDING_STATUS);
+        assert_eq!(
+            screen.pending_status.front().map(|(_, msg)| msg.as_str()),
+            Some("msg 2")
+        );
+    }
+
+    #[test]
+    fn test_dirty_indicator_rendered_only_when_dirty() {
+        let mut screen = MainScreen::new();
+        assert!(!render_status_row(&screen).starts_with('*'));
+
+        screen.set_dirty(true);
+        assert!(render_status_row(&screen).starts_with('*'));
+
+        screen.set_dirty(false);
+        assert!(!render_status_row(&screen).starts_with('*'));
+    }
+
+    #[test]
+    fn test_error_status_styled_and_kept_longer() {
+        let mut screen = MainScreen::new();
+        screen.set_error("Save failed".to_string());
+        assert_eq!(screen.status_level(), Some(StatusLevel::Error));
+        assert_eq!(StatusLevel::Error.style(), theme::style_error());
+        assert!(StatusLevel::Error.display_secs() > StatusLevel::Info.display_secs());
+
+        // Still visible once an info message would have expired.
+        if let Some(status) = screen.status_message.as_mut() {
+            status.set_at -= Duration::from_secs(STATUS_DISPLAY_SECS);
+        }
+        screen.tick();
+        assert_eq!(screen.status_message(), Some("Save failed"));
+
+        expire_current(&mut screen);
+        screen.tick();
+        assert_eq!(screen.status_message(), None);
+    }
+
+    #[test]
+    fn test_errors_only_suppresses_success_but_not_errors() {
+        let mut screen = MainScreen::new();
+        screen.set_status_verbosity(StatusVerbosity::ErrorsOnly);
+        screen.set_success("Item created".to_string());
+        screen.set_warning("Large file".to_string());
+        assert_eq!(screen.status_message(), None);
+
+        screen.set_error("Save failed".to_string());
+        assert_eq!(screen.status_message(), Some("Save failed"));
+        // Hidden messages still reach the event log.
+        assert_eq!(screen.take_unlogged().len(), 3);
+
+        screen.clear_status();
+        screen.set_status_verbosity(StatusVerbosity::Silent);
+        screen.set_error("Save failed".to_string());
+        assert_eq!(screen.status_message(), None);
+    }
+}
@@ -0,0 +1,81 @@
+use unicode_width::UnicodeWidthChar;
+
+const ELLIPSIS: char = '…';
+
+/// Shorten `s` to at most `width` terminal columns, ending in an ellipsis when cut.
+///
+/// Widths are measured per character, so wide (e.g. CJK) glyphs count as two
+/// columns and are never split.
+pub fn truncate_to_width(s: &str, width: usize) -> String {
+    let total: usize = s.chars().map(|c| c.width().unwrap_or(0)).sum();
+    if total <= width {
+        return s.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+
+    // Reserve one column for the ellipsis.
+    let budget = width - 1;
+    let mut used = 0;
+    let mut out = String::new();
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        used += w;
+        out.push(c);
+    }
+    out.push(ELLIPSIS);
+    out
+}
+
+/// Whether `s` starts or ends with whitespace, which usually means a pasting accident.
+pub fn has_edge_whitespace(s: &str) -> bool {
+    s.starts_with(char::is_whitespace) || s.ends_with(char::is_whitespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_width::UnicodeWidthStr;
+
+    #[test]
+    fn test_short_strings_are_untouched() {
+        assert_eq!(truncate_to_width("github", 10), "github");
+        assert_eq!(truncate_to_width("", 0), "");
+    }
+
+    #[test]
+    fn test_exact_width_boundary() {
+        assert_eq!(truncate_to_width("abcde", 5), "abcde");
+        assert_eq!(truncate_to_width("abcdef", 5), "abcd…");
+        assert_eq!(truncate_to_width("abcdef", 1), "…");
+        assert_eq!(truncate_to_width("abcdef", 0), "");
+    }
+
+    #[test]
+    fn test_multibyte_and_wide_characters() {
+        // Accented characters are multibyte but one column wide.
+        assert_eq!(truncate_to_width("café-crème", 6), "café-…");
+
+        // CJK glyphs are two columns wide and must not be split.
+        let cut = truncate_to_width("日本語のタイトル", 6);
+        assert_eq!(cut, "日本…");
+        assert!(cut.width() <= 6);
+
+        let cut = truncate_to_width("日本語", 5);
+        assert_eq!(cut, "日本…");
+        assert_eq!(truncate_to_width("日本語", 6), "日本語");
+    }
+
+    #[test]
+    fn test_has_edge_whitespace() {
+        assert!(has_edge_whitespace("secret\n"));
+        assert!(has_edge_whitespace(" secret"));
+        assert!(has_edge_whitespace("secret\t"));
+        assert!(!has_edge_whitespace("sec ret"));
+        assert!(!has_edge_whitespace(""));
+    }
+}
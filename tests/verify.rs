@@ -0,0 +1,81 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+use tempfile::TempDir;
+use vaultura::core::models::KdfParams;
+use vaultura::core::vault_service::{ItemDraft, VaultService};
+
+fn create_vault(path: &Path) {
+    let params = KdfParams {
+        memory_cost_kib: 1024,
+        time_cost: 1,
+        parallelism: 1,
+    };
+    let mut svc = VaultService::new(path.to_path_buf(), params);
+    svc.create("password").unwrap();
+    let group = svc.create_group("Work".to_string(), None).unwrap();
+    svc.create_item(ItemDraft {
+        title: "GitHub".to_string(),
+        group_id: Some(group),
+        ..Default::default()
+    })
+    .unwrap();
+    svc.save().unwrap();
+}
+
+fn verify(path: &Path, password: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_vaultura"))
+        .arg("verify")
+        .arg("--vault")
+        .arg(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    writeln!(child.stdin.take().unwrap(), "{password}").unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn verify_accepts_good_vault() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("good.vltr");
+    create_vault(&path);
+
+    let output = verify(&path, "password");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("OK"), "{stdout}");
+    assert!(stdout.contains("1 groups, 1 items"), "{stdout}");
+}
+
+#[test]
+fn verify_rejects_corrupted_vault() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("bad.vltr");
+    create_vault(&path);
+
+    let mut data = fs::read(&path).unwrap();
+    data[0] = b'X';
+    fs::write(&path, data).unwrap();
+
+    let output = verify(&path, "password");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Invalid magic bytes"), "{stderr}");
+}
+
+#[test]
+fn verify_rejects_wrong_password() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("good.vltr");
+    create_vault(&path);
+
+    let output = verify(&path, "nope");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Wrong master password"), "{stderr}");
+}